@@ -22,12 +22,12 @@ impl Counter {
 }
 
 fn main() {
-    println!("=== Example WITHOUT locks (AtomicPtr) ===");
+    println!("=== Example WITHOUT locks (ArcSwap) ===");
 
     // Create ArcThreadShare directly (without RwLock!)
     let counter = ArcThreadShare::new(Counter::new());
 
-    // Create several threads that work with one AtomicPtr
+    // Create several threads that work with one ArcSwap
     let mut handles = vec![];
 
     for thread_id in 0..5 {
@@ -64,5 +64,5 @@ fn main() {
 
     let final_value = counter.get();
     println!("\nFinal value: {}", final_value.get_value());
-    println!("✅ Successfully used AtomicPtr WITHOUT locks!");
+    println!("✅ Successfully used ArcSwap WITHOUT locks!");
 }
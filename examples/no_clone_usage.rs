@@ -24,16 +24,15 @@ impl GameState {
 fn main() {
     println!("=== Example WITHOUT cloning ===");
 
-    // Option 1: WITHOUT locks (AtomicPtr) - faster but less safe
-    println!("\n--- Option 1: WITHOUT locks (AtomicPtr) ---");
-    let game_state = share!(GameState::new());
+    // Option 1: WITHOUT locks (ArcSwap) - lock-free reads, copy-on-write updates
+    println!("\n--- Option 1: WITHOUT locks (ArcSwap) ---");
+    let game_state = ArcThreadShare::new(GameState::new());
 
-    // Get Arc<AtomicPtr<T>> and create ArcThreadShare for thread
-    let arc_data = game_state.as_arc();
-    let thread_share = ArcThreadShare::from_arc(arc_data.clone());
+    // Share the same Arc<ArcSwap<T>> with the thread
+    let thread_share = ArcThreadShare::from_arc(game_state.data.clone());
 
     // Create clone for main thread
-    let thread_share_main = ArcThreadShare::from_arc(arc_data);
+    let thread_share_main = game_state.clone();
 
     // Thread for updating score - WITHOUT locks!
     let score_handle = thread::spawn(move || {
@@ -42,19 +41,19 @@ fn main() {
             thread_share.update(|state: &mut GameState| {
                 state.add_score(25);
                 println!(
-                    "Score thread (AtomicPtr): +25 points, current score: {}",
+                    "Score thread (ArcSwap): +25 points, current score: {}",
                     state.score
                 );
             });
         }
     });
 
-    // Main thread reads values from AtomicPtr version
+    // Main thread reads values from ArcSwap version
     for _ in 0..5 {
         thread::sleep(Duration::from_millis(300));
         let current_state = thread_share_main.get();
         println!(
-            "Player (AtomicPtr): Level {}, Score: {}",
+            "Player (ArcSwap): Level {}, Score: {}",
             current_state.level, current_state.score
         );
     }
@@ -100,10 +99,10 @@ fn main() {
     let final_state_atomic = game_state.get();
     let final_state_locked = game_state_locked.get();
 
-    println!("AtomicPtr (without locks): {:?}", final_state_atomic);
+    println!("ArcSwap (without locks): {:?}", final_state_atomic);
     println!("RwLock (with locks): {:?}", final_state_locked);
 
     println!("\n✅ Successfully used library WITHOUT cloning!");
-    println!("🎯 AtomicPtr - faster but less safe");
+    println!("🎯 ArcSwap - lock-free reads, copy-on-write updates");
     println!("🔒 RwLock - slower but safer");
 }
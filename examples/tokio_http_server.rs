@@ -173,7 +173,7 @@ async fn handle_connection(
     
     // Read request
     let n = match stream.read(&mut buffer).await {
-        Ok(n) if n == 0 => return, // Connection closed
+        Ok(0) => return, // Connection closed
         Ok(n) => n,
         Err(e) => {
             eprintln!("❌ Error reading from stream: {}", e);
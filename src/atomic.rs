@@ -1,95 +1,85 @@
 //! # Atomic Module - ArcThreadShare<T>
 //!
-//! This module provides `ArcThreadShare<T>`, a high-performance structure for
-//! zero-copy data sharing between threads using atomic operations.
-//!
-//! ## ⚠️ Important Warning
-//!
-//! **`ArcThreadShare<T>` has significant limitations and should be used with caution!**
+//! This module provides `ArcThreadShare<T>`, a lock-free structure for sharing
+//! data between threads, built on `arc_swap::ArcSwap`.
 //!
 //! ## Overview
 //!
-//! `ArcThreadShare<T>` uses `Arc<AtomicPtr<T>>` internally to provide zero-copy
-//! data sharing without locks. While this can offer high performance, it comes
-//! with important trade-offs.
+//! `ArcThreadShare<T>` keeps the current value in an `Arc<T>` that is swapped
+//! atomically. Readers never take a lock and never block writers; writers
+//! never block readers. A value that was replaced is freed only once the last
+//! reader still looking at it is done, so reads can never observe freed
+//! memory.
 //!
 //! ## Key Features
 //!
-//! - **Zero-Copy Operations**: No data cloning during access
-//! - **Atomic Updates**: Uses atomic pointer operations
-//! - **High Performance**: Potentially faster than lock-based approaches
-//! - **Memory Efficiency**: Single copy of data shared across threads
-//!
-//! ## ⚠️ Critical Limitations
-//!
-//! ### 1. **Non-Atomic Complex Operations**
-//! ```rust
-//! use thread_share::ArcThreadShare;
+//! - **Lock-Free Reads**: `get()` and `read()` never wait for a writer
+//! - **Atomic Updates**: `update()`, `write()`, `increment()` and `add()` are
+//!   applied atomically; concurrent writers never lose each other's changes
+//! - **Safe Reclamation**: old values are dropped only when no reader can
+//!   still observe them
+//! - **Ownership**: the data is dropped with the last handle, or taken out of
+//!   it with `try_unwrap()` or `into_inner()`
+//! - **Read-Heavy Workloads**: readers scale with the number of threads
 //!
-//! let arc_share = ArcThreadShare::new(0);
+//! ## How Writes Work
 //!
-//! // ❌ This is NOT atomic and can cause race conditions
-//! arc_share.update(|x| *x = *x + 1);
-//!
-//! // ✅ Use the atomic increment method instead
-//! arc_share.increment();
-//! ```
+//! Writes are **copy-on-write**: `update()` clones the current value, applies
+//! the closure to the clone and publishes it with a compare-and-swap. If
+//! another writer published a value in between, the clone is discarded and
+//! the closure runs again on the newer value:
 //!
-//! **Problem**: The `update` method with complex operations like `+=` is not atomic.
-//! Between reading the value, modifying it, and writing it back, other threads can interfere.
-//!
-//! ### 2. **High Contention Performance Issues**
 //! ```rust
 //! use thread_share::ArcThreadShare;
+//! use std::thread;
 //!
-//! let arc_share = ArcThreadShare::new(0);
+//! let counter = ArcThreadShare::new(0);
 //!
-//! // ❌ High contention can cause significant performance degradation
-//! for _ in 0..10000 {
-//!     arc_share.increment(); // May lose many operations under high contention
+//! let handles: Vec<_> = (0..4)
+//!     .map(|_| {
+//!         let counter = counter.clone();
+//!         thread::spawn(move || {
+//!             for _ in 0..100 {
+//!                 counter.update(|x| *x += 1);
+//!             }
+//!         })
+//!     })
+//!     .collect();
+//! for handle in handles {
+//!     handle.join().unwrap();
 //! }
-//! ```
-//!
-//! **Problem**: Under high contention (many threads updating simultaneously), `AtomicPtr`
-//! operations can lose updates due to:
-//! - Box allocation/deallocation overhead
-//! - CAS (Compare-And-Swap) failures requiring retries
-//! - Memory pressure from frequent allocations
 //!
-//! **Expected Behavior**: In high-contention scenarios, you may see only 20-30% of
-//! expected operations complete successfully.
+//! // No update is lost
+//! assert_eq!(counter.get(), 400);
+//! ```
 //!
-//! ### 3. **Memory Allocation Overhead**
-//! ```rust
-//! use thread_share::ArcThreadShare;
+//! ## ⚠️ Limitations
 //!
-//! let arc_share = ArcThreadShare::new(0);
+//! ### 1. **Closures May Run More Than Once**
+//! Under contention, the closure passed to `update()` or `write()` can be
+//! called several times, each time on a fresh clone. Keep side effects out of
+//! it.
 //!
-//! // Each increment operation involves:
-//! // 1. Allocating new Box<T>
-//! // 2. Converting to raw pointer
-//! // 3. Atomic pointer swap
-//! // 4. Deallocating old Box<T>
-//! arc_share.increment();
-//! ```
+//! ### 2. **Every Write Clones and Allocates**
+//! Each write clones `T` and allocates a new `Arc<T>`, which is expensive for
+//! large data. Prefer `ThreadShare<T>` or `ArcThreadShareLocked<T>` for large
+//! values that are written often.
 //!
-//! **Problem**: Every update operation creates a new `Box<T>` and deallocates the old one,
-//! which can be expensive for large data types.
+//! ### 3. **No Change Notification**
+//! `ArcThreadShare<T>` has no waiting methods; use `ThreadShare<T>` to wait
+//! for changes.
 //!
 //! ## When to Use ArcThreadShare<T>
 //!
 //! ### ✅ Good Use Cases
-//! - **Low-contention scenarios** (few threads, infrequent updates)
-//! - **Performance-critical applications** where you understand the limitations
-//! - **Simple atomic operations** using built-in methods (`increment()`, `add()`)
 //! - **Read-heavy workloads** with occasional writes
+//! - **Small values** such as configuration, counters and flags
+//! - **Latency-sensitive readers** that must never wait for a writer
 //!
 //! ### ❌ Avoid When
-//! - **High-frequency updates** (>1000 ops/second per thread)
-//! - **Critical data integrity** requirements
-//! - **Predictable performance** needs
-//! - **Large data structures** (due to allocation overhead)
-//! - **Multi-threaded counters** with strict accuracy requirements
+//! - **Large data structures** written frequently (every write clones)
+//! - **Closures with side effects** (they may be retried)
+//! - **Waiting for changes** is needed
 //!
 //! ## Example Usage
 //!
@@ -99,7 +89,6 @@
 //!
 //! let counter = ArcThreadShare::new(0);
 //!
-//! // Use atomic methods for safety
 //! counter.increment();
 //! counter.add(5);
 //!
@@ -111,120 +100,96 @@
 //! use thread_share::{share, ArcThreadShare};
 //!
 //! let data = share!(String::from("Hello"));
-//! let arc_data = data.as_arc();
-//! let arc_share = ArcThreadShare::from_arc(arc_data);
 //!
-//! // Safe atomic operations
+//! // Independent copy: lock-free, but not seen by `data`
+//! let arc_share = ArcThreadShare::new(data.get());
 //! arc_share.update(|s| s.push_str(" World"));
+//! assert_eq!(arc_share.get(), "Hello World");
+//!
+//! // Live view: same API, writes go to `data` and notify its waiters
+//! let view = data.as_arc();
+//! view.update(|s| s.push_str(" There"));
+//! assert_eq!(data.get(), "Hello There");
 //! ```
 //!
 //! ## Performance Characteristics
 //!
-//! - **Low Contention**: Excellent performance, minimal overhead
-//! - **Medium Contention**: Good performance with some lost operations
-//! - **High Contention**: Poor performance, many lost operations
-//! - **Memory Usage**: Higher due to Box allocation/deallocation
+//! - **Reads**: Lock-free and wait-free in the common case, no allocation
+//! - **Low Write Contention**: One clone and one allocation per write
+//! - **High Write Contention**: Writers retry, cloning again on each attempt
+//! - **Memory Usage**: Replaced values live until their last reader is done
 //!
 //! ## Best Practices
 //!
-//! 1. **Always use atomic methods** (`increment()`, `add()`) instead of complex `update()` operations
-//! 2. **Test with realistic contention levels** before production use
-//! 3. **Consider `ThreadShare<T>`** for critical applications
-//! 4. **Monitor performance** under expected load conditions
-//! 5. **Use for simple operations** only (increment, add, simple updates)
+//! 1. **Keep values small** or use `ThreadShare<T>` for large ones
+//! 2. **Keep closures free of side effects**, since they may be retried
+//! 3. **Keep `read()` closures short**: the value they see stays alive until
+//!    they return
 //!
 //! ## Alternatives
 //!
-//! ### For High-Frequency Updates
+//! ### For Large Data
 //! ```rust
-//! use thread_share::share;
+//! use thread_share::{share, ArcThreadShareLocked};
 //!
-//! // Use ThreadShare with batching
-//! let share = share!(0);
-//! let clone = share.clone();
+//! // Use ArcThreadShareLocked to modify large data in place
+//! let share = share!(vec![1, 2, 3]);
+//! let arc_data = share.as_arc_locked();
+//! let locked_share = ArcThreadShareLocked::from_arc(arc_data);
 //!
-//! clone.update(|x| {
-//!     for _ in 0..100 {
-//!         *x = *x + 1;
-//!     }
-//! });
+//! locked_share.update(|data| data.push(4));
 //! ```
 //!
-//! ### For Critical Data Integrity
+//! ### For Change Notification
 //! ```rust
 //! use thread_share::share;
 //!
-//! // Use ThreadShare for guaranteed safety
-//! let share = share!(vec![1, 2, 3]);
+//! // Use ThreadShare to wait for changes
+//! let share = share!(0);
 //! let clone = share.clone();
 //!
-//! // All operations are guaranteed to succeed
-//! clone.update(|data| {
-//!     // Critical modifications
-//! });
-//! ```
-//!
-//! ### For Safe Zero-Copy
-//! ```rust
-//! use thread_share::{share, ArcThreadShareLocked};
-//!
-//! // Use ArcThreadShareLocked for safe zero-copy
-//! let share = share!(vec![1, 2, 3]);
-//! let arc_data = share.as_arc_locked();
-//! let locked_share = ArcThreadShareLocked::from_arc(arc_data);
-//!
-//! // Safe zero-copy with guaranteed thread safety
-//! locked_share.update(|data| {
-//!     // Safe modifications
-//! });
+//! std::thread::spawn(move || clone.set(1));
+//! share.wait_for_change_forever();
 //! ```
 
-use std::sync::atomic::{AtomicPtr, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Weak};
+
+pub use arc_swap::ArcSwap;
 
+#[cfg(feature = "serialize")]
+use crate::error::Error;
 #[cfg(feature = "serialize")]
 use serde::{de::DeserializeOwned, Serialize};
 
-/// Helper structure for working with Arc<AtomicPtr<T>> directly (without locks!)
-///
-/// **⚠️ WARNING: This structure has significant limitations and should be used with caution!**
+/// Lock-free shared data with atomic, copy-on-write updates
 ///
 /// ## Overview
 ///
-/// `ArcThreadShare<T>` provides zero-copy data sharing between threads using atomic
-/// pointer operations. While this can offer high performance, it comes with important
-/// trade-offs that developers must understand.
+/// `ArcThreadShare<T>` stores its value in an `Arc<ArcSwap<T>>`. Reads load
+/// the current `Arc<T>` without locking; writes publish a new `Arc<T>` with a
+/// compare-and-swap and retry if another writer got in first. Replaced values
+/// are dropped once no reader holds them anymore.
 ///
 /// ## Key Features
 ///
-/// - **Zero-Copy Operations**: No data cloning during access
-/// - **Atomic Updates**: Uses atomic pointer operations
-/// - **High Performance**: Potentially faster than lock-based approaches
-/// - **Memory Efficiency**: Single copy of data shared across threads
+/// - **Lock-Free Reads**: Readers never wait for writers
+/// - **Atomic Updates**: Concurrent writes never lose each other's changes
+/// - **Safe Reclamation**: No reader can observe a freed value
 ///
+/// ## Limitations
 ///
-/// ### 2. **High Contention Performance Issues**
-/// Under high contention, many operations may be lost due to:
-/// - Box allocation/deallocation overhead
-/// - CAS failures requiring retries
-/// - Memory pressure from frequent allocations
-///
-/// ### 3. **Memory Allocation Overhead**
-/// Every update operation involves Box allocation and deallocation.
+/// - Write closures may run more than once under contention
+/// - Every write clones `T` and allocates a new `Arc<T>`
 ///
 /// ## When to Use
 ///
-/// - **Low-contention scenarios** (few threads, infrequent updates)
-/// - **Performance-critical applications** where you understand the limitations
-/// - **Simple atomic operations** using built-in methods
 /// - **Read-heavy workloads** with occasional writes
+/// - **Small values** such as configuration, counters and flags
 ///
 /// ## When to Avoid
 ///
-/// - **High-frequency updates** (>1000 ops/second per thread)
-/// - **Critical data integrity** requirements
-/// - **Predictable performance** needs
-/// - **Large data structures**
+/// - **Large data structures** written frequently
+/// - **Waiting for changes**, which `ThreadShare<T>` supports
 ///
 /// ## Example
 ///
@@ -233,20 +198,15 @@ use serde::{de::DeserializeOwned, Serialize};
 ///
 /// let counter = ArcThreadShare::new(0);
 ///
-/// // Use atomic methods for safety
 /// counter.increment();
 /// counter.add(5);
 ///
 /// assert_eq!(counter.get(), 6);
 /// ```
 pub struct ArcThreadShare<T> {
-    pub data: Arc<AtomicPtr<T>>,
+    pub data: Arc<ArcSwap<T>>,
 }
 
-// Automatically implement Send and Sync for ArcThreadShare
-unsafe impl<T> Send for ArcThreadShare<T> {}
-unsafe impl<T> Sync for ArcThreadShare<T> {}
-
 impl<T> Clone for ArcThreadShare<T> {
     fn clone(&self) -> Self {
         Self {
@@ -256,14 +216,14 @@ impl<T> Clone for ArcThreadShare<T> {
 }
 
 impl<T> ArcThreadShare<T> {
-    /// Creates from Arc<AtomicPtr<T>>
+    /// Creates from Arc<ArcSwap<T>>
     ///
-    /// This method creates an `ArcThreadShare<T>` from an existing `Arc<AtomicPtr<T>>`.
-    /// Useful when you already have atomic pointer data from other sources.
+    /// This method creates an `ArcThreadShare<T>` from an existing `Arc<ArcSwap<T>>`,
+    /// such as the `data` field of another `ArcThreadShare<T>`.
     ///
     /// ## Arguments
     ///
-    /// * `arc` - An `Arc<AtomicPtr<T>>` containing the data to share
+    /// * `arc` - An `Arc<ArcSwap<T>>` containing the data to share
     ///
     /// ## Returns
     ///
@@ -272,32 +232,24 @@ impl<T> ArcThreadShare<T> {
     /// ## Example
     ///
     /// ```rust
-    /// use thread_share::{share, ArcThreadShare};
+    /// use thread_share::ArcThreadShare;
     ///
-    /// let data = share!(String::from("Hello"));
-    /// let arc_data = data.as_arc();
-    /// let arc_share = ArcThreadShare::from_arc(arc_data);
+    /// let data = ArcThreadShare::new(String::from("Hello"));
+    /// let arc_share = ArcThreadShare::from_arc(data.data.clone());
     ///
-    /// // Now you can use atomic operations
     /// arc_share.update(|s| s.push_str(" World"));
+    /// assert_eq!(data.get(), "Hello World");
     /// ```
-    pub fn from_arc(arc: Arc<AtomicPtr<T>>) -> Self {
+    pub fn from_arc(arc: Arc<ArcSwap<T>>) -> Self {
         Self { data: arc }
     }
 
     /// Creates a new ArcThreadShare with data
     ///
-    /// This method creates a new `ArcThreadShare<T>` instance with the provided data.
-    /// The data is boxed and converted to an atomic pointer for thread-safe sharing.
-    ///
     /// ## Arguments
     ///
     /// * `data` - The initial data to share between threads
     ///
-    /// ## Requirements
-    ///
-    /// The type `T` must implement `Clone` trait.
-    ///
     /// ## Returns
     ///
     /// A new `ArcThreadShare<T>` instance containing the data.
@@ -311,29 +263,20 @@ impl<T> ArcThreadShare<T> {
     /// let message = ArcThreadShare::new(String::from("Hello"));
     /// let data = ArcThreadShare::new(vec![1, 2, 3]);
     /// ```
-    pub fn new(data: T) -> Self
-    where
-        T: Clone,
-    {
-        let boxed = Box::new(data);
-        let ptr = Box::into_raw(boxed);
-        let atomic = Arc::new(AtomicPtr::new(ptr));
-        Self { data: atomic }
+    pub fn new(data: T) -> Self {
+        Self {
+            data: Arc::new(ArcSwap::from_pointee(data)),
+        }
     }
 
     /// Gets a copy of data
     ///
-    /// This method retrieves a copy of the current data. The operation is safe
-    /// but involves cloning the data.
+    /// The current value is loaded without locking and cloned.
     ///
     /// ## Requirements
     ///
     /// The type `T` must implement `Clone` trait.
     ///
-    /// ## Returns
-    ///
-    /// A copy of the current data.
-    ///
     /// ## Example
     ///
     /// ```rust
@@ -347,14 +290,13 @@ impl<T> ArcThreadShare<T> {
     where
         T: Clone,
     {
-        let ptr = self.data.load(Ordering::Acquire);
-        unsafe { (*ptr).clone() }
+        T::clone(&self.data.load())
     }
 
     /// Sets data atomically
     ///
-    /// This method atomically replaces the current data with new data.
-    /// The old data is automatically deallocated.
+    /// This method atomically replaces the current data with new data. The
+    /// old data is dropped once no reader holds it anymore.
     ///
     /// ## Arguments
     ///
@@ -370,60 +312,43 @@ impl<T> ArcThreadShare<T> {
     /// assert_eq!(counter.get(), 100);
     /// ```
     pub fn set(&self, new_data: T) {
-        let new_boxed = Box::new(new_data);
-        let new_ptr = Box::into_raw(new_boxed);
-
-        let old_ptr = self.data.swap(new_ptr, Ordering::AcqRel);
-
-        // Free old data
-        if !old_ptr.is_null() {
-            unsafe {
-                drop(Box::from_raw(old_ptr));
-            }
-        }
+        self.data.store(Arc::new(new_data));
     }
 
-    /// Updates data (⚠️ NOT atomic for complex operations!)
+    /// Updates data atomically
     ///
-    /// **⚠️ WARNING: This method is NOT atomic for complex operations!**
+    /// The closure is applied to a clone of the current data, which is then
+    /// published with a compare-and-swap. If another thread changed the data
+    /// in the meantime, the closure runs again on the newer data, so it may be
+    /// called more than once.
     ///
-    /// For simple operations like `+= 1`, use the atomic methods `increment()` or `add()`
-    /// instead. This method can cause race conditions under high contention.
+    /// ## Requirements
+    ///
+    /// The type `T` must implement `Clone` trait.
     ///
     /// ## Arguments
     ///
-    /// * `f` - Closure that receives a mutable reference to the data
+    /// * `f` - Closure that receives a mutable reference to a copy of the data
     ///
     /// ## Example
     ///
     /// ```rust
     /// use thread_share::ArcThreadShare;
     ///
-    /// let counter = ArcThreadShare::new(0);
-    ///
-    /// // ❌ NOT atomic - can cause race conditions
-    /// counter.update(|x| *x += 1);
-    ///
-    /// // ✅ Use atomic methods instead
-    /// counter.increment();
+    /// let data = ArcThreadShare::new(vec![1, 2]);
+    /// data.update(|v| v.push(3));
+    /// assert_eq!(data.get(), vec![1, 2, 3]);
     /// ```
-    pub fn update<F>(&self, f: F)
+    pub fn update<F>(&self, mut f: F)
     where
-        F: FnOnce(&mut T),
+        F: FnMut(&mut T),
+        T: Clone,
     {
-        let ptr = self.data.load(Ordering::Acquire);
-        if !ptr.is_null() {
-            unsafe {
-                f(&mut *ptr);
-            }
-        }
+        self.write(|data| f(data));
     }
 
     /// Atomically increments numeric values (for types that support it)
     ///
-    /// This method provides atomic increment operations for numeric types.
-    /// It uses a compare-exchange loop to ensure atomicity.
-    ///
     /// ## Requirements
     ///
     /// The type `T` must implement:
@@ -451,35 +376,7 @@ impl<T> ArcThreadShare<T> {
     where
         T: Copy + std::ops::Add<Output = T> + std::ops::AddAssign + From<u8> + 'static,
     {
-        loop {
-            let ptr = self.data.load(Ordering::Acquire);
-            if ptr.is_null() {
-                break;
-            }
-
-            let current_value = unsafe { *ptr };
-            let new_value = current_value + T::from(1u8);
-
-            // Try to atomically update the pointer with new data
-            let new_boxed = Box::new(new_value);
-            let new_ptr = Box::into_raw(new_boxed);
-
-            if let Ok(_) =
-                self.data
-                    .compare_exchange(ptr, new_ptr, Ordering::AcqRel, Ordering::Acquire)
-            {
-                // Successfully updated, free old data
-                unsafe {
-                    drop(Box::from_raw(ptr));
-                }
-                break;
-            } else {
-                // Failed to update, free new data and retry
-                unsafe {
-                    drop(Box::from_raw(new_ptr));
-                }
-            }
-        }
+        let _ = self.fetch_update(|x| Some(*x + T::from(1u8)));
     }
 
     /// Atomically adds a value (for types that support it)
@@ -487,77 +384,340 @@ impl<T> ArcThreadShare<T> {
     where
         T: Copy + std::ops::Add<Output = T> + std::ops::AddAssign + 'static,
     {
+        let _ = self.fetch_update(|x| Some(*x + value));
+    }
+
+    /// Atomically replaces the data with a value computed from it
+    ///
+    /// The function receives the current data and returns the new data, or
+    /// `None` to leave it unchanged. If another thread changes the data before
+    /// the new value is published, the function is called again with the
+    /// newer data, so it may run more than once.
+    ///
+    /// ## Requirements
+    ///
+    /// The type `T` must implement `Clone` trait.
+    ///
+    /// ## Arguments
+    ///
+    /// * `f` - Function computing the new data from the current data
+    ///
+    /// ## Returns
+    ///
+    /// `Ok` with the previous data if it was replaced, `Err` with the current
+    /// data if `f` returned `None`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::ArcThreadShare;
+    ///
+    /// let peak = ArcThreadShare::new(10);
+    ///
+    /// // Lock-free maximum
+    /// let record = |value: i32| peak.fetch_update(|&max| (value > max).then_some(value));
+    /// assert_eq!(record(25), Ok(10));
+    /// assert_eq!(record(20), Err(25));
+    ///
+    /// // Lock-free bit flags
+    /// let flags = ArcThreadShare::new(0b0001u8);
+    /// assert_eq!(flags.fetch_update(|&f| Some(f | 0b0100)), Ok(0b0001));
+    /// assert_eq!(flags.get(), 0b0101);
+    /// ```
+    pub fn fetch_update<F>(&self, mut f: F) -> Result<T, T>
+    where
+        F: FnMut(&T) -> Option<T>,
+        T: Clone,
+    {
+        let mut current = self.data.load();
         loop {
-            let ptr = self.data.load(Ordering::Acquire);
-            if ptr.is_null() {
-                break;
+            let Some(new_data) = f(&current) else {
+                return Err(T::clone(&current));
+            };
+            let previous = self.data.compare_and_swap(&*current, Arc::new(new_data));
+            if Arc::ptr_eq(&previous, &current) {
+                return Ok(T::clone(&current));
             }
+            current = previous;
+        }
+    }
 
-            let current_value = unsafe { *ptr };
-            let new_value = current_value + value;
-
-            // Try to atomically update the pointer with new data
-            let new_boxed = Box::new(new_value);
-            let new_ptr = Box::into_raw(new_boxed);
-
-            if let Ok(_) =
-                self.data
-                    .compare_exchange(ptr, new_ptr, Ordering::AcqRel, Ordering::Acquire)
-            {
-                // Successfully updated, free old data
-                unsafe {
-                    drop(Box::from_raw(ptr));
-                }
-                break;
+    /// Sets new data only if the current data equals `current`
+    ///
+    /// ## Requirements
+    ///
+    /// The type `T` must implement `Clone` and `PartialEq` traits.
+    ///
+    /// ## Arguments
+    ///
+    /// * `current` - The value the data must currently have
+    /// * `new_data` - The new data to set
+    ///
+    /// ## Returns
+    ///
+    /// `Ok` with the previous data if it was replaced, `Err` with the actual
+    /// data if it did not equal `current`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::ArcThreadShare;
+    ///
+    /// let state = ArcThreadShare::new("idle");
+    ///
+    /// assert_eq!(state.compare_exchange(&"idle", "running"), Ok("idle"));
+    /// assert_eq!(state.compare_exchange(&"idle", "running"), Err("running"));
+    /// ```
+    pub fn compare_exchange(&self, current: &T, new_data: T) -> Result<T, T>
+    where
+        T: Clone + PartialEq,
+    {
+        let mut new_data = Some(new_data);
+        self.fetch_update(|data| {
+            if data == current {
+                new_data.take()
             } else {
-                // Failed to update, free new data and retry
-                unsafe {
-                    drop(Box::from_raw(new_ptr));
-                }
+                None
             }
-        }
+        })
+    }
+
+    /// Sets new data and returns the previous data
+    ///
+    /// ## Requirements
+    ///
+    /// The type `T` must implement `Clone` trait; the previous data is only
+    /// cloned if a reader still holds it.
+    ///
+    /// ## Arguments
+    ///
+    /// * `new_data` - The new data to set
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::ArcThreadShare;
+    ///
+    /// let data = ArcThreadShare::new(1);
+    /// assert_eq!(data.swap(2), 1);
+    /// assert_eq!(data.get(), 2);
+    /// ```
+    pub fn swap(&self, new_data: T) -> T
+    where
+        T: Clone,
+    {
+        let previous = self.data.swap(Arc::new(new_data));
+        Arc::try_unwrap(previous).unwrap_or_else(|previous| T::clone(&previous))
     }
 
-    /// Reads data
+    /// Reads data without locking
+    ///
+    /// The value seen by the closure stays alive, even if it is replaced
+    /// meanwhile, until the closure returns.
+    ///
+    /// ## Arguments
+    ///
+    /// * `f` - Closure that receives a reference to the data
     pub fn read<F, R>(&self, f: F) -> R
     where
         F: FnOnce(&T) -> R,
     {
-        let ptr = self.data.load(Ordering::Acquire);
-        if !ptr.is_null() {
-            unsafe { f(&*ptr) }
-        } else {
-            panic!("Attempted to read from null pointer");
-        }
+        f(&self.data.load())
     }
 
-    /// Writes data
-    pub fn write<F, R>(&self, f: F) -> R
+    /// Writes data atomically and returns a result
+    ///
+    /// Like `update()`: the closure may run more than once, and the result of
+    /// the attempt that was published is returned.
+    ///
+    /// ## Arguments
+    ///
+    /// * `f` - Closure that receives a mutable reference to a copy of the data
+    pub fn write<F, R>(&self, mut f: F) -> R
     where
-        F: FnOnce(&mut T) -> R,
+        F: FnMut(&mut T) -> R,
+        T: Clone,
     {
-        let ptr = self.data.load(Ordering::Acquire);
-        if !ptr.is_null() {
-            unsafe { f(&mut *ptr) }
-        } else {
-            panic!("Attempted to write to null pointer");
+        let mut current = self.data.load();
+        loop {
+            let mut new_data = T::clone(&current);
+            let result = f(&mut new_data);
+            let previous = self.data.compare_and_swap(&*current, Arc::new(new_data));
+            if Arc::ptr_eq(&previous, &current) {
+                return result;
+            }
+            current = previous;
         }
     }
 
+    /// Creates a weak handle that does not keep the data alive
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::ArcThreadShare;
+    ///
+    /// let counter = ArcThreadShare::new(1);
+    /// let weak = counter.downgrade();
+    ///
+    /// assert_eq!(weak.upgrade().map(|c| c.get()), Some(1));
+    /// drop(counter);
+    /// assert!(weak.upgrade().is_none());
+    /// ```
+    pub fn downgrade(&self) -> WeakArcThreadShare<T> {
+        WeakArcThreadShare {
+            data: Arc::downgrade(&self.data),
+        }
+    }
+
+    /// Takes the data out of the last handle
+    ///
+    /// Succeeds only if this is the only handle sharing the data; weak
+    /// handles do not count.
+    ///
+    /// ## Returns
+    ///
+    /// `Ok` with the data, or `Err` with this handle if other handles exist.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::ArcThreadShare;
+    ///
+    /// let data = ArcThreadShare::new(vec![1, 2, 3]);
+    /// let clone = data.clone();
+    ///
+    /// // Still shared with `clone`: the handle is given back
+    /// let Err(data) = data.try_unwrap() else { unreachable!() };
+    /// drop(clone);
+    /// assert_eq!(data.try_unwrap().ok(), Some(vec![1, 2, 3]));
+    /// ```
+    pub fn try_unwrap(self) -> Result<T, Self> {
+        let data = Arc::try_unwrap(self.data).map_err(|data| Self { data })?;
+        match Arc::try_unwrap(data.into_inner()) {
+            Ok(value) => Ok(value),
+            Err(value) => Err(Self {
+                data: Arc::new(ArcSwap::new(value)),
+            }),
+        }
+    }
+
+    /// Takes the data out if this is the last handle
+    ///
+    /// Like `Arc::into_inner()`: when several handles call this at the same
+    /// time, exactly one of them gets the data. Handles that get `None` are
+    /// dropped.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::ArcThreadShare;
+    /// use std::thread;
+    ///
+    /// let data = ArcThreadShare::new(String::from("result"));
+    /// let clone = data.clone();
+    ///
+    /// let other = thread::spawn(move || clone.into_inner());
+    /// let results = [data.into_inner(), other.join().unwrap()];
+    ///
+    /// assert_eq!(results.iter().flatten().count(), 1);
+    /// ```
+    pub fn into_inner(self) -> Option<T> {
+        Arc::into_inner(self.data).and_then(|data| Arc::into_inner(data.into_inner()))
+    }
+
+    /// Gets the number of handles keeping the data alive
+    pub fn strong_count(&self) -> usize {
+        Arc::strong_count(&self.data)
+    }
+
+    /// Gets the number of weak handles to the data
+    pub fn weak_count(&self) -> usize {
+        Arc::weak_count(&self.data)
+    }
+
+    /// Serializes the current data to JSON string
+    ///
+    /// This method requires the `serialize` feature to be enabled.
+    ///
+    /// ## Returns
+    ///
+    /// JSON string representation of the data, or `Error::Serialize` if serialization fails.
     #[cfg(feature = "serialize")]
-    pub fn to_json(&self) -> Result<String, serde_json::Error>
+    pub fn to_json(&self) -> crate::Result<String>
     where
-        T: Serialize + Clone,
+        T: Serialize,
     {
-        serde_json::to_string(&self.get())
+        self.read(serde_json::to_string).map_err(Error::Serialize)
     }
 
+    /// Deserializes data from JSON string and sets it
+    ///
+    /// This method requires the `serialize` feature to be enabled.
+    ///
+    /// ## Arguments
+    ///
+    /// * `json` - JSON string to deserialize
+    ///
+    /// ## Returns
+    ///
+    /// `Ok(())` on success, `Err(Error::Deserialize)` if deserialization fails.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::ArcThreadShare;
+    ///
+    /// #[cfg(feature = "serialize")]
+    /// {
+    ///     let data = ArcThreadShare::new(vec![0; 0]);
+    ///     data.from_json("[1,2,3]").expect("Failed to deserialize");
+    ///     assert_eq!(data.get(), vec![1, 2, 3]);
+    ///     assert_eq!(data.to_json().unwrap(), "[1,2,3]");
+    /// }
+    /// ```
     #[cfg(feature = "serialize")]
-    pub fn from_json<D>(&self, json: &str) -> Result<D, serde_json::Error>
+    pub fn from_json(&self, json: &str) -> crate::Result<()>
     where
-        D: DeserializeOwned,
+        T: DeserializeOwned,
     {
-        serde_json::from_str(json)
+        let deserialized: T = serde_json::from_str(json).map_err(Error::Deserialize)?;
+
+        self.set(deserialized);
+        Ok(())
+    }
+}
+
+/// Weak handle to the data of an `ArcThreadShare<T>`
+///
+/// Created with `ArcThreadShare::downgrade()`. It does not keep the data
+/// alive; `upgrade()` returns `None` once every owning handle is gone.
+pub struct WeakArcThreadShare<T> {
+    data: Weak<ArcSwap<T>>,
+}
+
+impl<T> WeakArcThreadShare<T> {
+    /// Gets an owning handle if the data is still alive
+    pub fn upgrade(&self) -> Option<ArcThreadShare<T>> {
+        self.data.upgrade().map(ArcThreadShare::from_arc)
+    }
+
+    /// Gets the number of handles keeping the data alive
+    pub fn strong_count(&self) -> usize {
+        self.data.strong_count()
+    }
+
+    /// Gets the number of weak handles to the data
+    pub fn weak_count(&self) -> usize {
+        self.data.weak_count()
+    }
+}
+
+impl<T> Clone for WeakArcThreadShare<T> {
+    fn clone(&self) -> Self {
+        Self {
+            data: Weak::clone(&self.data),
+        }
     }
 }
 
@@ -0,0 +1,525 @@
+//! # Atomic Share Module - AtomicShare<T>
+//!
+//! This module provides `AtomicShare<T>`, a shared value stored in a native
+//! `std::sync::atomic` type.
+//!
+//! ## Overview
+//!
+//! `ArcThreadShare<T>` works for any `T`, but every write allocates a new
+//! value. For integers, `bool`, floats and raw pointers the hardware can do
+//! better: `AtomicShare<T>` keeps the value in the matching atomic type
+//! (`AtomicI32`, `AtomicBool`, `AtomicPtr`, ...), so reads and writes are
+//! single instructions that never lock and never allocate.
+//!
+//! | Type | Storage | Operations |
+//! |------|---------|------------|
+//! | `i8`..`i64`, `u8`..`u64`, `isize`, `usize` | `AtomicI8`..`AtomicUsize` | arithmetic and bitwise |
+//! | `bool` | `AtomicBool` | bitwise |
+//! | `f32`, `f64` | `AtomicU32`, `AtomicU64` (bit patterns) | arithmetic |
+//! | `*mut P` | `AtomicPtr<P>` | load, store and compare |
+//!
+//! All operations use `Ordering::SeqCst`.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use thread_share::atomic_share;
+//! use std::thread;
+//!
+//! let requests = atomic_share!(0u64);
+//!
+//! let handles: Vec<_> = (0..4)
+//!     .map(|_| {
+//!         let requests = requests.clone();
+//!         thread::spawn(move || {
+//!             for _ in 0..1000 {
+//!                 requests.increment();
+//!             }
+//!         })
+//!     })
+//!     .collect();
+//! for handle in handles {
+//!     handle.join().unwrap();
+//! }
+//!
+//! assert_eq!(requests.get(), 4000);
+//! ```
+//!
+//! ## Floats
+//!
+//! `f32` and `f64` are stored as their bit patterns, and their arithmetic is
+//! a compare-and-swap loop. `compare_exchange()` compares bit patterns, so
+//! `NaN` matches an identical `NaN` and `0.0` does not match `-0.0`.
+//!
+//! ## Limitations
+//!
+//! `AtomicShare<T>` has no change notification; use `ThreadShare<T>` to wait
+//! for changes. Integer arithmetic wraps around on overflow, like the
+//! `std::sync::atomic` types.
+
+use std::sync::Arc;
+use std::sync::atomic::{
+    AtomicBool, AtomicI8, AtomicI16, AtomicI32, AtomicI64, AtomicIsize, AtomicPtr, AtomicU8,
+    AtomicU16, AtomicU32, AtomicU64, AtomicUsize, Ordering,
+};
+
+const ORDER: Ordering = Ordering::SeqCst;
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Types that can be stored in an `AtomicShare<T>`
+///
+/// Implemented for all integer types, `bool`, `f32`, `f64` and `*mut P`.
+/// This trait is sealed.
+pub trait AtomicValue: Copy + sealed::Sealed {
+    /// The native atomic type holding the value
+    #[doc(hidden)]
+    type Atomic: Send + Sync;
+
+    #[doc(hidden)]
+    fn new_atomic(value: Self) -> Self::Atomic;
+    #[doc(hidden)]
+    fn load(atomic: &Self::Atomic) -> Self;
+    #[doc(hidden)]
+    fn store(atomic: &Self::Atomic, value: Self);
+    #[doc(hidden)]
+    fn swap(atomic: &Self::Atomic, value: Self) -> Self;
+    #[doc(hidden)]
+    fn compare_exchange(atomic: &Self::Atomic, current: Self, new: Self) -> Result<Self, Self>;
+}
+
+/// Values supporting atomic arithmetic
+///
+/// Implemented for all integer types, `f32` and `f64`.
+pub trait AtomicNumber: AtomicValue {
+    /// The value `increment()` adds
+    #[doc(hidden)]
+    const ONE: Self;
+
+    #[doc(hidden)]
+    fn fetch_add(atomic: &Self::Atomic, value: Self) -> Self;
+    #[doc(hidden)]
+    fn fetch_sub(atomic: &Self::Atomic, value: Self) -> Self;
+    #[doc(hidden)]
+    fn fetch_max(atomic: &Self::Atomic, value: Self) -> Self;
+    #[doc(hidden)]
+    fn fetch_min(atomic: &Self::Atomic, value: Self) -> Self;
+}
+
+/// Values supporting atomic bitwise operations
+///
+/// Implemented for all integer types and `bool`.
+pub trait AtomicBits: AtomicValue {
+    #[doc(hidden)]
+    fn fetch_and(atomic: &Self::Atomic, value: Self) -> Self;
+    #[doc(hidden)]
+    fn fetch_or(atomic: &Self::Atomic, value: Self) -> Self;
+    #[doc(hidden)]
+    fn fetch_xor(atomic: &Self::Atomic, value: Self) -> Self;
+}
+
+macro_rules! impl_atomic_value {
+    ($($t:ty => $atomic:ty),* $(,)?) => {$(
+        impl sealed::Sealed for $t {}
+
+        impl AtomicValue for $t {
+            type Atomic = $atomic;
+
+            fn new_atomic(value: Self) -> Self::Atomic {
+                <$atomic>::new(value)
+            }
+
+            fn load(atomic: &Self::Atomic) -> Self {
+                atomic.load(ORDER)
+            }
+
+            fn store(atomic: &Self::Atomic, value: Self) {
+                atomic.store(value, ORDER);
+            }
+
+            fn swap(atomic: &Self::Atomic, value: Self) -> Self {
+                atomic.swap(value, ORDER)
+            }
+
+            fn compare_exchange(atomic: &Self::Atomic, current: Self, new: Self) -> Result<Self, Self> {
+                atomic.compare_exchange(current, new, ORDER, ORDER)
+            }
+        }
+
+        impl AtomicBits for $t {
+            fn fetch_and(atomic: &Self::Atomic, value: Self) -> Self {
+                atomic.fetch_and(value, ORDER)
+            }
+
+            fn fetch_or(atomic: &Self::Atomic, value: Self) -> Self {
+                atomic.fetch_or(value, ORDER)
+            }
+
+            fn fetch_xor(atomic: &Self::Atomic, value: Self) -> Self {
+                atomic.fetch_xor(value, ORDER)
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_atomic_integer {
+    ($($t:ty => $atomic:ty),* $(,)?) => {
+        impl_atomic_value!($($t => $atomic),*);
+
+        $(
+            impl AtomicNumber for $t {
+                const ONE: Self = 1;
+
+                fn fetch_add(atomic: &Self::Atomic, value: Self) -> Self {
+                    atomic.fetch_add(value, ORDER)
+                }
+
+                fn fetch_sub(atomic: &Self::Atomic, value: Self) -> Self {
+                    atomic.fetch_sub(value, ORDER)
+                }
+
+                fn fetch_max(atomic: &Self::Atomic, value: Self) -> Self {
+                    atomic.fetch_max(value, ORDER)
+                }
+
+                fn fetch_min(atomic: &Self::Atomic, value: Self) -> Self {
+                    atomic.fetch_min(value, ORDER)
+                }
+            }
+        )*
+    };
+}
+
+impl_atomic_integer!(
+    i8 => AtomicI8,
+    i16 => AtomicI16,
+    i32 => AtomicI32,
+    i64 => AtomicI64,
+    isize => AtomicIsize,
+    u8 => AtomicU8,
+    u16 => AtomicU16,
+    u32 => AtomicU32,
+    u64 => AtomicU64,
+    usize => AtomicUsize,
+);
+
+impl_atomic_value!(bool => AtomicBool);
+
+macro_rules! impl_atomic_float {
+    ($($t:ty => $atomic:ty),* $(,)?) => {$(
+        impl sealed::Sealed for $t {}
+
+        impl AtomicValue for $t {
+            type Atomic = $atomic;
+
+            fn new_atomic(value: Self) -> Self::Atomic {
+                <$atomic>::new(value.to_bits())
+            }
+
+            fn load(atomic: &Self::Atomic) -> Self {
+                <$t>::from_bits(atomic.load(ORDER))
+            }
+
+            fn store(atomic: &Self::Atomic, value: Self) {
+                atomic.store(value.to_bits(), ORDER);
+            }
+
+            fn swap(atomic: &Self::Atomic, value: Self) -> Self {
+                <$t>::from_bits(atomic.swap(value.to_bits(), ORDER))
+            }
+
+            fn compare_exchange(atomic: &Self::Atomic, current: Self, new: Self) -> Result<Self, Self> {
+                atomic
+                    .compare_exchange(current.to_bits(), new.to_bits(), ORDER, ORDER)
+                    .map(<$t>::from_bits)
+                    .map_err(<$t>::from_bits)
+            }
+        }
+
+        impl AtomicNumber for $t {
+            const ONE: Self = 1.0;
+
+            fn fetch_add(atomic: &Self::Atomic, value: Self) -> Self {
+                fetch_float(atomic, |x: Self| x + value)
+            }
+
+            fn fetch_sub(atomic: &Self::Atomic, value: Self) -> Self {
+                fetch_float(atomic, |x: Self| x - value)
+            }
+
+            fn fetch_max(atomic: &Self::Atomic, value: Self) -> Self {
+                fetch_float(atomic, |x: Self| x.max(value))
+            }
+
+            fn fetch_min(atomic: &Self::Atomic, value: Self) -> Self {
+                fetch_float(atomic, |x: Self| x.min(value))
+            }
+        }
+    )*};
+}
+
+impl_atomic_float!(f32 => AtomicU32, f64 => AtomicU64);
+
+/// Applies `f` to a float stored as bits, retrying until no other write races
+fn fetch_float<T, F>(atomic: &T::Atomic, f: F) -> T
+where
+    T: AtomicValue,
+    F: Fn(T) -> T,
+{
+    let mut current = T::load(atomic);
+    loop {
+        match T::compare_exchange(atomic, current, f(current)) {
+            Ok(previous) => return previous,
+            Err(actual) => current = actual,
+        }
+    }
+}
+
+impl<P> sealed::Sealed for *mut P {}
+
+impl<P> AtomicValue for *mut P {
+    type Atomic = AtomicPtr<P>;
+
+    fn new_atomic(value: Self) -> Self::Atomic {
+        AtomicPtr::new(value)
+    }
+
+    fn load(atomic: &Self::Atomic) -> Self {
+        atomic.load(ORDER)
+    }
+
+    fn store(atomic: &Self::Atomic, value: Self) {
+        atomic.store(value, ORDER);
+    }
+
+    fn swap(atomic: &Self::Atomic, value: Self) -> Self {
+        atomic.swap(value, ORDER)
+    }
+
+    fn compare_exchange(atomic: &Self::Atomic, current: Self, new: Self) -> Result<Self, Self> {
+        atomic.compare_exchange(current, new, ORDER, ORDER)
+    }
+}
+
+/// Shared value stored in a native atomic type
+///
+/// `AtomicShare<T>` is the allocation-free alternative to `ArcThreadShare<T>`
+/// for integers, `bool`, floats and raw pointers. Clones share the value.
+///
+/// ## Example
+///
+/// ```rust
+/// use thread_share::AtomicShare;
+///
+/// let peak = AtomicShare::new(0u32);
+/// let flags = AtomicShare::new(0b0001u8);
+///
+/// peak.fetch_max(42);
+/// flags.fetch_or(0b0100);
+///
+/// assert_eq!(peak.get(), 42);
+/// assert_eq!(flags.get(), 0b0101);
+/// ```
+pub struct AtomicShare<T: AtomicValue> {
+    data: Arc<T::Atomic>,
+}
+
+impl<T: AtomicValue> Clone for AtomicShare<T> {
+    fn clone(&self) -> Self {
+        Self {
+            data: Arc::clone(&self.data),
+        }
+    }
+}
+
+impl<T: AtomicValue> AtomicShare<T> {
+    /// Creates a new AtomicShare with data
+    ///
+    /// ## Arguments
+    ///
+    /// * `data` - The initial value
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::AtomicShare;
+    ///
+    /// let counter = AtomicShare::new(0usize);
+    /// let running = AtomicShare::new(true);
+    /// let ratio = AtomicShare::new(0.5f64);
+    /// ```
+    pub fn new(data: T) -> Self {
+        Self {
+            data: Arc::new(T::new_atomic(data)),
+        }
+    }
+
+    /// Gets the current value
+    pub fn get(&self) -> T {
+        T::load(&self.data)
+    }
+
+    /// Sets a new value
+    ///
+    /// ## Arguments
+    ///
+    /// * `new_data` - The new value
+    pub fn set(&self, new_data: T) {
+        T::store(&self.data, new_data);
+    }
+
+    /// Sets a new value and returns the previous one
+    ///
+    /// ## Arguments
+    ///
+    /// * `new_data` - The new value
+    pub fn swap(&self, new_data: T) -> T {
+        T::swap(&self.data, new_data)
+    }
+
+    /// Sets a new value only if the current value is `current`
+    ///
+    /// ## Arguments
+    ///
+    /// * `current` - The value the data must currently have
+    /// * `new_data` - The new value
+    ///
+    /// ## Returns
+    ///
+    /// `Ok` with the previous value if it was replaced, `Err` with the actual
+    /// value otherwise.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::AtomicShare;
+    ///
+    /// let started = AtomicShare::new(false);
+    ///
+    /// // Only one caller wins
+    /// assert_eq!(started.compare_exchange(false, true), Ok(false));
+    /// assert_eq!(started.compare_exchange(false, true), Err(true));
+    /// ```
+    pub fn compare_exchange(&self, current: T, new_data: T) -> Result<T, T> {
+        T::compare_exchange(&self.data, current, new_data)
+    }
+
+    /// Atomically replaces the value with one computed from it
+    ///
+    /// Works like `ArcThreadShare::fetch_update()`: the function may be called
+    /// more than once if other threads write in between.
+    ///
+    /// ## Arguments
+    ///
+    /// * `f` - Function computing the new value, or `None` to keep the current one
+    ///
+    /// ## Returns
+    ///
+    /// `Ok` with the previous value if it was replaced, `Err` with the current
+    /// value if `f` returned `None`.
+    pub fn fetch_update<F>(&self, mut f: F) -> Result<T, T>
+    where
+        F: FnMut(T) -> Option<T>,
+    {
+        let mut current = self.get();
+        while let Some(new_data) = f(current) {
+            match self.compare_exchange(current, new_data) {
+                Ok(previous) => return Ok(previous),
+                Err(actual) => current = actual,
+            }
+        }
+        Err(current)
+    }
+}
+
+impl<T: AtomicNumber> AtomicShare<T> {
+    /// Adds to the value and returns the previous value
+    ///
+    /// Integers wrap around on overflow.
+    ///
+    /// ## Arguments
+    ///
+    /// * `value` - The value to add
+    pub fn fetch_add(&self, value: T) -> T {
+        T::fetch_add(&self.data, value)
+    }
+
+    /// Subtracts from the value and returns the previous value
+    ///
+    /// Integers wrap around on overflow.
+    ///
+    /// ## Arguments
+    ///
+    /// * `value` - The value to subtract
+    pub fn fetch_sub(&self, value: T) -> T {
+        T::fetch_sub(&self.data, value)
+    }
+
+    /// Sets the value to the maximum of itself and `value`, returning the previous value
+    ///
+    /// ## Arguments
+    ///
+    /// * `value` - The value to compare with
+    pub fn fetch_max(&self, value: T) -> T {
+        T::fetch_max(&self.data, value)
+    }
+
+    /// Sets the value to the minimum of itself and `value`, returning the previous value
+    ///
+    /// ## Arguments
+    ///
+    /// * `value` - The value to compare with
+    pub fn fetch_min(&self, value: T) -> T {
+        T::fetch_min(&self.data, value)
+    }
+
+    /// Atomically increments the value by one
+    pub fn increment(&self) {
+        self.fetch_add(T::ONE);
+    }
+
+    /// Atomically decrements the value by one
+    pub fn decrement(&self) {
+        self.fetch_sub(T::ONE);
+    }
+
+    /// Atomically adds a value
+    ///
+    /// ## Arguments
+    ///
+    /// * `value` - The value to add
+    pub fn add(&self, value: T) {
+        self.fetch_add(value);
+    }
+}
+
+impl<T: AtomicBits> AtomicShare<T> {
+    /// Bitwise "and" with `value`, returning the previous value
+    ///
+    /// ## Arguments
+    ///
+    /// * `value` - The operand
+    pub fn fetch_and(&self, value: T) -> T {
+        T::fetch_and(&self.data, value)
+    }
+
+    /// Bitwise "or" with `value`, returning the previous value
+    ///
+    /// ## Arguments
+    ///
+    /// * `value` - The operand
+    pub fn fetch_or(&self, value: T) -> T {
+        T::fetch_or(&self.data, value)
+    }
+
+    /// Bitwise "xor" with `value`, returning the previous value
+    ///
+    /// ## Arguments
+    ///
+    /// * `value` - The operand
+    pub fn fetch_xor(&self, value: T) -> T {
+        T::fetch_xor(&self.data, value)
+    }
+}
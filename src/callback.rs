@@ -0,0 +1,251 @@
+//! # Callback Module - On-Change Callbacks
+//!
+//! This module provides the registration handle returned by
+//! `ThreadShare::on_change()` and `ThreadShare::on_change_with()`.
+//!
+//! ## Overview
+//!
+//! A callback is invoked after every committed `set()`, `update()` and
+//! `write()` with the version the data had *before* the change and a snapshot
+//! of the new data. Callbacks never run while the share is locked, so they may
+//! freely read or even modify the share they are registered on.
+//!
+//! Where a callback runs is controlled by `CallbackMode`:
+//!
+//! - **`CallbackMode::Inline`** - On the writing thread, right after the write
+//!   lock is released. Concurrent writers may run callbacks out of version order.
+//! - **`CallbackMode::Thread`** - On a dedicated notifier thread per callback,
+//!   strictly in version order. Writers never wait for the callback.
+//!
+//! ## Unregistering
+//!
+//! Registration returns a `CallbackGuard`; dropping it unregisters the
+//! callback. A threaded callback finishes the changes already handed to it
+//! before its thread exits.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use thread_share::share;
+//! use std::sync::atomic::{AtomicUsize, Ordering};
+//! use std::sync::Arc;
+//!
+//! let data = share!(0);
+//! let calls = Arc::new(AtomicUsize::new(0));
+//!
+//! let counter = calls.clone();
+//! let guard = data.on_change(move |_old_version, _value| {
+//!     counter.fetch_add(1, Ordering::SeqCst);
+//! });
+//!
+//! data.set(1);
+//! data.update(|x| *x += 1);
+//! assert_eq!(calls.load(Ordering::SeqCst), 2);
+//!
+//! drop(guard);
+//! data.set(3);
+//! assert_eq!(calls.load(Ordering::SeqCst), 2);
+//! ```
+
+use parking_lot::Mutex;
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Weak};
+use std::thread;
+
+/// Where on-change callbacks are executed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CallbackMode {
+    /// Run on the writing thread after the write lock is released
+    #[default]
+    Inline,
+    /// Run on a dedicated notifier thread, in version order
+    Thread,
+}
+
+type InlineCallback<T> = Arc<dyn Fn(u64, &T) + Send + Sync>;
+type Watch<T> = Box<dyn Fn(&T) + Send + Sync>;
+
+enum Entry<T> {
+    Inline(InlineCallback<T>),
+    Thread(Sender<(u64, T)>),
+    /// Internal observer run under the write lock, without a snapshot
+    Watch(Watch<T>),
+}
+
+struct CallbackState<T> {
+    entries: Vec<(u64, Entry<T>)>,
+    next_key: u64,
+    snapshot: Option<fn(&T) -> T>,
+}
+
+/// Registry of on-change callbacks shared by all handles of a `ThreadShare<T>`
+pub(crate) struct Callbacks<T> {
+    state: Mutex<CallbackState<T>>,
+}
+
+/// Callbacks collected under the write lock, to be run once it is released
+pub(crate) struct PendingCallbacks<T> {
+    old_version: u64,
+    value: Option<T>,
+    callbacks: Vec<InlineCallback<T>>,
+}
+
+impl<T> PendingCallbacks<T> {
+    /// Runs the collected inline callbacks; call after releasing the lock
+    pub(crate) fn run(self) {
+        if let Some(value) = self.value {
+            for callback in self.callbacks {
+                callback(self.old_version, &value);
+            }
+        }
+    }
+}
+
+impl<T> Callbacks<T> {
+    pub(crate) fn new() -> Self {
+        Self {
+            state: Mutex::new(CallbackState {
+                entries: Vec::new(),
+                next_key: 0,
+                snapshot: None,
+            }),
+        }
+    }
+
+    /// Hands a committed change to the callbacks; called with the write lock held
+    ///
+    /// Threaded callbacks receive their snapshot right away so they observe
+    /// changes in version order, and watches run right away on the data
+    /// itself. Inline callbacks are returned for the caller to run after the
+    /// lock is released.
+    pub(crate) fn collect(&self, old_version: u64, data: &T) -> PendingCallbacks<T> {
+        let mut state = self.state.lock();
+        let mut pending = PendingCallbacks {
+            old_version,
+            value: None,
+            callbacks: Vec::new(),
+        };
+        if state.entries.is_empty() {
+            return pending;
+        }
+
+        let snapshot = state.snapshot;
+        state.entries.retain(|(_, entry)| match entry {
+            Entry::Inline(callback) => {
+                pending.callbacks.push(Arc::clone(callback));
+                true
+            }
+            // A closed channel means the callback thread panicked
+            Entry::Thread(sender) => {
+                let snapshot = snapshot.expect("threaded callbacks set the snapshot function");
+                sender.send((old_version, snapshot(data))).is_ok()
+            }
+            Entry::Watch(watch) => {
+                watch(data);
+                true
+            }
+        });
+        if !pending.callbacks.is_empty() {
+            pending.value = snapshot.map(|snapshot| snapshot(data));
+        }
+        pending
+    }
+
+    fn insert(&self, snapshot: Option<fn(&T) -> T>, entry: Entry<T>) -> u64 {
+        let mut state = self.state.lock();
+        let key = state.next_key;
+        state.next_key += 1;
+        if snapshot.is_some() {
+            state.snapshot = snapshot;
+        }
+        state.entries.push((key, entry));
+        key
+    }
+}
+
+impl<T> Callbacks<T>
+where
+    T: Send + 'static,
+{
+    /// Registers a callback and returns the guard that unregisters it
+    pub(crate) fn register<F>(
+        self: &Arc<Self>,
+        mode: CallbackMode,
+        snapshot: fn(&T) -> T,
+        callback: F,
+    ) -> CallbackGuard
+    where
+        F: Fn(u64, &T) + Send + Sync + 'static,
+    {
+        let entry = match mode {
+            CallbackMode::Inline => Entry::Inline(Arc::new(callback)),
+            CallbackMode::Thread => {
+                let (sender, receiver) = mpsc::channel::<(u64, T)>();
+                thread::Builder::new()
+                    .name("thread-share-callback".to_string())
+                    .spawn(move || {
+                        for (old_version, value) in receiver {
+                            callback(old_version, &value);
+                        }
+                    })
+                    .expect("Failed to spawn callback thread");
+                Entry::Thread(sender)
+            }
+        };
+        let key = self.insert(Some(snapshot), entry);
+        let registry = Arc::downgrade(self) as Weak<dyn Unregister>;
+        CallbackGuard { registry, key }
+    }
+
+    /// Registers a function run on the data under the write lock after every
+    /// change, and returns the guard that unregisters it
+    ///
+    /// Unlike callbacks, watches need no snapshot, so `T` need not be `Clone`.
+    /// They must be quick and must not touch the share they watch.
+    pub(crate) fn watch<F>(self: &Arc<Self>, watch: F) -> CallbackGuard
+    where
+        F: Fn(&T) + Send + Sync + 'static,
+    {
+        let key = self.insert(None, Entry::Watch(Box::new(watch)));
+        let registry = Arc::downgrade(self) as Weak<dyn Unregister>;
+        CallbackGuard { registry, key }
+    }
+}
+
+trait Unregister: Send + Sync {
+    fn unregister(&self, key: u64);
+}
+
+impl<T> Unregister for Callbacks<T>
+where
+    T: Send,
+{
+    fn unregister(&self, key: u64) {
+        let removed = {
+            let mut state = self.state.lock();
+            let position = state.entries.iter().position(|(k, _)| *k == key);
+            position.map(|index| state.entries.remove(index))
+        };
+        // Dropped outside the lock: a threaded callback's sender closes its channel
+        drop(removed);
+    }
+}
+
+/// Registration handle for an on-change callback
+///
+/// Returned by `ThreadShare::on_change()` and `ThreadShare::on_change_with()`.
+/// Dropping the guard unregisters the callback; it does not keep the share
+/// alive.
+#[must_use = "the callback is unregistered as soon as the guard is dropped"]
+pub struct CallbackGuard {
+    registry: Weak<dyn Unregister>,
+    key: u64,
+}
+
+impl Drop for CallbackGuard {
+    fn drop(&mut self) {
+        if let Some(registry) = self.registry.upgrade() {
+            registry.unregister(self.key);
+        }
+    }
+}
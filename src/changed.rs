@@ -0,0 +1,204 @@
+//! # Changed Module - Async Change Notification
+//!
+//! This module provides the futures returned by `ThreadShare::changed()`,
+//! `ThreadShare::changed_since()` and `ThreadShare::changed_timeout()`.
+//!
+//! ## Overview
+//!
+//! The futures are built on plain `std::task::Waker`s and do not depend on
+//! any async runtime, so they work the same under `tokio`, `async-std` or a
+//! hand-written executor. Awaiting them never blocks an executor thread.
+//!
+//! - **`Changed`** - Resolves to the new version once the data changed
+//! - **`ChangedTimeout`** - Same, but resolves to `None` after a timeout
+//!
+//! ## Cancellation Safety
+//!
+//! Dropping a future before it completes unregisters its waker and leaves the
+//! handle's observed version untouched, so the change is still reported by the
+//! next `changed()` or `wait_for_change()` call. This makes the futures safe to
+//! use in `select!` loops.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use thread_share::share;
+//! use std::time::Duration;
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let data = share!(0);
+//!     let clone = data.clone();
+//!
+//!     std::thread::spawn(move || {
+//!         std::thread::sleep(Duration::from_millis(50));
+//!         clone.set(42);
+//!     });
+//!
+//!     // Does not block the executor while waiting
+//!     data.changed().await;
+//!     assert_eq!(data.get(), 42);
+//!
+//!     // Nothing else changes: times out
+//!     let result = data.changed_timeout(Duration::from_millis(20)).await;
+//!     assert_eq!(result, None);
+//! }
+//! ```
+//!
+//! ## Timeouts
+//!
+//! `ChangedTimeout` relies on a single background timer thread shared by the
+//! whole process, the same one that delivers delayed notifications for the
+//! `Coalesce` and `Debounce` policies. It is started on first use and sleeps
+//! while nothing is pending.
+
+use crate::notify::Notifier;
+use crate::timer::timer;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::task::{Context, Poll, Waker};
+use std::time::{Duration, Instant};
+
+/// Future that resolves once the shared data changes
+///
+/// Created by `ThreadShare::changed()` and `ThreadShare::changed_since()`.
+/// Resolves to the new version.
+///
+/// ## Example
+///
+/// ```rust
+/// use thread_share::share;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let data = share!(vec![1, 2, 3]);
+///     let clone = data.clone();
+///
+///     tokio::spawn(async move {
+///         clone.update(|v| v.push(4));
+///     });
+///
+///     let version = data.changed().await;
+///     assert!(version > 0);
+///     assert_eq!(data.get(), vec![1, 2, 3, 4]);
+/// }
+/// ```
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Changed<'a> {
+    notifier: &'a Notifier,
+    seen: Option<&'a AtomicU64>,
+    since: u64,
+    key: Option<u64>,
+}
+
+impl<'a> Changed<'a> {
+    pub(crate) fn new(notifier: &'a Notifier, seen: Option<&'a AtomicU64>, since: u64) -> Self {
+        Self {
+            notifier,
+            seen,
+            since,
+            key: None,
+        }
+    }
+}
+
+impl Future for Changed<'_> {
+    type Output = u64;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<u64> {
+        let this = &mut *self;
+        let version = std::task::ready!(this.notifier.poll_since(this.since, &mut this.key, cx));
+        if let Some(seen) = this.seen {
+            seen.fetch_max(version, Ordering::AcqRel);
+        }
+        Poll::Ready(version)
+    }
+}
+
+impl Drop for Changed<'_> {
+    fn drop(&mut self) {
+        if let Some(key) = self.key.take() {
+            self.notifier.unregister(key);
+        }
+    }
+}
+
+/// Future that resolves once the shared data changes or a timeout expires
+///
+/// Created by `ThreadShare::changed_timeout()`. Resolves to `Some(new_version)`
+/// if the data changed, `None` if the timeout was reached first.
+///
+/// ## Example
+///
+/// ```rust
+/// use thread_share::share;
+/// use std::time::Duration;
+///
+/// #[async_std::main]
+/// async fn main() {
+///     let data = share!(0);
+///
+///     // Nobody writes: times out
+///     assert_eq!(data.changed_timeout(Duration::from_millis(10)).await, None);
+///
+///     data.set(1);
+///     let other = data.clone();
+///     other.set(2);
+///     assert!(data.changed_timeout(Duration::from_millis(10)).await.is_some());
+/// }
+/// ```
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct ChangedTimeout<'a> {
+    changed: Changed<'a>,
+    deadline: Instant,
+    /// Timer entry waking this waker at the deadline
+    timer_entry: Option<(u64, Waker)>,
+}
+
+impl<'a> ChangedTimeout<'a> {
+    pub(crate) fn new(changed: Changed<'a>, timeout: Duration) -> Self {
+        Self {
+            changed,
+            deadline: Instant::now() + timeout,
+            timer_entry: None,
+        }
+    }
+}
+
+impl Future for ChangedTimeout<'_> {
+    type Output = Option<u64>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<u64>> {
+        let this = &mut *self;
+        if let Poll::Ready(version) = Pin::new(&mut this.changed).poll(cx) {
+            return Poll::Ready(Some(version));
+        }
+        if Instant::now() >= this.deadline {
+            return Poll::Ready(None);
+        }
+        match &this.timer_entry {
+            Some((_, waker)) if waker.will_wake(cx.waker()) => {}
+            _ => {
+                if let Some((key, _)) = this.timer_entry.take() {
+                    timer().cancel(key);
+                }
+                let waker = cx.waker().clone();
+                let key = timer().schedule(this.deadline, {
+                    let waker = waker.clone();
+                    move || waker.wake()
+                });
+                this.timer_entry = Some((key, waker));
+            }
+        }
+        Poll::Pending
+    }
+}
+
+impl Drop for ChangedTimeout<'_> {
+    fn drop(&mut self) {
+        if let Some((key, _)) = self.timer_entry.take() {
+            timer().cancel(key);
+        }
+    }
+}
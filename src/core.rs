@@ -18,6 +18,7 @@
 //!
 //! - **Automatic Cloning**: Each thread gets its own clone for safe access
 //! - **Change Detection**: Built-in waiting mechanisms for data changes
+//! - **Versioning**: Every committed mutation bumps a generation number
 //! - **Flexible Access**: Read, write, and update operations with proper locking
 //! - **Condition Variables**: Efficient waiting for data modifications
 //! - **Thread Safety**: Implements `Send` and `Sync` automatically
@@ -44,6 +45,64 @@
 //! assert_eq!(result, vec![1, 2, 3, 4]);
 //! ```
 //!
+//! ### Change Detection and Versions
+//!
+//! `set()`, `update()` and `write()` all bump the share's version and wake
+//! waiting threads, or fewer of them with a coalescing or debouncing
+//! `NotifyPolicy` (see `with_notify_policy()`). Every handle remembers the last version it has observed,
+//! so `wait_for_change()` returns immediately if the data already changed
+//! since then, even if the change happened before the call:
+//!
+//! ```rust
+//! use thread_share::ThreadShare;
+//! use std::time::Duration;
+//!
+//! let data = ThreadShare::new(0);
+//! let clone = data.clone();
+//!
+//! let seen = data.version();
+//! clone.set(1); // happens before anybody waits
+//!
+//! // Not lost: the share has moved past `seen`
+//! let version = data.wait_for_change_since(seen, Duration::from_millis(10));
+//! assert_eq!(version, Some(seen + 1));
+//! ```
+//!
+//! ### Async Waiting
+//!
+//! `changed()`, `changed_since()` and `changed_timeout()` are the async
+//! counterparts of the `wait_for_change*()` methods. They are woken by the same
+//! notifications and work with any async runtime (see the `changed` module).
+//!
+//! ### Subscriptions
+//!
+//! `subscribe()` and `subscribe_versions()` deliver every change to a
+//! `Subscription`, which can be read by blocking, as an `mpsc::Receiver` or as
+//! an async `Stream` (see the `subscribe` module).
+//!
+//! ### On-Change Callbacks
+//!
+//! `on_change()` registers a callback that runs after every committed change,
+//! outside the write lock (see the `callback` module). Dropping the returned
+//! guard unregisters it.
+//!
+//! ### Derived Shares
+//!
+//! `map()` creates a read-only `Derived<U>` that recomputes lazily when the
+//! data changed; `combine()` does the same for two shares (see the `derived`
+//! module).
+//!
+//! ### Projections
+//!
+//! `project()` creates a `Projected<U>` handle to one field of the data,
+//! notified only when that field changes (see the `projection` module).
+//!
+//! ### Lock Guards
+//!
+//! `read_guard()`, `write_guard()` and `upgradable_read()` hold the lock until
+//! the returned guard is dropped; dropping a write guard commits the change
+//! (see the `guard` module).
+//!
 //! ### Performance Characteristics
 //!
 //! - **Read Operations**: Multiple threads can read simultaneously
@@ -107,7 +166,7 @@
 //!
 //! - **Arc**: Provides reference counting for shared ownership
 //! - **RwLock**: Ensures exclusive write access and concurrent read access
-//! - **Mutex**: Protects condition variable access
+//! - **Mutex**: Guards the version counter shared by all handles
 //! - **Condvar**: Enables efficient waiting for changes
 //!
 //! ## Best Practices
@@ -118,10 +177,19 @@
 //! 4. **Minimize lock contention**: Keep critical sections as short as possible
 //! 5. **Handle errors gracefully**: Always check return values from operations
 
-use parking_lot::RwLock;
-use std::sync::{Arc, Condvar, Mutex};
-use std::time::Duration;
+use crate::callback::{CallbackGuard, CallbackMode, Callbacks, PendingCallbacks};
+use crate::changed::{Changed, ChangedTimeout};
+use crate::error::{Error, Result};
+use crate::metrics::{Access, Probe, Tracked};
+use crate::notify::{Notifier, NotifyPolicy};
+use crate::subscribe::{SubscribePolicy, Subscribers, Subscription, VersionSubscription};
+use parking_lot::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, Instant};
 
+#[cfg(feature = "metrics")]
+use crate::metrics::LockStats;
 #[cfg(feature = "serialize")]
 use serde::{de::DeserializeOwned, Serialize};
 
@@ -137,7 +205,7 @@ where
     ///
     /// ## Returns
     ///
-    /// JSON string representation of the data, or error string if serialization fails.
+    /// JSON string representation of the data, or `Error::Serialize` if serialization fails.
     ///
     /// ## Example
     ///
@@ -151,9 +219,9 @@ where
     ///     assert_eq!(json, "[1,2,3]");
     /// }
     /// ```
-    pub fn to_json(&self) -> Result<String, String> {
-        let data = self.data.read();
-        serde_json::to_string(&*data).map_err(|e| format!("Serialization failed: {}", e))
+    pub fn to_json(&self) -> Result<String> {
+        let data = self.read_lock();
+        serde_json::to_string(&*data).map_err(Error::Serialize)
     }
 
     /// Deserializes data from JSON string
@@ -166,7 +234,7 @@ where
     ///
     /// ## Returns
     ///
-    /// `Ok(())` on success, `Err(String)` if deserialization fails.
+    /// `Ok(())` on success, `Err(Error::Deserialize)` if deserialization fails.
     ///
     /// ## Example
     ///
@@ -180,9 +248,8 @@ where
     ///     assert_eq!(data.get(), vec![1, 2, 3]);
     /// }
     /// ```
-    pub fn from_json(&self, json: &str) -> Result<(), String> {
-        let deserialized: T =
-            serde_json::from_str(json).map_err(|e| format!("Deserialization failed: {}", e))?;
+    pub fn from_json(&self, json: &str) -> Result<()> {
+        let deserialized: T = serde_json::from_str(json).map_err(Error::Deserialize)?;
 
         self.set(deserialized);
         Ok(())
@@ -198,6 +265,7 @@ where
 ///
 /// - **Automatic Cloning**: Each thread gets its own clone for safe access
 /// - **Change Detection**: Built-in waiting mechanisms for data changes
+/// - **Versioning**: Monotonic generation number exposed through `version()`
 /// - **Flexible Access**: Read, write, and update operations with proper locking
 /// - **Condition Variables**: Efficient waiting for data modifications
 /// - **Thread Safety**: Implements `Send` and `Sync` automatically
@@ -227,9 +295,23 @@ where
 /// - **Memory Overhead**: Minimal overhead from Arc and RwLock structures
 pub struct ThreadShare<T> {
     data: Arc<RwLock<T>>,
-    sender: Arc<Mutex<()>>,
-    receiver: Arc<Mutex<()>>,
-    condvar: Arc<Condvar>,
+    notifier: Arc<Notifier>,
+    subscribers: Arc<Subscribers<T>>,
+    callbacks: Arc<Callbacks<T>>,
+    registries: Arc<parking_lot::Mutex<Registries<T>>>,
+    probe: Probe,
+    /// Last version observed through this handle
+    seen: AtomicU64,
+}
+
+/// Subscription and callback registries of a share, for its weak handles
+///
+/// Shared by every handle of the share. Weak handles upgraded while the data
+/// is only kept alive by something else, such as an `as_arc_locked()` result,
+/// recreate the registries here so that they all end up sharing them.
+struct Registries<T> {
+    subscribers: Weak<Subscribers<T>>,
+    callbacks: Weak<Callbacks<T>>,
 }
 
 // Automatically implement Send and Sync for ThreadShare
@@ -253,14 +335,112 @@ impl<T> ThreadShare<T> {
     /// let message = ThreadShare::new(String::from("Hello"));
     /// ```
     pub fn new(data: T) -> Self {
+        let data = Arc::new(RwLock::new(data));
+        let subscribers = Arc::new(Subscribers::new());
+        let callbacks = Arc::new(Callbacks::new());
         Self {
-            data: Arc::new(RwLock::new(data)),
-            sender: Arc::new(Mutex::new(())),
-            receiver: Arc::new(Mutex::new(())),
-            condvar: Arc::new(Condvar::new()),
+            probe: Probe::new(&data),
+            data,
+            notifier: Arc::new(Notifier::new()),
+            registries: Arc::new(parking_lot::Mutex::new(Registries {
+                subscribers: Arc::downgrade(&subscribers),
+                callbacks: Arc::downgrade(&callbacks),
+            })),
+            subscribers,
+            callbacks,
+            seen: AtomicU64::new(0),
         }
     }
 
+    /// Names the share for diagnostics
+    ///
+    /// The name appears in the reentrancy panics and deadlock reports of the
+    /// `debug-locks` feature. Name the share right after creating it: clones
+    /// made before the call keep the old name.
+    ///
+    /// ## Arguments
+    ///
+    /// * `name` - Name shown in diagnostics
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::ThreadShare;
+    ///
+    /// let config = ThreadShare::new(0).with_name("config");
+    /// assert_eq!(config.name(), Some("config"));
+    /// ```
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.probe.set_name(name);
+        self
+    }
+
+    /// Gets the name given with `with_name()`
+    pub fn name(&self) -> Option<&str> {
+        self.probe.name()
+    }
+
+    /// Sets how long the lock may be held before a warning is reported
+    ///
+    /// Requires the `lock-warnings` feature. Overrides the global threshold
+    /// of `lock_warnings::set_threshold()` for this share; use
+    /// `Duration::MAX` to never warn. Like `with_name()`, call it right after
+    /// creating the share.
+    ///
+    /// ## Arguments
+    ///
+    /// * `threshold` - Maximum hold time before a warning
+    #[cfg(feature = "lock-warnings")]
+    pub fn with_hold_threshold(mut self, threshold: std::time::Duration) -> Self {
+        self.probe.set_hold_threshold(threshold);
+        self
+    }
+
+    /// Sets when waiting threads and tasks are woken after a change
+    ///
+    /// By default every change wakes every waiter. For data written at a high
+    /// rate, `NotifyPolicy::Coalesce` and `NotifyPolicy::Debounce` limit the
+    /// wakeups of `wait_for_change()`, `changed()` and the other waiting
+    /// methods, and of shares derived from this one. Waiters still observe
+    /// the latest version when they wake. Subscriptions and on-change
+    /// callbacks are not affected.
+    ///
+    /// The policy is shared by all clones of the share.
+    ///
+    /// ## Arguments
+    ///
+    /// * `policy` - When to wake waiters
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::{NotifyPolicy, ThreadShare};
+    /// use std::thread;
+    /// use std::time::Duration;
+    ///
+    /// let progress = ThreadShare::new(0)
+    ///     .with_notify_policy(NotifyPolicy::Debounce(Duration::from_millis(20)));
+    /// let writer = progress.clone();
+    ///
+    /// thread::spawn(move || {
+    ///     for _ in 0..100 {
+    ///         writer.update(|x| *x += 1);
+    ///     }
+    /// });
+    ///
+    /// // Woken once the writer has been quiet for 20 ms
+    /// progress.wait_until_forever(|x| *x == 100);
+    /// ```
+    pub fn with_notify_policy(self, policy: NotifyPolicy) -> Self {
+        self.notifier.set_policy(policy);
+        self
+    }
+
+    /// Gets the policy set with `with_notify_policy()`
+    pub fn notify_policy(&self) -> NotifyPolicy {
+        self.notifier.policy()
+    }
+
     /// Gets a copy of data (for types implementing Clone)
     ///
     /// ## Requirements
@@ -284,7 +464,7 @@ impl<T> ThreadShare<T> {
     where
         T: Clone,
     {
-        self.data.read().clone()
+        self.read_lock().clone()
     }
 
     /// Gets a reference to data for reading
@@ -316,14 +496,15 @@ impl<T> ThreadShare<T> {
     where
         F: FnOnce(&T) -> R,
     {
-        let data = self.data.read();
+        let data = self.read_lock();
         f(&data)
     }
 
     /// Gets a mutable reference to data
     ///
     /// This method provides mutable access to the data through a closure.
-    /// Only one thread can write at a time.
+    /// Only one thread can write at a time. Like `update()`, it bumps the
+    /// version and notifies waiting threads once the closure returns.
     ///
     /// ## Arguments
     ///
@@ -350,8 +531,7 @@ impl<T> ThreadShare<T> {
     where
         F: FnOnce(&mut T) -> R,
     {
-        let mut data = self.data.write();
-        f(&mut data)
+        self.apply(self.write_lock(), f)
     }
 
     /// Sets new data and notifies waiting threads
@@ -380,297 +560,1454 @@ impl<T> ThreadShare<T> {
     /// assert_eq!(data.get(), 100);
     /// ```
     pub fn set(&self, new_data: T) {
-        let mut data = self.data.write();
-        *data = new_data;
-        self.condvar.notify_all();
+        self.apply(self.write_lock(), |data| *data = new_data);
     }
 
-    /// Updates data using a function and notifies waiting threads
+    /// Sets new data only if the current data equals `expected`
     ///
-    /// This method allows you to modify the data through a closure and
-    /// automatically notifies waiting threads of the change.
+    /// The comparison and the write happen under the same lock, so no other
+    /// writer can change the data in between. Waiting threads are notified
+    /// only if the data was set.
+    ///
+    /// ## Requirements
+    ///
+    /// The type `T` must implement `PartialEq` trait.
     ///
     /// ## Arguments
     ///
-    /// * `f` - Closure that receives a mutable reference to the data
+    /// * `expected` - The value the data must currently have
+    /// * `new_data` - The new data to set
+    ///
+    /// ## Returns
+    ///
+    /// `true` if the data was set, `false` if it did not equal `expected`.
     ///
     /// ## Example
     ///
     /// ```rust
     /// use thread_share::ThreadShare;
     ///
-    /// let counter = ThreadShare::new(0);
-    ///
-    /// counter.update(|x| *x += 1);
-    /// assert_eq!(counter.get(), 1);
+    /// let state = ThreadShare::new("idle");
     ///
-    /// counter.update(|x| *x *= 2);
-    /// assert_eq!(counter.get(), 2);
+    /// assert!(state.compare_and_set(&"idle", "running"));
+    /// assert!(!state.compare_and_set(&"idle", "running"));
+    /// assert_eq!(state.get(), "running");
+    /// assert_eq!(state.version(), 1);
     /// ```
-    pub fn update<F>(&self, f: F)
+    pub fn compare_and_set(&self, expected: &T, new_data: T) -> bool
     where
-        F: FnOnce(&mut T),
+        T: PartialEq,
     {
-        let mut data = self.data.write();
-        f(&mut data);
-        self.condvar.notify_all();
+        let data = self
+            .probe
+            .acquire(Access::Upgradable, || self.data.upgradable_read());
+        if *data != *expected {
+            return false;
+        }
+        self.apply(data.upgrade(&self.probe), |data| *data = new_data);
+        true
     }
 
-    /// Waits for data changes with timeout
+    /// Sets new data unless it equals the current data
+    ///
+    /// When the value is unchanged, nothing is written and nobody is
+    /// notified: no version bump, no wakeups, no subscription deliveries and
+    /// no callbacks. Readers are not blocked while the values are compared.
     ///
-    /// This method waits for a change notification with a specified timeout.
+    /// ## Requirements
+    ///
+    /// The type `T` must implement `PartialEq` trait.
     ///
     /// ## Arguments
     ///
-    /// * `timeout` - Maximum time to wait for changes
+    /// * `new_data` - The new data to set
     ///
     /// ## Returns
     ///
-    /// `true` if the timeout was reached, `false` if a change occurred.
+    /// `true` if the data was set, `false` if it was already equal.
     ///
     /// ## Example
     ///
     /// ```rust
     /// use thread_share::ThreadShare;
-    /// use std::time::Duration;
-    ///
-    /// let data = ThreadShare::new(0);
-    /// let clone = data.clone();
     ///
-    /// // Spawn thread that will change data after 200ms
-    /// std::thread::spawn(move || {
-    ///     std::thread::sleep(Duration::from_millis(200));
-    ///     clone.set(100);
-    /// });
+    /// let config = ThreadShare::new(String::from("debug=false"));
     ///
-    /// // Wait for change with 100ms timeout
-    /// let timed_out = data.wait_for_change(Duration::from_millis(100));
-    /// assert!(timed_out); // Should timeout
+    /// // Reloading an identical config does not trigger a rebuild
+    /// assert!(!config.set_if_changed(String::from("debug=false")));
+    /// assert_eq!(config.version(), 0);
     ///
-    /// // Wait for change with 300ms timeout
-    /// let timed_out = data.wait_for_change(Duration::from_millis(300));
-    /// assert!(!timed_out); // Should not timeout
+    /// assert!(config.set_if_changed(String::from("debug=true")));
+    /// assert_eq!(config.version(), 1);
     /// ```
-    pub fn wait_for_change(&self, timeout: Duration) -> bool {
-        let guard = self.receiver.lock().unwrap();
-        let result = self.condvar.wait_timeout(guard, timeout).unwrap();
-        result.1.timed_out()
+    pub fn set_if_changed(&self, new_data: T) -> bool
+    where
+        T: PartialEq,
+    {
+        let data = self
+            .probe
+            .acquire(Access::Upgradable, || self.data.upgradable_read());
+        if *data == new_data {
+            return false;
+        }
+        self.apply(data.upgrade(&self.probe), |data| *data = new_data);
+        true
     }
 
-    /// Waits for data changes infinitely
+    /// Sets new data and returns the previous data
+    ///
+    /// ## Arguments
+    ///
+    /// * `new_data` - The new data to set
+    ///
+    /// ## Returns
     ///
-    /// This method waits indefinitely for a change notification.
+    /// The data before the call.
     ///
     /// ## Example
     ///
     /// ```rust
     /// use thread_share::ThreadShare;
-    /// use std::thread;
-    /// use std::time::Duration;
-    ///
-    /// let data = ThreadShare::new(0);
-    /// let clone = data.clone();
     ///
-    /// thread::spawn(move || {
-    ///     thread::sleep(Duration::from_millis(100));
-    ///     clone.set(100);
-    /// });
-    ///
-    /// // Wait indefinitely for change
-    /// data.wait_for_change_forever();
-    /// assert_eq!(data.get(), 100);
+    /// let data = ThreadShare::new(1);
+    /// assert_eq!(data.replace(2), 1);
+    /// assert_eq!(data.get(), 2);
     /// ```
-    pub fn wait_for_change_forever(&self) {
-        let guard = self.receiver.lock().unwrap();
-        let _unused = self.condvar.wait(guard).unwrap();
+    pub fn replace(&self, new_data: T) -> T {
+        self.apply(self.write_lock(), |data| std::mem::replace(data, new_data))
     }
 
-    /// Creates a clone for use in another thread
+    /// Takes the data, leaving the default value in its place
     ///
-    /// This method creates a new `ThreadShare<T>` instance that shares
-    /// the same underlying data. Each clone can be safely moved to
-    /// different threads.
+    /// ## Requirements
+    ///
+    /// The type `T` must implement `Default` trait.
     ///
     /// ## Returns
     ///
-    /// A new `ThreadShare<T>` instance sharing the same data.
+    /// The data before the call.
     ///
     /// ## Example
     ///
     /// ```rust
     /// use thread_share::ThreadShare;
-    /// use std::thread;
-    /// use std::time::Duration;
-    ///
-    /// let data = ThreadShare::new(0);
-    /// let clone1 = data.clone();
-    /// let clone2 = data.clone();
-    ///
-    /// // Each clone can be used in different threads
-    /// thread::spawn(move || {
-    ///     clone1.set(100);
-    /// });
     ///
-    /// thread::spawn(move || {
-    ///     clone2.set(200);
-    /// });
+    /// let queue = ThreadShare::new(vec![1, 2, 3]);
     ///
-    /// // Main thread waits for changes
-    /// data.wait_for_change_forever();
+    /// let batch = queue.take();
+    /// assert_eq!(batch, vec![1, 2, 3]);
+    /// assert!(queue.get().is_empty());
     /// ```
-    pub fn clone(&self) -> Self {
-        Self {
-            data: Arc::clone(&self.data),
-            sender: Arc::clone(&self.sender),
-            receiver: Arc::clone(&self.receiver),
-            condvar: Arc::clone(&self.condvar),
-        }
+    pub fn take(&self) -> T
+    where
+        T: Default,
+    {
+        self.apply(self.write_lock(), std::mem::take)
     }
 
-    /// Gets Arc on data for transfer to thread without cloning
+    /// Updates data using a function and notifies waiting threads
     ///
-    /// This method converts the `ThreadShare<T>` into an `Arc<RwLock<T>>`,
-    /// which can be moved into threads without cloning the `ThreadShare` itself.
+    /// This method allows you to modify the data through a closure and
+    /// automatically notifies waiting threads of the change.
     ///
-    /// ## Returns
+    /// ## Arguments
     ///
-    /// An `Arc<RwLock<T>>` containing the shared data.
+    /// * `f` - Closure that receives a mutable reference to the data
     ///
     /// ## Example
     ///
     /// ```rust
     /// use thread_share::ThreadShare;
-    /// use std::thread;
     ///
-    /// let data = ThreadShare::new(0);
-    /// let arc_data = data.into_arc();
+    /// let counter = ThreadShare::new(0);
     ///
-    /// thread::spawn(move || {
-    ///     let mut guard = arc_data.write();
-    ///     *guard += 100;
-    /// });
+    /// counter.update(|x| *x += 1);
+    /// assert_eq!(counter.get(), 1);
+    ///
+    /// counter.update(|x| *x *= 2);
+    /// assert_eq!(counter.get(), 2);
     /// ```
-    pub fn into_arc(self) -> Arc<RwLock<T>> {
-        self.data
+    pub fn update<F>(&self, f: F)
+    where
+        F: FnOnce(&mut T),
+    {
+        self.apply(self.write_lock(), f);
     }
 
-    /// Gets Arc<RwLock<T>> for version with locks
+    /// Runs `f` on locked data, then commits and releases the lock
+    fn apply<F, R>(&self, mut data: Tracked<'_, RwLockWriteGuard<'_, T>>, f: F) -> R
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        let result = f(&mut data);
+        let pending = self.commit(&data);
+        drop(data);
+        pending.run();
+        result
+    }
+
+    /// Updates data through a scratch copy that is committed only on success
     ///
-    /// This method returns an `Arc<RwLock<T>>` that shares the same data
-    /// as this `ThreadShare<T>`. This is useful when you need to work
-    /// directly with the underlying `Arc<RwLock<T>>` structure.
+    /// The closure works on a clone of the data while the write lock is held.
+    /// If it returns normally the clone replaces the data and waiting threads
+    /// are notified. If it panics the shared data is left exactly as it was,
+    /// nobody is notified, and the panic continues to unwind.
     ///
-    /// ## Returns
+    /// ## Requirements
     ///
-    /// An `Arc<RwLock<T>>` sharing the same data.
+    /// The type `T` must implement `Clone` trait.
+    ///
+    /// ## Arguments
+    ///
+    /// * `f` - Closure that receives a mutable reference to the scratch copy
     ///
     /// ## Example
     ///
     /// ```rust
     /// use thread_share::ThreadShare;
+    /// use std::panic::{self, AssertUnwindSafe};
     ///
     /// let data = ThreadShare::new(vec![1, 2, 3]);
-    /// let arc_data = data.as_arc_locked();
     ///
-    /// // Use the Arc<RwLock<T>> directly
-    /// let mut guard = arc_data.write();
-    /// guard.push(4);
-    /// drop(guard);
+    /// let result = panic::catch_unwind(AssertUnwindSafe(|| {
+    ///     data.update_atomic(|v| {
+    ///         v.clear();
+    ///         panic!("failed halfway");
+    ///     });
+    /// }));
     ///
-    /// // Changes are visible in the original ThreadShare
-    /// assert_eq!(data.get(), vec![1, 2, 3, 4]);
+    /// assert!(result.is_err());
+    /// assert_eq!(data.get(), vec![1, 2, 3]); // rolled back
+    /// assert_eq!(data.version(), 0); // nobody was notified
     /// ```
-    pub fn as_arc_locked(&self) -> Arc<RwLock<T>> {
-        Arc::clone(&self.data)
+    pub fn update_atomic<F>(&self, f: F)
+    where
+        F: FnOnce(&mut T),
+        T: Clone,
+    {
+        let result: Result<(), std::convert::Infallible> = self.update_checked(|data| {
+            f(data);
+            Ok(())
+        });
+        let Ok(()) = result;
     }
 
-    /// Gets Arc on data for transfer to thread without cloning (reference)
+    /// Updates data through a scratch copy, committing only on `Ok`
     ///
-    /// This method creates an `Arc<AtomicPtr<T>>` from the current data.
-    /// **Warning**: This creates an independent copy of the data, not a shared reference.
-    /// Changes to the returned `Arc<AtomicPtr<T>>` will not be visible in the original `ThreadShare<T>`.
+    /// Like `update_atomic()`, but the closure can also reject the change by
+    /// returning `Err`: the scratch copy is discarded, the shared data is
+    /// left untouched and nobody is notified.
     ///
     /// ## Requirements
     ///
     /// The type `T` must implement `Clone` trait.
     ///
-    /// ## Returns
+    /// ## Arguments
     ///
-    /// An `Arc<AtomicPtr<T>>` containing a copy of the current data.
+    /// * `f` - Closure that receives a mutable reference to the scratch copy
     ///
-    /// ## Warning
+    /// ## Returns
     ///
-    /// This method creates an **independent copy** of the data. Use `as_arc_locked()` if you
-    /// need a shared reference to the same data.
+    /// The closure's result.
     ///
     /// ## Example
     ///
     /// ```rust
     /// use thread_share::ThreadShare;
     ///
-    /// let data = ThreadShare::new(vec![1, 2, 3]);
-    /// let arc_data = data.as_arc();
-    ///
-    /// // This modifies the copy, not the original
-    /// // Use ArcThreadShare::from_arc(arc_data) to work with it
+    /// let balance = ThreadShare::new(100);
+    ///
+    /// let withdraw = |amount: i32| {
+    ///     balance.update_checked(|balance| {
+    ///         *balance -= amount;
+    ///         if *balance < 0 {
+    ///             return Err("insufficient funds");
+    ///         }
+    ///         Ok(*balance)
+    ///     })
+    /// };
+    ///
+    /// assert_eq!(withdraw(30), Ok(70));
+    /// assert_eq!(withdraw(100), Err("insufficient funds"));
+    /// assert_eq!(balance.get(), 70);
     /// ```
-    pub fn as_arc(&self) -> Arc<std::sync::atomic::AtomicPtr<T>>
+    pub fn update_checked<F, R, E>(&self, f: F) -> Result<R, E>
     where
+        F: FnOnce(&mut T) -> Result<R, E>,
         T: Clone,
     {
-        // Create AtomicPtr from current data
-        let current_data = self.data.read();
-        let cloned_data = (*current_data).clone();
-        let boxed = Box::new(cloned_data);
-        let ptr = Box::into_raw(boxed);
-        Arc::new(std::sync::atomic::AtomicPtr::new(ptr))
-    }
-}
-
-impl<T> Clone for ThreadShare<T> {
-    fn clone(&self) -> Self {
-        self.clone()
+        let mut data = self.write_lock();
+        let mut scratch = data.clone();
+        let result = f(&mut scratch)?;
+        *data = scratch;
+        let pending = self.commit(&data);
+        drop(data);
+        pending.run();
+        Ok(result)
     }
-}
-
-/// Simplified version for simple types
-pub struct SimpleShare<T> {
-    data: Arc<Mutex<T>>,
-}
-
-// Automatically implement Send and Sync for SimpleShare
-unsafe impl<T> Send for SimpleShare<T> {}
-unsafe impl<T> Sync for SimpleShare<T> {}
 
-impl<T> SimpleShare<T> {
-    /// Creates a new SimpleShare
-    ///
-    /// This method creates a new `SimpleShare<T>` instance with the provided data.
-    /// SimpleShare is a simplified version of ThreadShare without change detection.
+    /// Reads data only if the lock is available right away
     ///
     /// ## Arguments
     ///
-    /// * `data` - The initial data to share between threads
+    /// * `f` - Closure that receives a reference to the data
     ///
     /// ## Returns
     ///
-    /// A new `SimpleShare<T>` instance containing the data.
+    /// The closure's result, or `Err(Error::WouldBlock)` if a writer holds
+    /// the lock.
     ///
     /// ## Example
     ///
     /// ```rust
-    /// use thread_share::SimpleShare;
+    /// use thread_share::ThreadShare;
     ///
-    /// let counter = SimpleShare::new(0);
+    /// let data = ThreadShare::new(vec![1, 2, 3]);
+    /// assert_eq!(data.try_read(|v| v.len()).unwrap(), 3);
+    /// ```
+    pub fn try_read<F, R>(&self, f: F) -> Result<R>
+    where
+        F: FnOnce(&T) -> R,
+    {
+        let data = self
+            .probe
+            .try_acquire(Access::Read, || self.data.try_read())
+            .ok_or(Error::WouldBlock)?;
+        Ok(f(&data))
+    }
+
+    /// Writes data only if the lock is available right away
+    ///
+    /// On success this behaves like `write()`, including the notification.
+    ///
+    /// ## Arguments
+    ///
+    /// * `f` - Closure that receives a mutable reference to the data
+    ///
+    /// ## Returns
+    ///
+    /// The closure's result, or `Err(Error::WouldBlock)` if another thread
+    /// holds the lock. In that case nothing is changed or notified.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::{Error, ThreadShare};
+    ///
+    /// let data = ThreadShare::new(0);
+    ///
+    /// let result = data.read(|_| data.try_write(|x| *x += 1));
+    /// assert!(matches!(result, Err(Error::WouldBlock)));
+    ///
+    /// data.try_write(|x| *x += 1).unwrap();
+    /// assert_eq!(data.get(), 1);
+    /// ```
+    pub fn try_write<F, R>(&self, f: F) -> Result<R>
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        let data = self
+            .probe
+            .try_acquire(Access::Write, || self.data.try_write())
+            .ok_or(Error::WouldBlock)?;
+        Ok(self.apply(data, f))
+    }
+
+    /// Updates data only if the lock is available right away
+    ///
+    /// The non-blocking counterpart of `update()`; see `try_write()`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `f` - Closure that receives a mutable reference to the data
+    ///
+    /// ## Returns
+    ///
+    /// `Ok(())` if the data was updated, or `Err(Error::WouldBlock)` if
+    /// another thread holds the lock.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::ThreadShare;
+    ///
+    /// let data = ThreadShare::new(vec![1, 2]);
+    ///
+    /// // Skip the update instead of waiting for a busy writer
+    /// if data.try_update(|v| v.push(3)).is_err() {
+    ///     println!("busy, try again later");
+    /// }
+    /// ```
+    pub fn try_update<F>(&self, f: F) -> Result<()>
+    where
+        F: FnOnce(&mut T),
+    {
+        self.try_write(f)
+    }
+
+    /// Reads data, waiting at most `timeout` for the lock
+    ///
+    /// ## Arguments
+    ///
+    /// * `timeout` - Maximum time to wait for the lock
+    /// * `f` - Closure that receives a reference to the data
+    ///
+    /// ## Returns
+    ///
+    /// The closure's result, or `Err(Error::Timeout)` if the lock could not
+    /// be acquired in time.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::ThreadShare;
+    /// use std::time::Duration;
+    ///
+    /// let data = ThreadShare::new(String::from("hello"));
+    /// let len = data.read_for(Duration::from_millis(10), |s| s.len()).unwrap();
+    /// assert_eq!(len, 5);
+    /// ```
+    pub fn read_for<F, R>(&self, timeout: Duration, f: F) -> Result<R>
+    where
+        F: FnOnce(&T) -> R,
+    {
+        let data = self
+            .probe
+            .try_acquire(Access::Read, || self.data.try_read_for(timeout))
+            .ok_or(Error::Timeout { timeout })?;
+        Ok(f(&data))
+    }
+
+    /// Writes data, waiting at most `timeout` for the lock
+    ///
+    /// On success this behaves like `write()`, including the notification.
+    ///
+    /// ## Arguments
+    ///
+    /// * `timeout` - Maximum time to wait for the lock
+    /// * `f` - Closure that receives a mutable reference to the data
+    ///
+    /// ## Returns
+    ///
+    /// The closure's result, or `Err(Error::Timeout)` if the lock could not
+    /// be acquired in time.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::{Error, ThreadShare};
+    /// use std::time::Duration;
+    ///
+    /// let data = ThreadShare::new(0);
+    ///
+    /// // A reader holds the lock: the writer gives up
+    /// let result = data.read(|_| data.write_for(Duration::from_millis(10), |x| *x = 1));
+    /// assert!(matches!(result, Err(Error::Timeout { .. })));
+    /// assert_eq!(data.get(), 0);
+    /// ```
+    pub fn write_for<F, R>(&self, timeout: Duration, f: F) -> Result<R>
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        let data = self
+            .probe
+            .try_acquire(Access::Write, || self.data.try_write_for(timeout))
+            .ok_or(Error::Timeout { timeout })?;
+        Ok(self.apply(data, f))
+    }
+
+    /// Updates data, waiting at most `timeout` for the lock
+    ///
+    /// The timed counterpart of `update()`; see `write_for()`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `timeout` - Maximum time to wait for the lock
+    /// * `f` - Closure that receives a mutable reference to the data
+    pub fn update_for<F>(&self, timeout: Duration, f: F) -> Result<()>
+    where
+        F: FnOnce(&mut T),
+    {
+        self.write_for(timeout, f)
+    }
+
+    /// Publishes a new version; called with the write lock still held
+    ///
+    /// The returned inline callbacks must be run after the lock is released.
+    pub(crate) fn commit(&self, data: &T) -> PendingCallbacks<T> {
+        let version = self.notifier.notify();
+        self.probe.notified();
+        self.seen.fetch_max(version, Ordering::AcqRel);
+        self.subscribers.deliver(version, data);
+        self.callbacks.collect(version - 1, data)
+    }
+
+    /// Gets the current version of the data
+    ///
+    /// The version starts at `0` and is incremented by every `set()`,
+    /// `update()` and `write()`. It never goes backwards, so it can be used
+    /// to tell whether the data changed between two points in time.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::ThreadShare;
+    ///
+    /// let data = ThreadShare::new(0);
+    /// assert_eq!(data.version(), 0);
+    ///
+    /// data.set(1);
+    /// data.update(|x| *x += 1);
+    /// assert_eq!(data.version(), 2);
+    /// ```
+    pub fn version(&self) -> u64 {
+        self.notifier.version()
+    }
+
+    /// Shared notifier, followed by shares derived from this one
+    pub(crate) fn notifier(&self) -> &Arc<Notifier> {
+        &self.notifier
+    }
+
+    /// Shared callback registry, also used by projections
+    pub(crate) fn callbacks(&self) -> &Arc<Callbacks<T>> {
+        &self.callbacks
+    }
+
+    /// Underlying lock, for operations spanning several shares
+    pub(crate) fn data(&self) -> &Arc<RwLock<T>> {
+        &self.data
+    }
+
+    /// Lock statistics shared by all handles
+    pub(crate) fn probe(&self) -> &Probe {
+        &self.probe
+    }
+
+    /// Acquires the read lock, recording it in the statistics
+    pub(crate) fn read_lock(&self) -> Tracked<'_, RwLockReadGuard<'_, T>> {
+        self.probe.acquire(Access::Read, || self.data.read())
+    }
+
+    /// Acquires the read lock even if a writer is waiting, recording it in the statistics
+    pub(crate) fn read_recursive_lock(&self) -> Tracked<'_, RwLockReadGuard<'_, T>> {
+        self.probe
+            .acquire(Access::ReadRecursive, || self.data.read_recursive())
+    }
+
+    /// Acquires the write lock, recording it in the statistics
+    pub(crate) fn write_lock(&self) -> Tracked<'_, RwLockWriteGuard<'_, T>> {
+        self.probe.acquire(Access::Write, || self.data.write())
+    }
+
+    /// Gets a snapshot of the lock statistics
+    ///
+    /// Requires the `metrics` feature. Statistics are shared by all clones.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::ThreadShare;
+    ///
+    /// #[cfg(feature = "metrics")]
+    /// {
+    ///     let data = ThreadShare::new(0);
+    ///     data.set(1);
+    ///     data.read(|x| assert_eq!(*x, 1));
+    ///
+    ///     let stats = data.stats();
+    ///     assert_eq!(stats.reads.count, 1);
+    ///     assert_eq!(stats.writes.count, 1);
+    ///     assert_eq!(stats.notifications, 1);
+    /// }
+    /// ```
+    #[cfg(feature = "metrics")]
+    pub fn stats(&self) -> LockStats {
+        self.probe.stats()
+    }
+
+    /// Waits for data changes with timeout
+    ///
+    /// This method waits until the data changes past the last version observed
+    /// through this handle. Changes made through this handle, or already seen
+    /// by a previous wait, do not count; a change made by another handle before
+    /// this call returns immediately instead of being missed.
+    ///
+    /// ## Arguments
+    ///
+    /// * `timeout` - Maximum time to wait for changes
+    ///
+    /// ## Returns
+    ///
+    /// `true` if the timeout was reached, `false` if a change occurred.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::ThreadShare;
+    /// use std::time::Duration;
+    ///
+    /// let data = ThreadShare::new(0);
+    /// let clone = data.clone();
+    ///
+    /// // Spawn thread that will change data after 200ms
+    /// std::thread::spawn(move || {
+    ///     std::thread::sleep(Duration::from_millis(200));
+    ///     clone.set(100);
+    /// });
+    ///
+    /// // Wait for change with 100ms timeout
+    /// let timed_out = data.wait_for_change(Duration::from_millis(100));
+    /// assert!(timed_out); // Should timeout
+    ///
+    /// // Wait for change with 300ms timeout
+    /// let timed_out = data.wait_for_change(Duration::from_millis(300));
+    /// assert!(!timed_out); // Should not timeout
+    /// ```
+    pub fn wait_for_change(&self, timeout: Duration) -> bool {
+        let since = self.seen.load(Ordering::Acquire);
+        match self.notifier.wait_since(since, Some(Instant::now() + timeout)) {
+            Some(version) => {
+                self.seen.fetch_max(version, Ordering::AcqRel);
+                false
+            }
+            None => true,
+        }
+    }
+
+    /// Waits for data changes infinitely
+    ///
+    /// This method waits indefinitely until the data changes past the last
+    /// version observed through this handle (see `wait_for_change()`).
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::ThreadShare;
+    /// use std::thread;
+    /// use std::time::Duration;
+    ///
+    /// let data = ThreadShare::new(0);
+    /// let clone = data.clone();
+    ///
+    /// thread::spawn(move || {
+    ///     thread::sleep(Duration::from_millis(100));
+    ///     clone.set(100);
+    /// });
+    ///
+    /// // Wait indefinitely for change
+    /// data.wait_for_change_forever();
+    /// assert_eq!(data.get(), 100);
+    /// ```
+    pub fn wait_for_change_forever(&self) {
+        let since = self.seen.load(Ordering::Acquire);
+        if let Some(version) = self.notifier.wait_since(since, None) {
+            self.seen.fetch_max(version, Ordering::AcqRel);
+        }
+    }
+
+    /// Waits until the version moves past `version`, with timeout
+    ///
+    /// Unlike `wait_for_change()`, the caller decides which version it has
+    /// already seen, typically a value returned by `version()`. If the share
+    /// has already moved on, the call returns immediately.
+    ///
+    /// ## Arguments
+    ///
+    /// * `version` - The last version the caller has observed
+    /// * `timeout` - Maximum time to wait for changes
+    ///
+    /// ## Returns
+    ///
+    /// `Some(new_version)` once the data changed, `None` if the timeout was reached.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::ThreadShare;
+    /// use std::thread;
+    /// use std::time::Duration;
+    ///
+    /// let data = ThreadShare::new(0);
+    /// let clone = data.clone();
+    /// let seen = data.version();
+    ///
+    /// thread::spawn(move || {
+    ///     clone.set(100);
+    /// });
+    ///
+    /// let version = data.wait_for_change_since(seen, Duration::from_secs(1));
+    /// assert!(version.unwrap() > seen);
+    /// assert_eq!(data.get(), 100);
+    ///
+    /// // Nothing new after that
+    /// let version = version.unwrap();
+    /// assert_eq!(data.wait_for_change_since(version, Duration::from_millis(10)), None);
+    /// ```
+    pub fn wait_for_change_since(&self, version: u64, timeout: Duration) -> Option<u64> {
+        self.notifier
+            .wait_since(version, Some(Instant::now() + timeout))
+    }
+
+    /// Waits indefinitely until the version moves past `version`
+    ///
+    /// ## Arguments
+    ///
+    /// * `version` - The last version the caller has observed
+    ///
+    /// ## Returns
+    ///
+    /// The new version.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::ThreadShare;
+    /// use std::thread;
+    ///
+    /// let data = ThreadShare::new(0);
+    /// let clone = data.clone();
+    /// let seen = data.version();
+    ///
+    /// thread::spawn(move || {
+    ///     clone.set(100);
+    /// });
+    ///
+    /// let version = data.wait_for_change_since_forever(seen);
+    /// assert!(version > seen);
+    /// ```
+    pub fn wait_for_change_since_forever(&self, version: u64) -> u64 {
+        self.notifier.wait_since(version, None).unwrap_or(version)
+    }
+
+    /// Waits until the data satisfies a predicate, with timeout
+    ///
+    /// The predicate is checked under the read lock right away and again after
+    /// every change, so no update can slip by between two checks. No CPU is
+    /// spent while nothing changes.
+    ///
+    /// ## Arguments
+    ///
+    /// * `predicate` - Condition the data must satisfy
+    /// * `timeout` - Maximum time to wait for the condition
+    ///
+    /// ## Requirements
+    ///
+    /// The type `T` must implement `Clone` trait.
+    ///
+    /// ## Returns
+    ///
+    /// A copy of the data that satisfied the predicate, or `Err(Error::Timeout)`
+    /// if the timeout was reached first.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::ThreadShare;
+    /// use std::thread;
+    /// use std::time::Duration;
+    ///
+    /// let counter = ThreadShare::new(0);
+    /// let clone = counter.clone();
+    ///
+    /// thread::spawn(move || {
+    ///     for _ in 0..10 {
+    ///         clone.update(|x| *x += 1);
+    ///     }
+    /// });
+    ///
+    /// let value = counter
+    ///     .wait_until(|x| *x >= 10, Duration::from_secs(1))
+    ///     .expect("Counter never reached 10");
+    /// assert_eq!(value, 10);
+    ///
+    /// // Never satisfied: times out
+    /// assert!(counter.wait_until(|x| *x < 0, Duration::from_millis(10)).is_err());
+    /// ```
+    pub fn wait_until<F>(&self, mut predicate: F, timeout: Duration) -> Result<T>
+    where
+        F: FnMut(&T) -> bool,
+        T: Clone,
+    {
+        let deadline = Instant::now() + timeout;
+        self.wait_until_deadline(&mut predicate, Some(deadline))
+            .ok_or(Error::Timeout { timeout })
+    }
+
+    /// Waits indefinitely until the data satisfies a predicate
+    ///
+    /// See `wait_until()` for details.
+    ///
+    /// ## Arguments
+    ///
+    /// * `predicate` - Condition the data must satisfy
+    ///
+    /// ## Returns
+    ///
+    /// A copy of the data that satisfied the predicate.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::ThreadShare;
+    /// use std::thread;
+    ///
+    /// let status = ThreadShare::new(String::from("starting"));
+    /// let clone = status.clone();
+    ///
+    /// thread::spawn(move || {
+    ///     clone.set(String::from("ready"));
+    /// });
+    ///
+    /// let value = status.wait_until_forever(|s| s == "ready");
+    /// assert_eq!(value, "ready");
+    /// ```
+    pub fn wait_until_forever<F>(&self, mut predicate: F) -> T
+    where
+        F: FnMut(&T) -> bool,
+        T: Clone,
+    {
+        self.wait_until_deadline(&mut predicate, None)
+            .expect("waiting without a deadline cannot time out")
+    }
+
+    fn wait_until_deadline<F>(&self, predicate: &mut F, deadline: Option<Instant>) -> Option<T>
+    where
+        F: FnMut(&T) -> bool,
+        T: Clone,
+    {
+        loop {
+            // Read the version first: any commit after this point wakes the wait below
+            let version = self.notifier.version();
+            {
+                let data = self.read_lock();
+                if predicate(&data) {
+                    self.seen.fetch_max(version, Ordering::AcqRel);
+                    return Some(data.clone());
+                }
+            }
+            self.notifier.wait_since(version, deadline)?;
+        }
+    }
+
+    /// Returns a future that resolves once the data changes
+    ///
+    /// This is the async counterpart of `wait_for_change_forever()`: it waits
+    /// for a version newer than the last one observed through this handle, but
+    /// instead of blocking the thread it yields to the executor. It works with
+    /// any async runtime (`tokio`, `async-std`, ...).
+    ///
+    /// The future is cancellation-safe: dropping it before completion does not
+    /// mark anything as observed.
+    ///
+    /// ## Returns
+    ///
+    /// A `Changed` future resolving to the new version.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::ThreadShare;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let data = ThreadShare::new(0);
+    ///     let clone = data.clone();
+    ///
+    ///     tokio::spawn(async move {
+    ///         clone.set(100);
+    ///     });
+    ///
+    ///     data.changed().await;
+    ///     assert_eq!(data.get(), 100);
+    /// }
+    /// ```
+    pub fn changed(&self) -> Changed<'_> {
+        Changed::new(
+            &self.notifier,
+            Some(&self.seen),
+            self.seen.load(Ordering::Acquire),
+        )
+    }
+
+    /// Returns a future that resolves once the version moves past `version`
+    ///
+    /// This is the async counterpart of `wait_for_change_since_forever()`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `version` - The last version the caller has observed
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::ThreadShare;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let data = ThreadShare::new(0);
+    ///     let seen = data.version();
+    ///
+    ///     data.set(1);
+    ///
+    ///     // Already moved on: resolves immediately
+    ///     assert_eq!(data.changed_since(seen).await, seen + 1);
+    /// }
+    /// ```
+    pub fn changed_since(&self, version: u64) -> Changed<'_> {
+        Changed::new(&self.notifier, None, version)
+    }
+
+    /// Returns a future that resolves once the data changes or the timeout expires
+    ///
+    /// This is the async counterpart of `wait_for_change()`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `timeout` - Maximum time to wait for changes
+    ///
+    /// ## Returns
+    ///
+    /// A `ChangedTimeout` future resolving to `Some(new_version)` if the data
+    /// changed, or `None` if the timeout was reached.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::ThreadShare;
+    /// use std::time::Duration;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let data = ThreadShare::new(0);
+    ///
+    ///     let result = data.changed_timeout(Duration::from_millis(10)).await;
+    ///     assert_eq!(result, None);
+    /// }
+    /// ```
+    pub fn changed_timeout(&self, timeout: Duration) -> ChangedTimeout<'_> {
+        ChangedTimeout::new(self.changed(), timeout)
+    }
+
+    /// Subscribes to every change of the data
+    ///
+    /// Each `set()`, `update()` and `write()` made after this call delivers
+    /// a `Change` with the new version and a clone of the new data. The
+    /// subscription can be consumed by blocking (`recv()`, iteration), as a
+    /// `std::sync::mpsc::Receiver` (`into_receiver()`), or as a
+    /// `futures_core::Stream` with the `async` feature.
+    ///
+    /// ## Arguments
+    ///
+    /// * `policy` - `SubscribePolicy::Latest` to keep only the newest change,
+    ///   or `SubscribePolicy::Queue(n)` to buffer up to `n` changes
+    ///
+    /// ## Returns
+    ///
+    /// A `Subscription<T>` that ends once every handle of the share is dropped.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::{ThreadShare, SubscribePolicy};
+    /// use std::thread;
+    ///
+    /// let data = ThreadShare::new(0);
+    /// let mut changes = data.subscribe(SubscribePolicy::Queue(16));
+    ///
+    /// let writer = data.clone();
+    /// thread::spawn(move || {
+    ///     writer.set(1);
+    ///     writer.update(|x| *x += 1);
+    /// });
+    ///
+    /// assert_eq!(changes.recv().unwrap().value, 1);
+    /// assert_eq!(changes.recv().unwrap().value, 2);
+    /// ```
+    pub fn subscribe(&self, policy: SubscribePolicy) -> Subscription<T>
+    where
+        T: Clone + Send + 'static,
+    {
+        // Holding the read lock keeps the version and the registration in step
+        let _data = self.read_lock();
+        self.subscribers.subscribe(policy, self.notifier.version(), T::clone)
+    }
+
+    /// Subscribes to version changes without cloning the data
+    ///
+    /// Works like `subscribe()` but delivers only the new version, so `T`
+    /// does not have to implement `Clone`. Use `read()` to look at the data.
+    ///
+    /// ## Arguments
+    ///
+    /// * `policy` - How undelivered changes are buffered
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::{ThreadShare, SubscribePolicy};
+    /// use std::time::Duration;
+    ///
+    /// let data = ThreadShare::new(vec![1, 2, 3]);
+    /// let mut versions = data.subscribe_versions(SubscribePolicy::Latest);
+    ///
+    /// data.update(|v| v.push(4));
+    ///
+    /// let change = versions.recv_timeout(Duration::from_secs(1)).unwrap();
+    /// assert_eq!(change.version, data.version());
+    /// ```
+    pub fn subscribe_versions(&self, policy: SubscribePolicy) -> VersionSubscription
+    where
+        T: 'static,
+    {
+        let _data = self.read_lock();
+        self.subscribers.subscribe(policy, self.notifier.version(), |_: &T| ())
+    }
+
+    /// Registers a callback invoked after every committed change
+    ///
+    /// The callback receives the version the data had before the change and
+    /// a snapshot of the new data. It runs on the writing thread after the
+    /// write lock has been released, so it may read or modify the share
+    /// without deadlocking.
+    ///
+    /// ## Arguments
+    ///
+    /// * `callback` - Closure receiving `(old_version, &new_data)`
+    ///
+    /// ## Returns
+    ///
+    /// A `CallbackGuard` that unregisters the callback when dropped.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::ThreadShare;
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// let data = ThreadShare::new(0);
+    /// let log = Arc::new(Mutex::new(Vec::new()));
+    ///
+    /// let entries = log.clone();
+    /// let _guard = data.on_change(move |old_version, value| {
+    ///     entries.lock().unwrap().push((old_version, *value));
+    /// });
+    ///
+    /// data.set(10);
+    /// data.update(|x| *x += 5);
+    /// assert_eq!(*log.lock().unwrap(), vec![(0, 10), (1, 15)]);
+    /// ```
+    pub fn on_change<F>(&self, callback: F) -> CallbackGuard
+    where
+        F: Fn(u64, &T) + Send + Sync + 'static,
+        T: Clone + Send + 'static,
+    {
+        self.on_change_with(CallbackMode::Inline, callback)
+    }
+
+    /// Registers an on-change callback with an explicit execution mode
+    ///
+    /// With `CallbackMode::Thread` the callback runs on its own notifier
+    /// thread and sees changes strictly in version order; writers only hand
+    /// over a snapshot and never wait for the callback.
+    ///
+    /// ## Arguments
+    ///
+    /// * `mode` - Where the callback is executed
+    /// * `callback` - Closure receiving `(old_version, &new_data)`
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::{ThreadShare, CallbackMode};
+    /// use std::sync::mpsc;
+    /// use std::time::Duration;
+    ///
+    /// let data = ThreadShare::new(String::from("a"));
+    /// let (sender, receiver) = mpsc::channel();
+    ///
+    /// let _guard = data.on_change_with(CallbackMode::Thread, move |_, value: &String| {
+    ///     sender.send(value.clone()).unwrap();
+    /// });
+    ///
+    /// data.update(|s| s.push('b'));
+    /// assert_eq!(receiver.recv_timeout(Duration::from_secs(1)).unwrap(), "ab");
+    /// ```
+    pub fn on_change_with<F>(&self, mode: CallbackMode, callback: F) -> CallbackGuard
+    where
+        F: Fn(u64, &T) + Send + Sync + 'static,
+        T: Clone + Send + 'static,
+    {
+        self.callbacks.register(mode, T::clone, callback)
+    }
+
+    /// Gets Arc on data for transfer to thread without cloning
+    ///
+    /// This method converts the `ThreadShare<T>` into an `Arc<RwLock<T>>`,
+    /// which can be moved into threads without cloning the `ThreadShare` itself.
+    ///
+    /// ## Returns
+    ///
+    /// An `Arc<RwLock<T>>` containing the shared data.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::ThreadShare;
+    /// use std::thread;
+    ///
+    /// let data = ThreadShare::new(0);
+    /// let arc_data = data.into_arc();
+    ///
+    /// thread::spawn(move || {
+    ///     let mut guard = arc_data.write();
+    ///     *guard += 100;
+    /// });
+    /// ```
+    pub fn into_arc(self) -> Arc<RwLock<T>> {
+        self.data
+    }
+
+    /// Gets Arc<RwLock<T>> for version with locks
+    ///
+    /// This method returns an `Arc<RwLock<T>>` that shares the same data
+    /// as this `ThreadShare<T>`. This is useful when you need to work
+    /// directly with the underlying `Arc<RwLock<T>>` structure.
+    ///
+    /// ## Returns
+    ///
+    /// An `Arc<RwLock<T>>` sharing the same data.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::ThreadShare;
+    ///
+    /// let data = ThreadShare::new(vec![1, 2, 3]);
+    /// let arc_data = data.as_arc_locked();
+    ///
+    /// // Use the Arc<RwLock<T>> directly
+    /// let mut guard = arc_data.write();
+    /// guard.push(4);
+    /// drop(guard);
+    ///
+    /// // Changes are visible in the original ThreadShare
+    /// assert_eq!(data.get(), vec![1, 2, 3, 4]);
+    /// ```
+    pub fn as_arc_locked(&self) -> Arc<RwLock<T>> {
+        Arc::clone(&self.data)
+    }
+
+    /// Creates a weak handle that does not keep the data alive
+    ///
+    /// Use it for registries and background workers that should stop once
+    /// every owning handle is gone.
+    ///
+    /// ## Returns
+    ///
+    /// A `WeakThreadShare<T>` that can be upgraded back while the data is
+    /// alive.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::ThreadShare;
+    ///
+    /// let data = ThreadShare::new(1);
+    /// let weak = data.downgrade();
+    ///
+    /// assert_eq!(weak.upgrade().map(|d| d.get()), Some(1));
+    /// drop(data);
+    /// assert!(weak.upgrade().is_none());
+    /// ```
+    pub fn downgrade(&self) -> WeakThreadShare<T> {
+        WeakThreadShare {
+            data: Arc::downgrade(&self.data),
+            notifier: Arc::clone(&self.notifier),
+            registries: Arc::clone(&self.registries),
+            probe: self.probe.clone(),
+        }
+    }
+
+    /// Gets the number of handles keeping the data alive
+    ///
+    /// Counts every `ThreadShare<T>` clone, plus anything else holding the
+    /// data strongly, such as derived shares and `as_arc_locked()` results.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::ThreadShare;
+    ///
+    /// let data = ThreadShare::new(0);
+    /// let clone = data.clone();
+    /// assert_eq!(data.strong_count(), 2);
+    ///
+    /// drop(clone);
+    /// assert_eq!(data.strong_count(), 1);
+    /// ```
+    pub fn strong_count(&self) -> usize {
+        Arc::strong_count(&self.data)
+    }
+
+    /// Gets the number of weak handles to the data
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::ThreadShare;
+    ///
+    /// let data = ThreadShare::new(0);
+    /// let weak = data.downgrade();
+    /// assert_eq!(data.weak_count(), 1);
+    /// ```
+    pub fn weak_count(&self) -> usize {
+        Arc::weak_count(&self.data)
+    }
+}
+
+impl<T> Clone for ThreadShare<T> {
+    /// Creates a clone for use in another thread
+    ///
+    /// This method creates a new `ThreadShare<T>` instance that shares
+    /// the same underlying data. Each clone can be safely moved to
+    /// different threads. The clone starts out having observed the same
+    /// version as the handle it was cloned from.
+    ///
+    /// ## Returns
+    ///
+    /// A new `ThreadShare<T>` instance sharing the same data.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::ThreadShare;
+    /// use std::thread;
+    /// use std::time::Duration;
+    ///
+    /// let data = ThreadShare::new(0);
+    /// let clone1 = data.clone();
+    /// let clone2 = data.clone();
+    ///
+    /// // Each clone can be used in different threads
+    /// thread::spawn(move || {
+    ///     clone1.set(100);
+    /// });
+    ///
+    /// thread::spawn(move || {
+    ///     clone2.set(200);
+    /// });
+    ///
+    /// // Main thread waits for changes
+    /// data.wait_for_change_forever();
+    /// ```
+    fn clone(&self) -> Self {
+        Self {
+            data: Arc::clone(&self.data),
+            notifier: Arc::clone(&self.notifier),
+            subscribers: Arc::clone(&self.subscribers),
+            callbacks: Arc::clone(&self.callbacks),
+            registries: Arc::clone(&self.registries),
+            probe: self.probe.clone(),
+            seen: AtomicU64::new(self.seen.load(Ordering::Acquire)),
+        }
+    }
+}
+
+/// Weak handle to the data of a `ThreadShare<T>`
+///
+/// Created with `ThreadShare::downgrade()`. It does not keep the data alive:
+/// once nothing holds the data strongly anymore, `upgrade()` returns `None`.
+/// Subscriptions to the share end when the last `ThreadShare<T>` is dropped.
+///
+/// The weak handle is `Send` and `Sync` only if `ThreadShare<T>`'s data may
+/// be shared between threads:
+///
+/// ```compile_fail
+/// use thread_share::ThreadShare;
+/// use std::rc::Rc;
+///
+/// fn assert_send<S: Send>(_: S) {}
+///
+/// let data = ThreadShare::new(Rc::new(0));
+/// assert_send(data.downgrade());
+/// ```
+///
+/// ## Example
+///
+/// ```rust
+/// use thread_share::ThreadShare;
+/// use std::thread;
+/// use std::time::Duration;
+///
+/// let data = ThreadShare::new(0);
+/// let weak = data.downgrade();
+///
+/// // The worker exits on its own once the owner drops the data
+/// let worker = thread::spawn(move || {
+///     while let Some(data) = weak.upgrade() {
+///         data.wait_for_change(Duration::from_millis(10));
+///     }
+/// });
+///
+/// data.set(1);
+/// drop(data);
+/// worker.join().unwrap();
+/// ```
+pub struct WeakThreadShare<T> {
+    data: Weak<RwLock<T>>,
+    notifier: Arc<Notifier>,
+    registries: Arc<parking_lot::Mutex<Registries<T>>>,
+    probe: Probe,
+}
+
+impl<T> WeakThreadShare<T> {
+    /// Gets an owning handle if the data is still alive
+    ///
+    /// The returned handle starts out having observed the current version, so
+    /// its `wait_for_change()` waits for the next change.
+    ///
+    /// ## Returns
+    ///
+    /// `Some(ThreadShare<T>)` while the data is alive, that is while
+    /// `strong_count()` is not `0`, `None` afterwards. If only non-`ThreadShare`
+    /// holders such as `as_arc_locked()` results kept the data alive, the
+    /// returned handle starts with no subscriptions or callbacks.
+    pub fn upgrade(&self) -> Option<ThreadShare<T>> {
+        let data = self.data.upgrade()?;
+        let mut registries = self.registries.lock();
+        let subscribers = registries.subscribers.upgrade().unwrap_or_else(|| {
+            let subscribers = Arc::new(Subscribers::new());
+            registries.subscribers = Arc::downgrade(&subscribers);
+            subscribers
+        });
+        let callbacks = registries.callbacks.upgrade().unwrap_or_else(|| {
+            let callbacks = Arc::new(Callbacks::new());
+            registries.callbacks = Arc::downgrade(&callbacks);
+            callbacks
+        });
+        drop(registries);
+
+        Some(ThreadShare {
+            data,
+            notifier: Arc::clone(&self.notifier),
+            subscribers,
+            callbacks,
+            registries: Arc::clone(&self.registries),
+            probe: self.probe.clone(),
+            seen: AtomicU64::new(self.notifier.version()),
+        })
+    }
+
+    /// Gets the number of handles keeping the data alive
+    ///
+    /// See `ThreadShare::strong_count()`.
+    pub fn strong_count(&self) -> usize {
+        self.data.strong_count()
+    }
+
+    /// Gets the number of weak handles to the data
+    pub fn weak_count(&self) -> usize {
+        self.data.weak_count()
+    }
+}
+
+impl<T> Clone for WeakThreadShare<T> {
+    fn clone(&self) -> Self {
+        Self {
+            data: Weak::clone(&self.data),
+            notifier: Arc::clone(&self.notifier),
+            registries: Arc::clone(&self.registries),
+            probe: self.probe.clone(),
+        }
+    }
+}
+
+/// Simplified version for simple types
+pub struct SimpleShare<T> {
+    data: Arc<Mutex<T>>,
+    probe: Probe,
+}
+
+// Automatically implement Send and Sync for SimpleShare
+unsafe impl<T> Send for SimpleShare<T> {}
+unsafe impl<T> Sync for SimpleShare<T> {}
+
+impl<T> SimpleShare<T> {
+    /// Creates a new SimpleShare
+    ///
+    /// This method creates a new `SimpleShare<T>` instance with the provided data.
+    /// SimpleShare is a simplified version of ThreadShare without change detection.
+    ///
+    /// ## Arguments
+    ///
+    /// * `data` - The initial data to share between threads
+    ///
+    /// ## Returns
+    ///
+    /// A new `SimpleShare<T>` instance containing the data.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::SimpleShare;
+    ///
+    /// let counter = SimpleShare::new(0);
     /// let message = SimpleShare::new(String::from("Hello"));
     /// let data = SimpleShare::new(vec![1, 2, 3]);
     /// ```
     pub fn new(data: T) -> Self {
+        let data = Arc::new(Mutex::new(data));
         Self {
-            data: Arc::new(Mutex::new(data)),
+            probe: Probe::new(&data),
+            data,
         }
     }
 
+    /// Names the share for diagnostics
+    ///
+    /// The name appears in the reentrancy panics and deadlock reports of the
+    /// `debug-locks` feature. Name the share right after creating it: clones
+    /// made before the call keep the old name.
+    ///
+    /// ## Arguments
+    ///
+    /// * `name` - Name shown in diagnostics
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::SimpleShare;
+    ///
+    /// let config = SimpleShare::new(0).with_name("config");
+    /// assert_eq!(config.name(), Some("config"));
+    /// ```
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.probe.set_name(name);
+        self
+    }
+
+    /// Gets the name given with `with_name()`
+    pub fn name(&self) -> Option<&str> {
+        self.probe.name()
+    }
+
+    /// Sets how long the lock may be held before a warning is reported
+    ///
+    /// Requires the `lock-warnings` feature. Overrides the global threshold
+    /// of `lock_warnings::set_threshold()` for this share; use
+    /// `Duration::MAX` to never warn. Like `with_name()`, call it right after
+    /// creating the share.
+    ///
+    /// ## Arguments
+    ///
+    /// * `threshold` - Maximum hold time before a warning
+    #[cfg(feature = "lock-warnings")]
+    pub fn with_hold_threshold(mut self, threshold: std::time::Duration) -> Self {
+        self.probe.set_hold_threshold(threshold);
+        self
+    }
+
     /// Gets data
     ///
     /// This method retrieves a copy of the current data. The operation is safe
@@ -697,7 +2034,9 @@ impl<T> SimpleShare<T> {
     where
         T: Clone,
     {
-        self.data.lock().unwrap().clone()
+        self.probe
+            .acquire(Access::Read, || self.data.lock().unwrap())
+            .clone()
     }
 
     /// Sets data
@@ -718,7 +2057,9 @@ impl<T> SimpleShare<T> {
     /// assert_eq!(counter.get(), 100);
     /// ```
     pub fn set(&self, new_data: T) {
-        let mut data = self.data.lock().unwrap();
+        let mut data = self
+            .probe
+            .acquire(Access::Write, || self.data.lock().unwrap());
         *data = new_data;
     }
 
@@ -747,45 +2088,12 @@ impl<T> SimpleShare<T> {
     where
         F: FnOnce(&mut T),
     {
-        let mut data = self.data.lock().unwrap();
+        let mut data = self
+            .probe
+            .acquire(Access::Write, || self.data.lock().unwrap());
         f(&mut data);
     }
 
-    /// Clones for use in another thread
-    ///
-    /// This method creates a new `SimpleShare<T>` instance that shares
-    /// the same underlying data. Each clone can be safely moved to
-    /// different threads.
-    ///
-    /// ## Returns
-    ///
-    /// A new `SimpleShare<T>` instance sharing the same data.
-    ///
-    /// ## Example
-    ///
-    /// ```rust
-    /// use thread_share::SimpleShare;
-    /// use std::thread;
-    ///
-    /// let data = SimpleShare::new(0);
-    /// let clone1 = data.clone();
-    /// let clone2 = data.clone();
-    ///
-    /// // Each clone can be used in different threads
-    /// thread::spawn(move || {
-    ///     clone1.set(100);
-    /// });
-    ///
-    /// thread::spawn(move || {
-    ///     clone2.set(200);
-    /// });
-    /// ```
-    pub fn clone(&self) -> Self {
-        Self {
-            data: Arc::clone(&self.data),
-        }
-    }
-
     /// Gets Arc on data for transfer to thread without cloning
     ///
     /// This method consumes the `SimpleShare<T>` and returns the underlying
@@ -842,10 +2150,129 @@ impl<T> SimpleShare<T> {
     pub fn as_arc(&self) -> Arc<Mutex<T>> {
         Arc::clone(&self.data)
     }
+
+    /// Creates a weak handle that does not keep the data alive
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::SimpleShare;
+    ///
+    /// let data = SimpleShare::new(1);
+    /// let weak = data.downgrade();
+    ///
+    /// assert_eq!(weak.upgrade().map(|d| d.get()), Some(1));
+    /// drop(data);
+    /// assert!(weak.upgrade().is_none());
+    /// ```
+    pub fn downgrade(&self) -> WeakSimpleShare<T> {
+        WeakSimpleShare {
+            data: Arc::downgrade(&self.data),
+            probe: self.probe.clone(),
+        }
+    }
+
+    /// Gets the number of handles keeping the data alive
+    pub fn strong_count(&self) -> usize {
+        Arc::strong_count(&self.data)
+    }
+
+    /// Gets the number of weak handles to the data
+    pub fn weak_count(&self) -> usize {
+        Arc::weak_count(&self.data)
+    }
+
+    /// Gets a snapshot of the lock statistics
+    ///
+    /// Requires the `metrics` feature. `get()` counts as a read and
+    /// `set()`/`update()` as writes, although both take the same mutex.
+    #[cfg(feature = "metrics")]
+    pub fn stats(&self) -> LockStats {
+        self.probe.stats()
+    }
 }
 
 impl<T> Clone for SimpleShare<T> {
+    /// Clones for use in another thread
+    ///
+    /// This method creates a new `SimpleShare<T>` instance that shares
+    /// the same underlying data. Each clone can be safely moved to
+    /// different threads.
+    ///
+    /// ## Returns
+    ///
+    /// A new `SimpleShare<T>` instance sharing the same data.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::SimpleShare;
+    /// use std::thread;
+    ///
+    /// let data = SimpleShare::new(0);
+    /// let clone1 = data.clone();
+    /// let clone2 = data.clone();
+    ///
+    /// // Each clone can be used in different threads
+    /// thread::spawn(move || {
+    ///     clone1.set(100);
+    /// });
+    ///
+    /// thread::spawn(move || {
+    ///     clone2.set(200);
+    /// });
+    /// ```
+    fn clone(&self) -> Self {
+        Self {
+            data: Arc::clone(&self.data),
+            probe: self.probe.clone(),
+        }
+    }
+}
+
+/// Weak handle to the data of a `SimpleShare<T>`
+///
+/// Created with `SimpleShare::downgrade()`; see `WeakThreadShare<T>`.
+///
+/// ```compile_fail
+/// use thread_share::SimpleShare;
+/// use std::rc::Rc;
+///
+/// fn assert_send<S: Send>(_: S) {}
+///
+/// let data = SimpleShare::new(Rc::new(0));
+/// assert_send(data.downgrade());
+/// ```
+pub struct WeakSimpleShare<T> {
+    data: Weak<Mutex<T>>,
+    probe: Probe,
+}
+
+impl<T> WeakSimpleShare<T> {
+    /// Gets an owning handle if the data is still alive
+    pub fn upgrade(&self) -> Option<SimpleShare<T>> {
+        Some(SimpleShare {
+            data: self.data.upgrade()?,
+            probe: self.probe.clone(),
+        })
+    }
+
+    /// Gets the number of handles keeping the data alive
+    pub fn strong_count(&self) -> usize {
+        self.data.strong_count()
+    }
+
+    /// Gets the number of weak handles to the data
+    pub fn weak_count(&self) -> usize {
+        self.data.weak_count()
+    }
+}
+
+impl<T> Clone for WeakSimpleShare<T> {
     fn clone(&self) -> Self {
-        self.clone()
+        Self {
+            data: Weak::clone(&self.data),
+            probe: self.probe.clone(),
+        }
     }
 }
@@ -0,0 +1,325 @@
+//! # Debug Locks Module - Reentrancy and Deadlock Detection
+//!
+//! This module is available with the `debug-locks` feature. It tracks which
+//! shares every thread currently holds and turns silent deadlocks into
+//! diagnostics.
+//!
+//! ## Overview
+//!
+//! - **Reentrancy**: acquiring a share's lock on a thread that already holds
+//!   it in a conflicting way panics right away with the share and thread
+//!   names, instead of hanging forever. This covers calling `update()` inside
+//!   `read()`, reading inside a write and upgrading while holding another
+//!   read lock. Nested reads are not reported, since they only hang if
+//!   another thread queues a writer in between.
+//! - **Deadlocks between threads**: the first share created starts a
+//!   background thread running `parking_lot`'s deadlock detector once per
+//!   second. When it finds a cycle, every thread blocked on a share is
+//!   reported together with the shares it holds and the one it waits for.
+//!
+//! Reports go to standard error unless a handler is installed with
+//! `set_deadlock_handler()`. Name shares with `with_name()` to make reports
+//! readable.
+//!
+//! Non-blocking and timed acquisitions (`try_read()`, `write_for()`, ...) are
+//! never reported as reentrant since they cannot deadlock. Raw guards returned
+//! by `ArcThreadShareLocked::get_ref()` and `get_mut()` are checked when
+//! acquired but not tracked while held.
+//!
+//! ## Example
+//!
+//! ```rust,should_panic
+//! use thread_share::ThreadShare;
+//!
+//! let config = ThreadShare::new(0).with_name("config");
+//!
+//! // Panics: "thread 'main' tried to take a write lock on share 'config'
+//! // while already holding a read lock on it"
+//! config.read(|_| config.update(|x| *x += 1));
+//! # #[cfg(not(feature = "debug-locks"))]
+//! # panic!("requires the debug-locks feature");
+//! ```
+
+use crate::lock_info::{share_label, thread_label};
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock, Once};
+use std::thread::{self, ThreadId};
+use std::time::Duration;
+
+pub use crate::lock_info::LockKind;
+
+/// A share's lock as held or requested by a thread
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockInfo {
+    /// Name given with `with_name()`, or the address of the lock
+    pub share: String,
+    /// How the lock is held or requested
+    pub kind: LockKind,
+}
+
+impl fmt::Display for LockInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} on share '{}'", self.kind, self.share)
+    }
+}
+
+/// A thread blocked on a share's lock
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockedThread {
+    /// Name of the thread, or its id if it has none
+    pub thread: String,
+    /// The lock the thread is waiting for
+    pub waiting_for: LockInfo,
+    /// Locks the thread currently holds
+    pub holding: Vec<LockInfo>,
+}
+
+/// A deadlock found by the background detector
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeadlockReport {
+    /// Number of deadlock cycles `parking_lot` found
+    pub cycles: usize,
+    /// All threads blocked on a share at the time of the check
+    pub threads: Vec<BlockedThread>,
+}
+
+impl fmt::Display for DeadlockReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "thread-share: {} deadlock cycle(s) detected",
+            self.cycles
+        )?;
+        for blocked in &self.threads {
+            write!(
+                f,
+                "  thread '{}' waits for a {}",
+                blocked.thread, blocked.waiting_for
+            )?;
+            if blocked.holding.is_empty() {
+                writeln!(f)?;
+            } else {
+                let holding: Vec<String> = blocked.holding.iter().map(|l| l.to_string()).collect();
+                writeln!(f, " while holding a {}", holding.join(", a "))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+type DeadlockHandler = Arc<dyn Fn(&DeadlockReport) + Send + Sync>;
+
+static HANDLER: Mutex<Option<DeadlockHandler>> = Mutex::new(None);
+
+/// Installs the function called with each deadlock report
+///
+/// Replaces the default handler, which prints the report to standard error.
+/// The handler runs on the detector thread.
+///
+/// ## Example
+///
+/// ```rust
+/// use thread_share::debug_locks;
+///
+/// debug_locks::set_deadlock_handler(|report| {
+///     eprintln!("{}", report);
+///     std::process::abort();
+/// });
+/// ```
+pub fn set_deadlock_handler<F>(handler: F)
+where
+    F: Fn(&DeadlockReport) + Send + Sync + 'static,
+{
+    *HANDLER.lock() = Some(Arc::new(handler));
+}
+
+/// Lock identity and name as seen by the registry
+#[derive(Clone)]
+struct Held {
+    token: u64,
+    lock: usize,
+    name: Option<Arc<str>>,
+    kind: LockKind,
+}
+
+impl Held {
+    fn info(&self) -> LockInfo {
+        LockInfo {
+            share: share_label(self.lock, self.name.as_ref()),
+            kind: self.kind,
+        }
+    }
+}
+
+#[derive(Default)]
+struct ThreadLocks {
+    name: String,
+    held: Vec<Held>,
+    waiting: Option<Held>,
+}
+
+static REGISTRY: LazyLock<Mutex<HashMap<ThreadId, ThreadLocks>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+static NEXT_TOKEN: AtomicU64 = AtomicU64::new(0);
+
+/// Checks that a blocking acquisition cannot deadlock on the current thread
+///
+/// Marks the thread as waiting for the lock until `acquired()` is called.
+pub(crate) fn before_blocking(lock: usize, name: Option<&Arc<str>>, kind: LockKind) {
+    let mut registry = REGISTRY.lock();
+    let locks = registry
+        .entry(thread::current().id())
+        .or_insert_with(|| ThreadLocks {
+            name: thread_label(),
+            ..ThreadLocks::default()
+        });
+
+    let conflict = locks.held.iter().find(|held| {
+        held.lock == lock
+            && match (held.kind, kind) {
+                (LockKind::Write, _) | (_, LockKind::Write) => true,
+                (LockKind::UpgradableRead, LockKind::UpgradableRead) => true,
+                // Shared next to shared access only blocks behind a writer
+                // queued by another thread
+                _ => false,
+            }
+    });
+    if let Some(held) = conflict {
+        let message = format!(
+            "thread '{}' tried to take a {} on share '{}' while already holding a {} on it",
+            locks.name,
+            kind,
+            share_label(lock, name),
+            held.kind
+        );
+        drop(registry);
+        panic!("{}", message);
+    }
+
+    locks.waiting = Some(Held {
+        token: 0,
+        lock,
+        name: name.cloned(),
+        kind,
+    });
+}
+
+/// Checks a recursive read, which only conflicts with a held write lock
+pub(crate) fn before_recursive_read(lock: usize, name: Option<&Arc<str>>) {
+    let registry = REGISTRY.lock();
+    let Some(locks) = registry.get(&thread::current().id()) else {
+        return;
+    };
+    let writing = locks
+        .held
+        .iter()
+        .any(|held| held.lock == lock && held.kind == LockKind::Write);
+    if writing {
+        let message = format!(
+            "thread '{}' tried to take a read lock on share '{}' while already holding a write lock on it",
+            locks.name,
+            share_label(lock, name)
+        );
+        drop(registry);
+        panic!("{}", message);
+    }
+}
+
+/// Records that the current thread now holds the lock
+///
+/// Returns the token to pass to `released()`.
+pub(crate) fn acquired(lock: usize, name: Option<&Arc<str>>, kind: LockKind) -> u64 {
+    let token = NEXT_TOKEN.fetch_add(1, Ordering::Relaxed);
+    let mut registry = REGISTRY.lock();
+    let locks = registry
+        .entry(thread::current().id())
+        .or_insert_with(|| ThreadLocks {
+            name: thread_label(),
+            ..ThreadLocks::default()
+        });
+    locks.waiting = None;
+    locks.held.push(Held {
+        token,
+        lock,
+        name: name.cloned(),
+        kind,
+    });
+    token
+}
+
+/// Clears the waiting mark of an acquisition whose guard is not tracked
+pub(crate) fn acquired_untracked() {
+    let mut registry = REGISTRY.lock();
+    let id = thread::current().id();
+    if let Some(locks) = registry.get_mut(&id) {
+        locks.waiting = None;
+        if locks.held.is_empty() {
+            registry.remove(&id);
+        }
+    }
+}
+
+/// Records that the lock acquired with `token` was released
+pub(crate) fn released(token: u64) {
+    let mut registry = REGISTRY.lock();
+    let id = thread::current().id();
+    if let Some(locks) = registry.get_mut(&id) {
+        locks.held.retain(|held| held.token != token);
+        if locks.held.is_empty() && locks.waiting.is_none() {
+            registry.remove(&id);
+        }
+    } else {
+        // Guard moved to and dropped on another thread
+        for locks in registry.values_mut() {
+            locks.held.retain(|held| held.token != token);
+        }
+        registry.retain(|_, locks| !locks.held.is_empty() || locks.waiting.is_some());
+    }
+}
+
+/// Starts the background deadlock detector once per process
+pub(crate) fn start_detector() {
+    static START: Once = Once::new();
+    START.call_once(|| {
+        let spawned = thread::Builder::new()
+            .name("thread-share-deadlock-detector".to_string())
+            .spawn(|| {
+                loop {
+                    thread::sleep(Duration::from_secs(1));
+                    let cycles = parking_lot::deadlock::check_deadlock();
+                    if !cycles.is_empty() {
+                        report(cycles.len());
+                    }
+                }
+            });
+        if let Err(e) = spawned {
+            eprintln!("thread-share: failed to start the deadlock detector: {}", e);
+        }
+    });
+}
+
+fn report(cycles: usize) {
+    let threads = REGISTRY
+        .lock()
+        .values()
+        .filter_map(|locks| {
+            let waiting = locks.waiting.as_ref()?;
+            Some(BlockedThread {
+                thread: locks.name.clone(),
+                waiting_for: waiting.info(),
+                holding: locks.held.iter().map(Held::info).collect(),
+            })
+        })
+        .collect();
+    let report = DeadlockReport { cycles, threads };
+
+    let handler = HANDLER.lock().clone();
+    match handler {
+        Some(handler) => handler(&report),
+        None => eprint!("{}", report),
+    }
+}
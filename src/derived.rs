@@ -0,0 +1,418 @@
+//! # Derived Module - Computed Shares
+//!
+//! This module provides `Derived<U>`, a read-only value computed from one or
+//! more `ThreadShare`s, created with `ThreadShare::map()`, `Derived::map()`,
+//! `combine()` or `combine_all()`.
+//!
+//! ## Overview
+//!
+//! A derived share is **lazy and memoized**: the computation runs only when
+//! the value is read and one of its sources has changed since the last
+//! computation. Reading an unchanged derived share just returns the cached
+//! value, which makes it cheap to expose summaries of large shared state.
+//!
+//! Derived shares take part in change notification. Their version advances
+//! whenever a source commits a change, so `wait_for_change()`, `changed()`
+//! and friends work the same way as on `ThreadShare<T>`.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use thread_share::{share, combine};
+//!
+//! let orders = share!(vec![120, 80, 45]);
+//! let discount = share!(10);
+//!
+//! let total = orders.map(|orders| orders.iter().sum::<i32>());
+//! let discounted = combine(&orders, &discount, |orders, discount| {
+//!     orders.iter().sum::<i32>() - discount
+//! });
+//!
+//! assert_eq!(total.get(), 245);
+//! assert_eq!(discounted.get(), 235);
+//!
+//! orders.update(|orders| orders.push(5));
+//! assert_eq!(total.get(), 250);
+//! assert_eq!(discounted.get(), 240);
+//! ```
+//!
+//! ## Consistency
+//!
+//! `combine()` and `combine_all()` hold the read locks of all sources while
+//! computing, so they always see values that existed at the same time. A source
+//! change that lands during a computation is picked up by the next read.
+
+use crate::changed::{Changed, ChangedTimeout};
+use crate::core::ThreadShare;
+use crate::notify::Notifier;
+use parking_lot::{RwLock, RwLockWriteGuard};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+type Compute<U> = Box<dyn Fn() -> U + Send + Sync>;
+
+struct DerivedInner<U> {
+    compute: Compute<U>,
+    notifier: Arc<Notifier>,
+    /// Last computed value, tagged with the version it was computed at
+    cache: RwLock<Option<(u64, U)>>,
+}
+
+/// Read-only value computed from one or more shares
+///
+/// `Derived<U>` recomputes its value lazily, only when a source changed
+/// since the last computation. Clones share the cached value.
+///
+/// ## Example
+///
+/// ```rust
+/// use thread_share::share;
+///
+/// let data = share!(vec![3, 1, 2]);
+/// let sorted = data.map(|v| {
+///     let mut v = v.clone();
+///     v.sort();
+///     v
+/// });
+///
+/// assert_eq!(sorted.get(), vec![1, 2, 3]);
+/// assert_eq!(sorted.read(|v| v.len()), 3);
+/// ```
+pub struct Derived<U> {
+    inner: Arc<DerivedInner<U>>,
+    /// Last version observed through this handle
+    seen: AtomicU64,
+}
+
+impl<U> Derived<U> {
+    fn new(compute: Compute<U>, sources: &[&Arc<Notifier>]) -> Self {
+        let notifier = Arc::new(Notifier::new());
+        for source in sources {
+            source.add_dependent(Arc::downgrade(&notifier));
+        }
+        Self {
+            inner: Arc::new(DerivedInner {
+                compute,
+                notifier,
+                cache: RwLock::new(None),
+            }),
+            seen: AtomicU64::new(0),
+        }
+    }
+
+    /// Reads the derived value, recomputing it first if a source changed
+    ///
+    /// ## Arguments
+    ///
+    /// * `f` - Closure that receives a reference to the derived value
+    ///
+    /// ## Returns
+    ///
+    /// The result of the closure execution.
+    pub fn read<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&U) -> R,
+    {
+        // Read the version first: a change after this point forces a recompute later
+        let version = self.inner.notifier.version();
+        {
+            let cache = self.inner.cache.read();
+            if let Some((computed_at, value)) = &*cache {
+                if *computed_at >= version {
+                    return f(value);
+                }
+            }
+        }
+
+        let mut cache = self.inner.cache.write();
+        let fresh = matches!(&*cache, Some((computed_at, _)) if *computed_at >= version);
+        if !fresh {
+            *cache = Some((version, (self.inner.compute)()));
+        }
+        let cache = RwLockWriteGuard::downgrade(cache);
+        match &*cache {
+            Some((_, value)) => f(value),
+            None => unreachable!("derived value was just computed"),
+        }
+    }
+
+    /// Gets a copy of the derived value, recomputing it if a source changed
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::share;
+    ///
+    /// let count = share!(2);
+    /// let doubled = count.map(|x| x * 2);
+    ///
+    /// assert_eq!(doubled.get(), 4);
+    /// count.set(5);
+    /// assert_eq!(doubled.get(), 10);
+    /// ```
+    pub fn get(&self) -> U
+    where
+        U: Clone,
+    {
+        self.read(U::clone)
+    }
+
+    /// Creates a share derived from this one
+    ///
+    /// The new share recomputes whenever this one would.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::share;
+    ///
+    /// let data = share!(vec![1, 2, 3]);
+    /// let sum = data.map(|v| v.iter().sum::<i32>());
+    /// let label = sum.map(|sum| format!("total: {}", sum));
+    ///
+    /// assert_eq!(label.get(), "total: 6");
+    /// ```
+    pub fn map<V, F>(&self, f: F) -> Derived<V>
+    where
+        F: Fn(&U) -> V + Send + Sync + 'static,
+        U: Send + Sync + 'static,
+    {
+        let source = self.clone();
+        Derived::new(Box::new(move || source.read(&f)), &[&self.inner.notifier])
+    }
+
+    /// Gets the current version of the derived share
+    ///
+    /// The version starts at `0` and is incremented every time any source
+    /// commits a change.
+    pub fn version(&self) -> u64 {
+        self.inner.notifier.version()
+    }
+
+    /// Waits for a source change with timeout
+    ///
+    /// Behaves like `ThreadShare::wait_for_change()`.
+    ///
+    /// ## Returns
+    ///
+    /// `true` if the timeout was reached, `false` if a source changed.
+    pub fn wait_for_change(&self, timeout: Duration) -> bool {
+        let since = self.seen.load(Ordering::Acquire);
+        match self
+            .inner
+            .notifier
+            .wait_since(since, Some(Instant::now() + timeout))
+        {
+            Some(version) => {
+                self.seen.fetch_max(version, Ordering::AcqRel);
+                false
+            }
+            None => true,
+        }
+    }
+
+    /// Waits for a source change indefinitely
+    ///
+    /// Behaves like `ThreadShare::wait_for_change_forever()`.
+    pub fn wait_for_change_forever(&self) {
+        let since = self.seen.load(Ordering::Acquire);
+        if let Some(version) = self.inner.notifier.wait_since(since, None) {
+            self.seen.fetch_max(version, Ordering::AcqRel);
+        }
+    }
+
+    /// Returns a future that resolves once a source changes
+    ///
+    /// Behaves like `ThreadShare::changed()`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::share;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let data = share!(1);
+    ///     let squared = data.map(|x| x * x);
+    ///
+    ///     let writer = data.clone();
+    ///     tokio::spawn(async move { writer.set(3) });
+    ///
+    ///     squared.changed().await;
+    ///     assert_eq!(squared.get(), 9);
+    /// }
+    /// ```
+    pub fn changed(&self) -> Changed<'_> {
+        Changed::new(
+            &self.inner.notifier,
+            Some(&self.seen),
+            self.seen.load(Ordering::Acquire),
+        )
+    }
+
+    /// Returns a future that resolves once a source changes or the timeout expires
+    ///
+    /// Behaves like `ThreadShare::changed_timeout()`.
+    pub fn changed_timeout(&self, timeout: Duration) -> ChangedTimeout<'_> {
+        ChangedTimeout::new(self.changed(), timeout)
+    }
+}
+
+impl<U> Clone for Derived<U> {
+    /// Creates another handle to the same derived share
+    ///
+    /// Clones share the cached value and start out having observed the same
+    /// version as the handle they were cloned from.
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+            seen: AtomicU64::new(self.seen.load(Ordering::Acquire)),
+        }
+    }
+}
+
+impl<T> ThreadShare<T> {
+    /// Creates a read-only share computed from this one
+    ///
+    /// The computation runs lazily on read, and only if the data changed
+    /// since the last computation.
+    ///
+    /// ## Arguments
+    ///
+    /// * `f` - Function computing the derived value from the data
+    ///
+    /// ## Returns
+    ///
+    /// A `Derived<U>` that follows this share's changes.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::ThreadShare;
+    ///
+    /// let data = ThreadShare::new(vec![1, 2, 3]);
+    /// let len = data.map(|v| v.len());
+    ///
+    /// assert_eq!(len.get(), 3);
+    /// data.update(|v| v.push(4));
+    /// assert_eq!(len.get(), 4);
+    /// ```
+    pub fn map<U, F>(&self, f: F) -> Derived<U>
+    where
+        F: Fn(&T) -> U + Send + Sync + 'static,
+        T: Send + Sync + 'static,
+    {
+        let source = self.clone();
+        Derived::new(Box::new(move || source.read(&f)), &[self.notifier()])
+    }
+}
+
+/// Creates a read-only share computed from two shares
+///
+/// The computation runs lazily on read, and only if either source changed
+/// since the last computation. For more than two sources of the same type,
+/// use `combine_all()`.
+///
+/// ## Arguments
+///
+/// * `a` - First source share
+/// * `b` - Second source share
+/// * `f` - Function computing the derived value from both sources
+///
+/// ## Example
+///
+/// ```rust
+/// use thread_share::{share, combine};
+///
+/// let width = share!(3);
+/// let height = share!(4);
+/// let area = combine(&width, &height, |w, h| w * h);
+///
+/// assert_eq!(area.get(), 12);
+/// height.set(10);
+/// assert_eq!(area.get(), 30);
+/// ```
+pub fn combine<A, B, U, F>(a: &ThreadShare<A>, b: &ThreadShare<B>, f: F) -> Derived<U>
+where
+    F: Fn(&A, &B) -> U + Send + Sync + 'static,
+    A: Send + Sync + 'static,
+    B: Send + Sync + 'static,
+{
+    let (source_a, source_b) = (a.clone(), b.clone());
+    Derived::new(
+        Box::new(move || {
+            // Lock in address order like writers spanning several shares do;
+            // the second lock is recursive in case both sources are the same share
+            if Arc::as_ptr(source_a.data()) as usize <= Arc::as_ptr(source_b.data()) as usize {
+                let a = source_a.read_lock();
+                let b = source_b.read_recursive_lock();
+                f(&a, &b)
+            } else {
+                let b = source_b.read_lock();
+                let a = source_a.read_recursive_lock();
+                f(&a, &b)
+            }
+        }),
+        &[a.notifier(), b.notifier()],
+    )
+}
+
+/// Creates a read-only share computed from any number of shares
+///
+/// The slice form of `combine()`, for sources of the same type. The
+/// computation runs lazily on read, and only if one of the sources changed
+/// since the last computation. All sources are read-locked together, so the
+/// function sees values that existed at the same time.
+///
+/// ## Arguments
+///
+/// * `sources` - Source shares; the same share may appear more than once
+/// * `f` - Function computing the derived value from the source values,
+///   given in the same order as `sources`
+///
+/// ## Example
+///
+/// ```rust
+/// use thread_share::{share, combine_all};
+///
+/// let north = share!(120);
+/// let south = share!(80);
+/// let west = share!(45);
+/// let total = combine_all(&[&north, &south, &west], |sales| {
+///     sales.iter().copied().sum::<i32>()
+/// });
+///
+/// assert_eq!(total.get(), 245);
+/// west.set(50);
+/// assert_eq!(total.get(), 250);
+/// ```
+pub fn combine_all<T, U, F>(sources: &[&ThreadShare<T>], f: F) -> Derived<U>
+where
+    F: Fn(&[&T]) -> U + Send + Sync + 'static,
+    T: Send + Sync + 'static,
+{
+    let shares: Vec<ThreadShare<T>> = sources.iter().map(|&share| share.clone()).collect();
+    let notifiers: Vec<&Arc<Notifier>> = sources.iter().map(|share| share.notifier()).collect();
+    Derived::new(
+        Box::new(move || {
+            // Lock in address order like `combine()`; every lock after the
+            // first is recursive in case a share appears more than once
+            let mut order: Vec<usize> = (0..shares.len()).collect();
+            order.sort_by_key(|&i| Arc::as_ptr(shares[i].data()) as usize);
+            let mut guards = Vec::with_capacity(shares.len());
+            for (n, &i) in order.iter().enumerate() {
+                let guard = if n == 0 {
+                    shares[i].read_lock()
+                } else {
+                    shares[i].read_recursive_lock()
+                };
+                guards.push((i, guard));
+            }
+            guards.sort_by_key(|&(i, _)| i);
+            let values: Vec<&T> = guards.iter().map(|(_, guard)| &**guard).collect();
+            f(&values)
+        }),
+        &notifiers,
+    )
+}
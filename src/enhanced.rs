@@ -206,7 +206,19 @@
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use std::thread::{self};
+use crate::callback::{CallbackGuard, CallbackMode};
+use crate::changed::{Changed, ChangedTimeout};
 use crate::core::ThreadShare;
+use crate::derived::Derived;
+use crate::error::{Error, Result};
+use crate::guard::{ReadGuard, UpgradableReadGuard, WriteGuard};
+use crate::notify::NotifyPolicy;
+use crate::projection::Projected;
+use crate::thread_pool::spawn_named;
+use crate::subscribe::{SubscribePolicy, Subscription, VersionSubscription};
+
+/// Boxed thread function accepted by `spawn_multiple_boxed()`
+pub type BoxedThreadFn<T> = Box<dyn FnOnce(ThreadShare<T>) + Send>;
 
 #[cfg(feature = "serialize")]
 use serde::{Serialize, Deserialize};
@@ -293,6 +305,41 @@ impl<T> EnhancedThreadShare<T> {
         }
     }
 
+    /// Names the shared data for diagnostics
+    ///
+    /// See `ThreadShare::with_name()`.
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.inner = self.inner.with_name(name);
+        self
+    }
+
+    /// Gets the name given with `with_name()`
+    pub fn name(&self) -> Option<&str> {
+        self.inner.name()
+    }
+
+    /// Sets how long the lock may be held before a warning is reported
+    ///
+    /// See `ThreadShare::with_hold_threshold()`.
+    #[cfg(feature = "lock-warnings")]
+    pub fn with_hold_threshold(mut self, threshold: std::time::Duration) -> Self {
+        self.inner = self.inner.with_hold_threshold(threshold);
+        self
+    }
+
+    /// Sets when waiting threads are woken after a change
+    ///
+    /// See `ThreadShare::with_notify_policy()`.
+    pub fn with_notify_policy(mut self, policy: NotifyPolicy) -> Self {
+        self.inner = self.inner.with_notify_policy(policy);
+        self
+    }
+
+    /// Gets the policy set with `with_notify_policy()`
+    pub fn notify_policy(&self) -> NotifyPolicy {
+        self.inner.notify_policy()
+    }
+
     /// Spawns a thread with access to this shared data
     ///
     /// This method creates a new thread with the given name and function.
@@ -314,7 +361,7 @@ impl<T> EnhancedThreadShare<T> {
     ///
     /// ## Returns
     ///
-    /// `Ok(())` on success, `Err(String)` if thread spawning fails.
+    /// `Ok(())` on success, `Err(Error::Spawn)` if the operating system fails to spawn the thread.
     ///
     /// ## Example
     ///
@@ -339,7 +386,7 @@ impl<T> EnhancedThreadShare<T> {
     ///     }
     /// }).expect("Failed to spawn monitor");
     /// ```
-    pub fn spawn<F>(&self, name: &str, f: F) -> Result<(), String>
+    pub fn spawn<F>(&self, name: &str, f: F) -> Result<()>
     where
         F: FnOnce(ThreadShare<T>) + Send + 'static,
         T: Send + Sync + 'static,
@@ -347,9 +394,9 @@ impl<T> EnhancedThreadShare<T> {
         let thread_name = name.to_string();
         let thread_data = self.inner.clone();
 
-        let handle = thread::spawn(move || {
+        let handle = spawn_named(name, move || {
             f(thread_data);
-        });
+        })?;
 
         self.threads.lock().unwrap().insert(thread_name, handle);
         Ok(())
@@ -370,9 +417,9 @@ impl<T> EnhancedThreadShare<T> {
     ///
     /// ## Returns
     ///
-    /// `Ok(())` on success, `Err(String)` if any thread spawning fails.
+    /// `Ok(())` on success, `Err(Error::Spawn)` if the operating system fails to spawn a thread.
     /// ```
-    pub fn spawn_multiple<F>(&self, thread_configs: Vec<(&str, F)>) -> Result<(), String>
+    pub fn spawn_multiple<F>(&self, thread_configs: Vec<(&str, F)>) -> Result<()>
     where
         F: FnOnce(ThreadShare<T>) + Send + Clone + 'static,
         T: Send + Sync + 'static,
@@ -394,7 +441,7 @@ impl<T> EnhancedThreadShare<T> {
     ///
     /// ## Returns
     ///
-    /// `Ok(())` on success, `Err(String)` if any thread spawning fails.
+    /// `Ok(())` on success, `Err(Error::Spawn)` if the operating system fails to spawn a thread.
     ///
     /// ## Example
     ///
@@ -412,16 +459,16 @@ impl<T> EnhancedThreadShare<T> {
     /// ```
     pub fn spawn_multiple_boxed(
         &self,
-        thread_configs: Vec<(&str, Box<dyn FnOnce(ThreadShare<T>) + Send>)>,
-    ) -> Result<(), String>
+        thread_configs: Vec<(&str, BoxedThreadFn<T>)>,
+    ) -> Result<()>
     where
         T: Send + Sync + 'static,
     {
         for (name, func) in thread_configs {
             let thread_data = self.inner.clone();
-            let handle = thread::spawn(move || {
+            let handle = spawn_named(name, move || {
                 func(thread_data);
-            });
+            })?;
             self.threads
                 .lock()
                 .unwrap()
@@ -437,7 +484,7 @@ impl<T> EnhancedThreadShare<T> {
     ///
     /// ## Returns
     ///
-    /// `Ok(())` when all threads complete successfully, `Err(String)` if any thread fails.
+    /// `Ok(())` when all threads complete successfully, `Err(Error::WorkerPanicked)` if any thread panics.
     ///
     /// ## Example
     ///
@@ -456,16 +503,13 @@ impl<T> EnhancedThreadShare<T> {
     /// // Now safe to access the final result
     /// assert_eq!(enhanced.get(), 100);
     /// ```
-    pub fn join_all(&self) -> Result<(), String> {
+    pub fn join_all(&self) -> Result<()> {
         let mut threads = self.threads.lock().unwrap();
         let thread_handles: Vec<_> = threads.drain().collect();
         drop(threads);
 
         for (name, handle) in thread_handles {
-            let result = handle.join();
-            if let Err(e) = result {
-                return Err(format!("Thread '{}' failed: {:?}", name, e));
-            }
+            handle.join().map_err(|payload| Error::panicked(name, payload))?;
         }
         Ok(())
     }
@@ -585,6 +629,43 @@ impl<T> EnhancedThreadShare<T> {
         self.inner.set(new_data);
     }
 
+    /// Sets new data only if the current data equals `expected`
+    ///
+    /// See `ThreadShare::compare_and_set()`.
+    pub fn compare_and_set(&self, expected: &T, new_data: T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.inner.compare_and_set(expected, new_data)
+    }
+
+    /// Sets new data unless it equals the current data
+    ///
+    /// See `ThreadShare::set_if_changed()`.
+    pub fn set_if_changed(&self, new_data: T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.inner.set_if_changed(new_data)
+    }
+
+    /// Sets new data and returns the previous data
+    ///
+    /// See `ThreadShare::replace()`.
+    pub fn replace(&self, new_data: T) -> T {
+        self.inner.replace(new_data)
+    }
+
+    /// Takes the data, leaving the default value in its place
+    ///
+    /// See `ThreadShare::take()`.
+    pub fn take(&self) -> T
+    where
+        T: Default,
+    {
+        self.inner.take()
+    }
+
     pub fn update<F>(&self, f: F)
     where
         F: FnOnce(&mut T),
@@ -592,6 +673,117 @@ impl<T> EnhancedThreadShare<T> {
         self.inner.update(f);
     }
 
+    /// Updates the shared data through a scratch copy that is committed only on success
+    ///
+    /// See `ThreadShare::update_atomic()`.
+    pub fn update_atomic<F>(&self, f: F)
+    where
+        F: FnOnce(&mut T),
+        T: Clone,
+    {
+        self.inner.update_atomic(f);
+    }
+
+    /// Updates the shared data through a scratch copy, committing only on `Ok`
+    ///
+    /// See `ThreadShare::update_checked()`.
+    pub fn update_checked<F, R, E>(&self, f: F) -> Result<R, E>
+    where
+        F: FnOnce(&mut T) -> Result<R, E>,
+        T: Clone,
+    {
+        self.inner.update_checked(f)
+    }
+
+    /// Reads the shared data only if the lock is available right away
+    ///
+    /// See `ThreadShare::try_read()`.
+    pub fn try_read<F, R>(&self, f: F) -> Result<R>
+    where
+        F: FnOnce(&T) -> R,
+    {
+        self.inner.try_read(f)
+    }
+
+    /// Writes the shared data only if the lock is available right away
+    ///
+    /// See `ThreadShare::try_write()`.
+    pub fn try_write<F, R>(&self, f: F) -> Result<R>
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        self.inner.try_write(f)
+    }
+
+    /// Updates the shared data only if the lock is available right away
+    ///
+    /// See `ThreadShare::try_update()`.
+    pub fn try_update<F>(&self, f: F) -> Result<()>
+    where
+        F: FnOnce(&mut T),
+    {
+        self.inner.try_update(f)
+    }
+
+    /// Reads the shared data, waiting at most `timeout` for the lock
+    ///
+    /// See `ThreadShare::read_for()`.
+    pub fn read_for<F, R>(&self, timeout: std::time::Duration, f: F) -> Result<R>
+    where
+        F: FnOnce(&T) -> R,
+    {
+        self.inner.read_for(timeout, f)
+    }
+
+    /// Writes the shared data, waiting at most `timeout` for the lock
+    ///
+    /// See `ThreadShare::write_for()`.
+    pub fn write_for<F, R>(&self, timeout: std::time::Duration, f: F) -> Result<R>
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        self.inner.write_for(timeout, f)
+    }
+
+    /// Updates the shared data, waiting at most `timeout` for the lock
+    ///
+    /// See `ThreadShare::update_for()`.
+    pub fn update_for<F>(&self, timeout: std::time::Duration, f: F) -> Result<()>
+    where
+        F: FnOnce(&mut T),
+    {
+        self.inner.update_for(timeout, f)
+    }
+
+    /// Locks the shared data for reading until the guard is dropped
+    ///
+    /// See `ThreadShare::read_guard()`.
+    pub fn read_guard(&self) -> ReadGuard<'_, T> {
+        self.inner.read_guard()
+    }
+
+    /// Locks the shared data for writing until the guard is dropped
+    ///
+    /// See `ThreadShare::write_guard()`.
+    pub fn write_guard(&self) -> WriteGuard<'_, T> {
+        self.inner.write_guard()
+    }
+
+    /// Locks the shared data for reading with the option to upgrade
+    ///
+    /// See `ThreadShare::upgradable_read()`.
+    pub fn upgradable_read(&self) -> UpgradableReadGuard<'_, T> {
+        self.inner.upgradable_read()
+    }
+
+    /// Gets a snapshot of the lock statistics
+    ///
+    /// See `ThreadShare::stats()`.
+    #[cfg(feature = "metrics")]
+    pub fn stats(&self) -> crate::metrics::LockStats {
+        self.inner.stats()
+    }
+
     pub fn read<F, R>(&self, f: F) -> R
     where
         F: FnOnce(&T) -> R,
@@ -614,17 +806,143 @@ impl<T> EnhancedThreadShare<T> {
         self.inner.wait_for_change_forever();
     }
 
-    pub fn clone(&self) -> Self {
-        Self {
-            inner: self.inner.clone(),
-            threads: Arc::new(Mutex::new(HashMap::new())),
-        }
+    /// Gets the current version of the shared data
+    ///
+    /// See `ThreadShare::version()`.
+    pub fn version(&self) -> u64 {
+        self.inner.version()
+    }
+
+    /// Waits until the version moves past `version`, with timeout
+    ///
+    /// See `ThreadShare::wait_for_change_since()`.
+    pub fn wait_for_change_since(&self, version: u64, timeout: std::time::Duration) -> Option<u64> {
+        self.inner.wait_for_change_since(version, timeout)
+    }
+
+    /// Waits indefinitely until the version moves past `version`
+    ///
+    /// See `ThreadShare::wait_for_change_since_forever()`.
+    pub fn wait_for_change_since_forever(&self, version: u64) -> u64 {
+        self.inner.wait_for_change_since_forever(version)
+    }
+
+    /// Waits until the shared data satisfies a predicate, with timeout
+    ///
+    /// See `ThreadShare::wait_until()`.
+    pub fn wait_until<F>(&self, predicate: F, timeout: std::time::Duration) -> Result<T>
+    where
+        F: FnMut(&T) -> bool,
+        T: Clone,
+    {
+        self.inner.wait_until(predicate, timeout)
+    }
+
+    /// Waits indefinitely until the shared data satisfies a predicate
+    ///
+    /// See `ThreadShare::wait_until_forever()`.
+    pub fn wait_until_forever<F>(&self, predicate: F) -> T
+    where
+        F: FnMut(&T) -> bool,
+        T: Clone,
+    {
+        self.inner.wait_until_forever(predicate)
+    }
+
+    /// Returns a future that resolves once the shared data changes
+    ///
+    /// See `ThreadShare::changed()`.
+    pub fn changed(&self) -> Changed<'_> {
+        self.inner.changed()
+    }
+
+    /// Returns a future that resolves once the version moves past `version`
+    ///
+    /// See `ThreadShare::changed_since()`.
+    pub fn changed_since(&self, version: u64) -> Changed<'_> {
+        self.inner.changed_since(version)
+    }
+
+    /// Returns a future that resolves once the shared data changes or the timeout expires
+    ///
+    /// See `ThreadShare::changed_timeout()`.
+    pub fn changed_timeout(&self, timeout: std::time::Duration) -> ChangedTimeout<'_> {
+        self.inner.changed_timeout(timeout)
+    }
+
+    /// Subscribes to every change of the shared data
+    ///
+    /// See `ThreadShare::subscribe()`.
+    pub fn subscribe(&self, policy: SubscribePolicy) -> Subscription<T>
+    where
+        T: Clone + Send + 'static,
+    {
+        self.inner.subscribe(policy)
+    }
+
+    /// Subscribes to version changes without cloning the data
+    ///
+    /// See `ThreadShare::subscribe_versions()`.
+    pub fn subscribe_versions(&self, policy: SubscribePolicy) -> VersionSubscription
+    where
+        T: 'static,
+    {
+        self.inner.subscribe_versions(policy)
+    }
+
+    /// Registers a callback invoked after every committed change
+    ///
+    /// See `ThreadShare::on_change()`.
+    pub fn on_change<F>(&self, callback: F) -> CallbackGuard
+    where
+        F: Fn(u64, &T) + Send + Sync + 'static,
+        T: Clone + Send + 'static,
+    {
+        self.inner.on_change(callback)
+    }
+
+    /// Registers an on-change callback with an explicit execution mode
+    ///
+    /// See `ThreadShare::on_change_with()`.
+    pub fn on_change_with<F>(&self, mode: CallbackMode, callback: F) -> CallbackGuard
+    where
+        F: Fn(u64, &T) + Send + Sync + 'static,
+        T: Clone + Send + 'static,
+    {
+        self.inner.on_change_with(mode, callback)
+    }
+
+    /// Creates a read-only share computed from this one
+    ///
+    /// See `ThreadShare::map()`.
+    pub fn map<U, F>(&self, f: F) -> Derived<U>
+    where
+        F: Fn(&T) -> U + Send + Sync + 'static,
+        T: Send + Sync + 'static,
+    {
+        self.inner.map(f)
+    }
+
+    /// Creates a handle to one field of the shared data
+    ///
+    /// See `ThreadShare::project()`.
+    pub fn project<U, G, M>(&self, get: G, get_mut: M) -> Projected<U>
+    where
+        G: Fn(&T) -> &U + Send + Sync + 'static,
+        M: Fn(&mut T) -> &mut U + Send + Sync + 'static,
+        T: Send + Sync + 'static,
+        U: Clone + PartialEq + Send + Sync + 'static,
+    {
+        self.inner.project(get, get_mut)
     }
 }
 
 impl<T> Clone for EnhancedThreadShare<T> {
     fn clone(&self) -> Self {
-        self.clone()
+        Self {
+            inner: self.inner.clone(),
+            threads: Arc::new(Mutex::new(HashMap::new())),
+        }
     }
 }
 
@@ -647,8 +965,8 @@ impl<T: Serialize + Clone + for<'de> Deserialize<'de>> EnhancedThreadShare<T> {
     /// let json_string = enhanced.to_json().expect("Failed to serialize");
     /// assert_eq!(json_string, "42");
     /// ```
-    pub fn to_json(&self) -> Result<String, serde_json::Error> {
-        serde_json::to_string(&self.inner.get())
+    pub fn to_json(&self) -> Result<String> {
+        self.inner.to_json()
     }
 
     /// Deserializes JSON data back into the shared data
@@ -662,7 +980,7 @@ impl<T: Serialize + Clone + for<'de> Deserialize<'de>> EnhancedThreadShare<T> {
     ///
     /// ## Returns
     ///
-    /// `Ok(())` on success, `Err(serde_json::Error)` if deserialization fails.
+    /// `Ok(())` on success, `Err(Error::Deserialize)` if deserialization fails.
     ///
     /// ## Example
     ///
@@ -674,10 +992,8 @@ impl<T: Serialize + Clone + for<'de> Deserialize<'de>> EnhancedThreadShare<T> {
     /// enhanced.from_json(&json_string).unwrap();
     /// assert_eq!(enhanced.get(), 42);
     /// ```
-    pub fn from_json(&self, json_string: &str) -> Result<(), serde_json::Error> {
-        let data: T = serde_json::from_str(json_string)?;
-        self.set(data);
-        Ok(())
+    pub fn from_json(&self, json_string: &str) -> Result<()> {
+        self.inner.from_json(json_string)
     }
 }
 
@@ -0,0 +1,137 @@
+//! # Error Module - Crate-Wide Error Type
+//!
+//! This module provides `Error`, returned by every fallible operation in the
+//! crate, and the `Result<T>` alias.
+//!
+//! ## Overview
+//!
+//! Each variant carries the context needed to handle it programmatically:
+//!
+//! - **`WorkerExists`** / **`WorkerNotFound`** - The worker name involved
+//! - **`WorkerPanicked`** - The worker name and the panic message
+//! - **`Spawn`** - The thread name and the underlying I/O error
+//! - **`Timeout`** - How long the operation waited
+//! - **`WouldBlock`** - A non-blocking operation found the lock taken
+//! - **`Serialize`** / **`Deserialize`** - The `serde_json` error (with the
+//!   `serialize` feature)
+//!
+//! `Error` implements `std::error::Error`, so it works with `?` in functions
+//! returning `Box<dyn std::error::Error>` and exposes the underlying cause
+//! through `source()`.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use thread_share::{enhanced_share, Error};
+//!
+//! let data = enhanced_share!(0);
+//! data.spawn("worker", |_| panic!("out of work")).expect("Failed to spawn");
+//!
+//! match data.join_all() {
+//!     Err(Error::WorkerPanicked { name, message }) => {
+//!         assert_eq!(name, "worker");
+//!         assert_eq!(message, "out of work");
+//!     }
+//!     other => panic!("unexpected result: {:?}", other),
+//! }
+//! ```
+
+use std::any::Any;
+use std::fmt;
+use std::io;
+use std::time::Duration;
+
+/// Errors returned by thread-share operations
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// A worker with this name is already tracked
+    WorkerExists {
+        /// Name of the worker
+        name: String,
+    },
+    /// No worker with this name is tracked
+    WorkerNotFound {
+        /// Name of the worker
+        name: String,
+    },
+    /// A worker thread panicked
+    WorkerPanicked {
+        /// Name of the worker
+        name: String,
+        /// The panic message, if it was a string
+        message: String,
+    },
+    /// The operating system failed to spawn a thread
+    Spawn {
+        /// Name of the thread
+        name: String,
+        /// The underlying error
+        source: io::Error,
+    },
+    /// An operation did not complete within its timeout
+    Timeout {
+        /// How long the operation waited
+        timeout: Duration,
+    },
+    /// A non-blocking operation could not acquire the lock right away
+    WouldBlock,
+    /// Data could not be serialized to JSON
+    #[cfg(feature = "serialize")]
+    Serialize(serde_json::Error),
+    /// JSON could not be deserialized into the data type
+    #[cfg(feature = "serialize")]
+    Deserialize(serde_json::Error),
+}
+
+/// Result type used throughout the crate
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+impl Error {
+    /// Creates a `WorkerPanicked` error from a `JoinHandle::join()` payload
+    pub(crate) fn panicked(name: impl Into<String>, payload: Box<dyn Any + Send>) -> Self {
+        let message = if let Some(message) = payload.downcast_ref::<&str>() {
+            (*message).to_string()
+        } else if let Some(message) = payload.downcast_ref::<String>() {
+            message.clone()
+        } else {
+            "Box<dyn Any>".to_string()
+        };
+        Error::WorkerPanicked {
+            name: name.into(),
+            message,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::WorkerExists { name } => write!(f, "Worker '{}' already exists", name),
+            Error::WorkerNotFound { name } => write!(f, "Worker '{}' not found", name),
+            Error::WorkerPanicked { name, message } => {
+                write!(f, "Worker '{}' panicked: {}", name, message)
+            }
+            Error::Spawn { name, source } => {
+                write!(f, "Failed to spawn thread '{}': {}", name, source)
+            }
+            Error::Timeout { timeout } => write!(f, "Timed out after {:?}", timeout),
+            Error::WouldBlock => write!(f, "Lock is held by another thread"),
+            #[cfg(feature = "serialize")]
+            Error::Serialize(e) => write!(f, "Serialization failed: {}", e),
+            #[cfg(feature = "serialize")]
+            Error::Deserialize(e) => write!(f, "Deserialization failed: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Spawn { source, .. } => Some(source),
+            #[cfg(feature = "serialize")]
+            Error::Serialize(e) | Error::Deserialize(e) => Some(e),
+            _ => None,
+        }
+    }
+}
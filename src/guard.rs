@@ -0,0 +1,262 @@
+//! # Guard Module - Scoped Lock Guards
+//!
+//! This module provides the guards returned by `ThreadShare::read_guard()`,
+//! `ThreadShare::write_guard()` and `ThreadShare::upgradable_read()`.
+//!
+//! ## Overview
+//!
+//! Guards are an alternative to the closure-based `read()` and `write()` for
+//! access that spans several statements or early returns. They deref to `T`
+//! and hold the lock until they are dropped:
+//!
+//! - **`ReadGuard`** - Shared access; other readers may hold the lock too
+//! - **`WriteGuard`** - Exclusive access; dropping it commits the change like
+//!   `update()` does, bumping the version and notifying waiting threads,
+//!   subscribers and callbacks
+//! - **`UpgradableReadGuard`** - Shared access that can be atomically turned
+//!   into a `WriteGuard`, for check-then-write without a race
+//!
+//! ## Example
+//!
+//! ```rust
+//! use thread_share::share;
+//!
+//! let cache = share!(Vec::<String>::new());
+//!
+//! fn insert(cache: &thread_share::ThreadShare<Vec<String>>, key: &str) -> bool {
+//!     let entries = cache.upgradable_read();
+//!     if entries.iter().any(|k| k == key) {
+//!         return false; // No change, no notification
+//!     }
+//!     let mut entries = entries.upgrade();
+//!     entries.push(key.to_string());
+//!     true
+//! }
+//!
+//! assert!(insert(&cache, "a"));
+//! assert!(!insert(&cache, "a"));
+//! assert_eq!(cache.version(), 1);
+//! ```
+//!
+//! ## Deadlocks
+//!
+//! A guard holds the lock for as long as it lives. Calling a method that
+//! writes the same share while holding any of its guards, or reading it
+//! while holding a `WriteGuard`, deadlocks.
+
+use crate::core::ThreadShare;
+use crate::metrics::{Access, Tracked};
+use parking_lot::{RwLockReadGuard, RwLockUpgradableReadGuard, RwLockWriteGuard};
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+
+/// Shared access to the data of a `ThreadShare<T>`
+///
+/// Returned by `ThreadShare::read_guard()`. The read lock is released when
+/// the guard is dropped.
+pub struct ReadGuard<'a, T> {
+    guard: Tracked<'a, RwLockReadGuard<'a, T>>,
+}
+
+impl<T> Deref for ReadGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.guard
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ReadGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+/// Exclusive access to the data of a `ThreadShare<T>`
+///
+/// Returned by `ThreadShare::write_guard()` and
+/// `UpgradableReadGuard::upgrade()`. Dropping the guard commits the change:
+/// the version is bumped and waiting threads, subscribers and callbacks are
+/// notified, exactly as after `update()`. This happens even if the data was
+/// not modified through the guard.
+///
+/// If a panic starts while the guard is held, dropping it releases the lock
+/// without a commit, like `update()` with a panicking closure: the version
+/// stays the same and nobody is notified, even though the data may already
+/// hold part of the change. Guards taken while the thread is already
+/// unwinding, for example in a `Drop` impl, commit as usual.
+pub struct WriteGuard<'a, T> {
+    share: &'a ThreadShare<T>,
+    /// Always `Some` until the guard is dropped
+    guard: Option<Tracked<'a, RwLockWriteGuard<'a, T>>>,
+    /// Whether the thread was already panicking when the guard was taken
+    panicking: bool,
+}
+
+impl<'a, T> WriteGuard<'a, T> {
+    fn new(share: &'a ThreadShare<T>, guard: Tracked<'a, RwLockWriteGuard<'a, T>>) -> Self {
+        Self {
+            share,
+            guard: Some(guard),
+            panicking: std::thread::panicking(),
+        }
+    }
+}
+
+impl<T> Deref for WriteGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.guard.as_ref().expect("write guard already released")
+    }
+}
+
+impl<T> DerefMut for WriteGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.guard.as_mut().expect("write guard already released")
+    }
+}
+
+impl<T> Drop for WriteGuard<'_, T> {
+    fn drop(&mut self) {
+        if let Some(guard) = self.guard.take() {
+            if !self.panicking && std::thread::panicking() {
+                return;
+            }
+            let pending = self.share.commit(&guard);
+            drop(guard);
+            pending.run();
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for WriteGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+/// Shared access that can be upgraded to exclusive access
+///
+/// Returned by `ThreadShare::upgradable_read()`. Only one upgradable reader
+/// can hold the lock at a time, alongside any number of plain readers, so the
+/// data seen before `upgrade()` is still current afterwards. Dropping the
+/// guard without upgrading does not notify anyone.
+pub struct UpgradableReadGuard<'a, T> {
+    share: &'a ThreadShare<T>,
+    guard: Tracked<'a, RwLockUpgradableReadGuard<'a, T>>,
+}
+
+impl<'a, T> UpgradableReadGuard<'a, T> {
+    /// Upgrades to exclusive access
+    ///
+    /// Waits until all other readers have released the lock. No writer can
+    /// get in between, so checks made through this guard remain valid.
+    ///
+    /// ## Returns
+    ///
+    /// A `WriteGuard` that commits the change when dropped.
+    pub fn upgrade(self) -> WriteGuard<'a, T> {
+        WriteGuard::new(self.share, self.guard.upgrade(self.share.probe()))
+    }
+}
+
+impl<T> Deref for UpgradableReadGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.guard
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for UpgradableReadGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T> ThreadShare<T> {
+    /// Locks the data for reading until the guard is dropped
+    ///
+    /// ## Returns
+    ///
+    /// A `ReadGuard` that derefs to the data.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::ThreadShare;
+    ///
+    /// let data = ThreadShare::new(vec![1, 2, 3]);
+    ///
+    /// let items = data.read_guard();
+    /// assert_eq!(items.len(), 3);
+    /// assert_eq!(items[0], 1);
+    /// ```
+    pub fn read_guard(&self) -> ReadGuard<'_, T> {
+        ReadGuard {
+            guard: self.read_lock(),
+        }
+    }
+
+    /// Locks the data for writing until the guard is dropped
+    ///
+    /// Dropping the guard bumps the version and notifies waiting threads,
+    /// like `update()`.
+    ///
+    /// ## Returns
+    ///
+    /// A `WriteGuard` that derefs mutably to the data.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::ThreadShare;
+    ///
+    /// let data = ThreadShare::new(vec![1, 2, 3]);
+    ///
+    /// {
+    ///     let mut items = data.write_guard();
+    ///     items.push(4);
+    ///     items.retain(|x| x % 2 == 0);
+    /// } // Committed here
+    ///
+    /// assert_eq!(data.get(), vec![2, 4]);
+    /// assert_eq!(data.version(), 1);
+    /// ```
+    pub fn write_guard(&self) -> WriteGuard<'_, T> {
+        WriteGuard::new(self, self.write_lock())
+    }
+
+    /// Locks the data for reading with the option to upgrade to writing
+    ///
+    /// Use this to check the data and write only if needed, without another
+    /// writer slipping in between the check and the write.
+    ///
+    /// ## Returns
+    ///
+    /// An `UpgradableReadGuard` that derefs to the data.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::ThreadShare;
+    ///
+    /// let balance = ThreadShare::new(100);
+    ///
+    /// let current = balance.upgradable_read();
+    /// if *current >= 30 {
+    ///     *current.upgrade() -= 30;
+    /// }
+    ///
+    /// assert_eq!(balance.get(), 70);
+    /// ```
+    pub fn upgradable_read(&self) -> UpgradableReadGuard<'_, T> {
+        UpgradableReadGuard {
+            share: self,
+            guard: self
+                .probe()
+                .acquire(Access::Upgradable, || self.data().upgradable_read()),
+        }
+    }
+}
@@ -0,0 +1,386 @@
+//! # History Module - Undo/Redo for Shared Data
+//!
+//! This module provides `HistoryShare<T>`, a `ThreadShare<T>` that remembers
+//! its last committed values and can roll back to them.
+//!
+//! ## Overview
+//!
+//! Every `set()` and `update()` records a snapshot of the new value together
+//! with the version and the time it was committed. `undo()` and `redo()` move
+//! through these snapshots; both are regular changes of the share, so waiting
+//! threads, subscribers and callbacks of the underlying share see them too.
+//!
+//! - **Bounded**: only the last `capacity` values before the current one are
+//!   kept; older ones are discarded
+//! - **Cheap snapshots**: values are stored as `Arc<T>` and shared with the
+//!   entries returned by `history()`
+//! - **Linear history**: a new change after `undo()` discards the values that
+//!   could have been redone
+//!
+//! ## Example
+//!
+//! ```rust
+//! use thread_share::HistoryShare;
+//!
+//! let config = HistoryShare::new(String::from("timeout=30"), 10);
+//!
+//! config.set(String::from("timeout=0")); // bad edit
+//! assert!(config.undo());
+//! assert_eq!(config.get(), "timeout=30");
+//!
+//! assert!(config.redo());
+//! assert_eq!(config.get(), "timeout=0");
+//!
+//! let values: Vec<String> = config.history().iter().map(|e| (*e.value).clone()).collect();
+//! assert_eq!(values, vec!["timeout=30", "timeout=0"]);
+//! ```
+//!
+//! ## Thread Safety
+//!
+//! Values are recorded while the share's write lock is held, so the recorded
+//! history always matches the order in which values were committed. The
+//! history's own mutex is released before the change is committed, so
+//! `on_change()` callbacks registered through `as_share()` may use the
+//! `HistoryShare<T>`, including `set()` and `undo()`. Clones share both the
+//! data and the history.
+
+use crate::changed::Changed;
+use crate::core::ThreadShare;
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+/// A committed value recorded by `HistoryShare<T>`
+#[derive(Debug)]
+pub struct HistoryEntry<T> {
+    /// The value as committed
+    pub value: Arc<T>,
+    /// Version of the share right after the value was committed
+    pub version: u64,
+    /// When the value was committed
+    pub timestamp: SystemTime,
+}
+
+impl<T> Clone for HistoryEntry<T> {
+    fn clone(&self) -> Self {
+        Self {
+            value: Arc::clone(&self.value),
+            version: self.version,
+            timestamp: self.timestamp,
+        }
+    }
+}
+
+struct History<T> {
+    /// Recorded values, oldest first; the one at `cursor` is current
+    entries: VecDeque<HistoryEntry<T>>,
+    cursor: usize,
+    capacity: usize,
+}
+
+impl<T> History<T> {
+    fn record(&mut self, value: Arc<T>, version: u64) {
+        self.entries.truncate(self.cursor + 1);
+        self.entries.push_back(HistoryEntry {
+            value,
+            version,
+            timestamp: SystemTime::now(),
+        });
+        while self.entries.len() > self.capacity + 1 {
+            self.entries.pop_front();
+        }
+        self.cursor = self.entries.len() - 1;
+    }
+}
+
+/// Shared data with bounded undo/redo history
+///
+/// `HistoryShare<T>` wraps a `ThreadShare<T>` and keeps the last `capacity`
+/// committed values so that changes can be rolled back at runtime.
+///
+/// ## Example
+///
+/// ```rust
+/// use thread_share::HistoryShare;
+/// use std::thread;
+///
+/// let counter = HistoryShare::new(0, 5);
+/// let clone = counter.clone();
+///
+/// thread::spawn(move || {
+///     clone.update(|x| *x += 1);
+/// })
+/// .join()
+/// .unwrap();
+///
+/// assert_eq!(counter.get(), 1);
+/// counter.undo();
+/// assert_eq!(counter.get(), 0);
+/// ```
+pub struct HistoryShare<T> {
+    share: ThreadShare<T>,
+    history: Arc<Mutex<History<T>>>,
+}
+
+impl<T> HistoryShare<T>
+where
+    T: Clone,
+{
+    /// Creates a new HistoryShare instance
+    ///
+    /// ## Arguments
+    ///
+    /// * `data` - The initial data
+    /// * `capacity` - Maximum number of earlier values kept for `undo()`
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::HistoryShare;
+    ///
+    /// let data = HistoryShare::new(vec![1, 2, 3], 100);
+    /// assert_eq!(data.history().len(), 1);
+    /// ```
+    pub fn new(data: T, capacity: usize) -> Self {
+        let snapshot = Arc::new(data.clone());
+        let mut history = History {
+            entries: VecDeque::new(),
+            cursor: 0,
+            capacity,
+        };
+        history.record(snapshot, 0);
+        Self {
+            share: ThreadShare::new(data),
+            history: Arc::new(Mutex::new(history)),
+        }
+    }
+
+    /// Gets a copy of the current data
+    pub fn get(&self) -> T {
+        self.share.get()
+    }
+
+    /// Reads the current data through a closure
+    ///
+    /// ## Arguments
+    ///
+    /// * `f` - Closure that receives a reference to the data
+    pub fn read<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&T) -> R,
+    {
+        self.share.read(f)
+    }
+
+    /// Sets new data and records it in the history
+    ///
+    /// Any values that could have been restored with `redo()` are discarded.
+    ///
+    /// ## Arguments
+    ///
+    /// * `new_data` - The new data to set
+    pub fn set(&self, new_data: T) {
+        self.update(|data| *data = new_data);
+    }
+
+    /// Updates data using a function and records the result in the history
+    ///
+    /// Any values that could have been restored with `redo()` are discarded.
+    ///
+    /// ## Arguments
+    ///
+    /// * `f` - Closure that receives a mutable reference to the data
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::HistoryShare;
+    ///
+    /// let data = HistoryShare::new(vec![1], 10);
+    /// data.update(|v| v.push(2));
+    /// data.update(|v| v.push(3));
+    ///
+    /// data.undo();
+    /// assert_eq!(data.get(), vec![1, 2]);
+    /// ```
+    pub fn update<F>(&self, f: F)
+    where
+        F: FnOnce(&mut T),
+    {
+        let mut data = self.share.write_guard();
+        f(&mut data);
+        self.record(&data);
+        // Committed here, with the history unlocked
+        drop(data);
+    }
+
+    /// Records the value about to be committed; the write lock must be held
+    fn record(&self, data: &T) {
+        let snapshot = Arc::new(data.clone());
+        // Only commits bump the version, and they need the write lock
+        let version = self.share.version() + 1;
+        self.history.lock().record(snapshot, version);
+    }
+
+    /// Restores the previous value
+    ///
+    /// The restored value is committed like any other change, so waiting
+    /// threads are notified.
+    ///
+    /// ## Returns
+    ///
+    /// `true` if a previous value was restored, `false` if there is nothing
+    /// to undo.
+    pub fn undo(&self) -> bool {
+        self.restore(|history| history.cursor.checked_sub(1))
+    }
+
+    /// Re-applies the value most recently undone
+    ///
+    /// ## Returns
+    ///
+    /// `true` if a value was restored, `false` if there is nothing to redo.
+    pub fn redo(&self) -> bool {
+        self.restore(|history| Some(history.cursor + 1).filter(|&i| i < history.entries.len()))
+    }
+
+    /// Commits the recorded value at the index chosen by `target`
+    fn restore<F>(&self, target: F) -> bool
+    where
+        F: FnOnce(&History<T>) -> Option<usize>,
+    {
+        // Keeps other writers, and so other history changes, out until the
+        // commit; the history is never locked while waiting for the data
+        let data = self.share.upgradable_read();
+        let (cursor, value) = {
+            let history = self.history.lock();
+            match target(&history) {
+                Some(cursor) => (cursor, Arc::clone(&history.entries[cursor].value)),
+                None => return false,
+            }
+        };
+        let mut data = data.upgrade();
+        *data = T::clone(&value);
+        self.history.lock().cursor = cursor;
+        // Committed here, with the history unlocked
+        drop(data);
+        true
+    }
+}
+
+impl<T> HistoryShare<T> {
+    /// Checks whether `undo()` would restore a value
+    pub fn can_undo(&self) -> bool {
+        self.history.lock().cursor > 0
+    }
+
+    /// Checks whether `redo()` would restore a value
+    pub fn can_redo(&self) -> bool {
+        let history = self.history.lock();
+        history.cursor + 1 < history.entries.len()
+    }
+
+    /// Gets the recorded values, oldest first
+    ///
+    /// The list includes the current value and any values that can still be
+    /// restored with `redo()`. Use `position()` to find the current one.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::HistoryShare;
+    ///
+    /// let data = HistoryShare::new(1, 2);
+    /// data.set(2);
+    /// data.set(3);
+    /// data.set(4);
+    ///
+    /// // Capacity 2: the current value plus two earlier ones
+    /// let values: Vec<i32> = data.history().iter().map(|e| *e.value).collect();
+    /// assert_eq!(values, vec![2, 3, 4]);
+    /// ```
+    pub fn history(&self) -> Vec<HistoryEntry<T>> {
+        self.history.lock().entries.iter().cloned().collect()
+    }
+
+    /// Gets the index of the current value in `history()`
+    pub fn position(&self) -> usize {
+        self.history.lock().cursor
+    }
+
+    /// Discards all recorded values except the current one
+    pub fn clear_history(&self) {
+        let _data = self.share.upgradable_read();
+        let mut history = self.history.lock();
+        let cursor = history.cursor;
+        history.entries.truncate(cursor + 1);
+        history.entries.drain(..cursor);
+        history.cursor = 0;
+    }
+
+    /// Gets the current version of the underlying share
+    ///
+    /// See `ThreadShare::version()`.
+    pub fn version(&self) -> u64 {
+        self.share.version()
+    }
+
+    /// Waits for data changes with timeout
+    ///
+    /// See `ThreadShare::wait_for_change()`.
+    pub fn wait_for_change(&self, timeout: Duration) -> bool {
+        self.share.wait_for_change(timeout)
+    }
+
+    /// Waits for data changes indefinitely
+    ///
+    /// See `ThreadShare::wait_for_change_forever()`.
+    pub fn wait_for_change_forever(&self) {
+        self.share.wait_for_change_forever();
+    }
+
+    /// Returns a future that resolves once the data changes
+    ///
+    /// See `ThreadShare::changed()`.
+    pub fn changed(&self) -> Changed<'_> {
+        self.share.changed()
+    }
+
+    /// Gets the underlying share
+    ///
+    /// Useful for `map()`, `subscribe()` and `on_change()`. Writes made
+    /// directly through the returned share bypass the history: they are not
+    /// recorded, and the next `undo()` or `redo()` overwrites them with a
+    /// recorded value.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::HistoryShare;
+    ///
+    /// let data = HistoryShare::new(1, 10);
+    /// data.set(2);
+    ///
+    /// data.as_share().set(3); // Not recorded
+    /// assert_eq!(data.history().len(), 2);
+    ///
+    /// data.undo();
+    /// assert_eq!(data.get(), 1);
+    /// data.redo();
+    /// assert_eq!(data.get(), 2); // The direct write is gone
+    /// ```
+    pub fn as_share(&self) -> &ThreadShare<T> {
+        &self.share
+    }
+}
+
+impl<T> Clone for HistoryShare<T> {
+    /// Creates another handle sharing the data and the history
+    fn clone(&self) -> Self {
+        Self {
+            share: self.share.clone(),
+            history: Arc::clone(&self.history),
+        }
+    }
+}
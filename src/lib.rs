@@ -11,6 +11,22 @@
 //! - **Multiple complexity levels**: from simple locks to zero-copy atomic operations
 //! - **Enhanced thread management** with automatic spawning and joining
 //! - **Smart macros** for cleaner thread management syntax
+//! - **Async change notification** that works with any runtime
+//! - **Change subscriptions** as iterators, channels or async streams
+//! - **On-change callbacks** that run outside the write lock
+//! - **Derived shares** that lazily recompute when their sources change
+//! - **Multi-share transactions** with deadlock-free lock ordering
+//! - **Undo/redo history** with bounded, timestamped snapshots
+//! - **Scoped lock guards** that notify waiters when a write guard is dropped
+//! - **Lock contention statistics** per share with the `metrics` feature
+//! - **Reentrancy and deadlock detection** with the `debug-locks` feature
+//! - **Long-held lock warnings** with the `lock-warnings` feature
+//! - **Weak handles** that let workers and registries outlive the data
+//! - **Coalesced and debounced notifications** for high-frequency writers
+//! - **Copy-on-write snapshots** for cheap reads of large data
+//! - **Field projections** with their own change detection
+//! - **Native atomic shares** for allocation-free counters and flags
+//! - **Live `ArcThreadShare`-style views** of a `ThreadShare` with `as_arc()`
 //! - **Real-world examples** including HTTP server and socket client
 //!
 //! ## 🎯 Quick Start
@@ -43,6 +59,7 @@
 //!
 //! ### ⚡ Atomic-Based (High Performance)
 //! - **`ArcThreadShare<T>`** - Zero-copy atomic operations (use with caution)
+//! - **`AtomicShare<T>`** - Native atomics for integers, `bool` and floats
 //!
 //! ### 🧵 Enhanced Management
 //! - **`EnhancedThreadShare<T>`** - Automatic thread spawning and joining
@@ -136,11 +153,13 @@
 //! use thread_share::{share, ArcThreadShare};
 //!
 //! let data = share!(String::from("Hello"));
-//! let arc_data = data.as_arc();
-//! let arc_share = ArcThreadShare::from_arc(arc_data);
+//! let arc_share = ArcThreadShare::new(data.get());
 //!
-//! // Use atomic operations for performance
+//! // Lock-free reads, atomic copy-on-write updates
 //! arc_share.update(|s| s.push_str(" World"));
+//!
+//! // Or work on the original data with the same API
+//! data.as_arc().update(|s| s.push_str(" World"));
 //! ```
 //!
 //! ### Enhanced Thread Management
@@ -160,13 +179,13 @@
 //! ## ⚠️ Important Notes
 //!
 //! ### ArcThreadShare Limitations
-//! - **Complex operations are not atomic** - use `increment()` and `add()` methods
-//! - **High contention can cause lost updates** - test with realistic load
-//! - **Memory allocation overhead** per operation
+//! - **Every write clones the value** and allocates a new `Arc<T>`
+//! - **Write closures may be retried** under contention - keep them free of side effects
+//! - **No change notification** - use `ThreadShare<T>` to wait for changes
 //!
 //! ### Best Practices
 //! - Use **`ThreadShare<T>`** for most applications
-//! - Use **`ArcThreadShare<T>`** only when you understand its limitations
+//! - Use **`ArcThreadShare<T>`** for small, read-heavy values
 //! - Use **`EnhancedThreadShare<T>`** for simplified thread management
 //! - Always test with realistic contention levels
 //!
@@ -198,18 +217,54 @@
 //! Contributions are welcome! Please feel free to submit a Pull Request.
 
 pub mod atomic;
+pub mod atomic_share;
+pub mod callback;
+pub mod changed;
 pub mod core;
+#[cfg(feature = "debug-locks")]
+pub mod debug_locks;
+pub mod derived;
 pub mod enhanced;
+pub mod error;
+pub mod guard;
+pub mod history;
+#[cfg(feature = "lock-warnings")]
+pub mod lock_warnings;
+#[cfg(any(feature = "debug-locks", feature = "lock-warnings"))]
+mod lock_info;
 pub mod locked;
 pub mod macros;
+pub mod metrics;
+mod notify;
+pub mod projection;
+pub mod snapshot;
+pub mod subscribe;
 pub mod thread_pool;
+mod timer;
+pub mod transaction;
+pub mod view;
 pub mod worker_manager;
 
 // Re-export main structures
-pub use atomic::ArcThreadShare;
-pub use core::{SimpleShare, ThreadShare};
+pub use atomic::{ArcThreadShare, WeakArcThreadShare};
+pub use atomic_share::{AtomicBits, AtomicNumber, AtomicShare, AtomicValue};
+pub use callback::{CallbackGuard, CallbackMode};
+pub use changed::{Changed, ChangedTimeout};
+pub use core::{SimpleShare, ThreadShare, WeakSimpleShare, WeakThreadShare};
+pub use derived::{combine, combine_all, Derived};
 pub use enhanced::EnhancedThreadShare;
-pub use locked::ArcThreadShareLocked;
+pub use error::{Error, Result};
+pub use guard::{ReadGuard, UpgradableReadGuard, WriteGuard};
+pub use history::{HistoryEntry, HistoryShare};
+pub use locked::{ArcThreadShareLocked, WeakArcThreadShareLocked};
+#[cfg(feature = "metrics")]
+pub use metrics::{AccessStats, LockStats};
+pub use notify::NotifyPolicy;
+pub use projection::Projected;
+pub use snapshot::SnapshotShare;
+pub use subscribe::{Change, SubscribePolicy, Subscription, VersionSubscription};
 pub use thread_pool::ThreadManager;
+pub use transaction::{transaction, TransactionShares};
+pub use view::ArcView;
 
 
@@ -0,0 +1,47 @@
+//! # Lock Info Module - Names Used in Lock Diagnostics
+//!
+//! Internal helpers shared by the `debug-locks` and `lock-warnings` features
+//! for describing a lock in reports: how it is held (`LockKind`), which share
+//! it belongs to and which thread holds it.
+
+use std::fmt;
+use std::sync::Arc;
+use std::thread;
+
+/// How a lock is held or requested, as reported in diagnostics
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LockKind {
+    /// Shared access
+    Read,
+    /// Shared access that may be upgraded to exclusive access
+    UpgradableRead,
+    /// Exclusive access
+    Write,
+}
+
+impl fmt::Display for LockKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LockKind::Read => write!(f, "read lock"),
+            LockKind::UpgradableRead => write!(f, "upgradable read lock"),
+            LockKind::Write => write!(f, "write lock"),
+        }
+    }
+}
+
+/// Name of a share in diagnostics: its given name or the lock's address
+pub(crate) fn share_label(lock: usize, name: Option<&Arc<str>>) -> String {
+    match name {
+        Some(name) => name.to_string(),
+        None => format!("{:#x}", lock),
+    }
+}
+
+/// Name of the current thread in diagnostics, or its id if it has none
+pub(crate) fn thread_label() -> String {
+    let current = thread::current();
+    match current.name() {
+        Some(name) => name.to_string(),
+        None => format!("{:?}", current.id()),
+    }
+}
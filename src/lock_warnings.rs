@@ -0,0 +1,158 @@
+//! # Lock Warnings Module - Long-Held Lock Detection
+//!
+//! This module is available with the `lock-warnings` feature. It reports
+//! locks that were held longer than a threshold, which usually means slow
+//! work such as I/O happened inside `update()`, `write()` or `read()`.
+//!
+//! ## Overview
+//!
+//! Every time a share's lock is released, the time it was held is compared
+//! with the share's threshold, set with `with_hold_threshold()`, or else the
+//! global one (100 ms unless changed with `set_threshold()`). When it is
+//! exceeded, the warning hook is called on the releasing thread, after the
+//! lock was released.
+//!
+//! The default hook logs through the `log` crate at the `warn` level; install
+//! another one with `set_hook()`.
+//!
+//! Hold times of raw guards returned by `ArcThreadShareLocked::get_ref()` and
+//! `get_mut()` are not measured.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use thread_share::ThreadShare;
+//! use std::time::Duration;
+//!
+//! #[cfg(feature = "lock-warnings")]
+//! {
+//!     use thread_share::lock_warnings;
+//!
+//!     lock_warnings::set_hook(|warning| {
+//!         eprintln!("{}", warning);
+//!     });
+//!
+//!     let config = ThreadShare::new(String::new())
+//!         .with_name("config")
+//!         .with_hold_threshold(Duration::from_millis(5));
+//!
+//!     // Reported: "thread 'main' held the write lock on share 'config' for ..."
+//!     config.update(|c| {
+//!         std::thread::sleep(Duration::from_millis(10));
+//!         c.push_str("loaded");
+//!     });
+//! }
+//! ```
+
+use crate::lock_info::{share_label, thread_label};
+use parking_lot::Mutex;
+use std::fmt;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+pub use crate::lock_info::LockKind;
+
+/// A lock that was held longer than its threshold
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HoldWarning {
+    /// Name given with `with_name()`, or the address of the lock
+    pub share: String,
+    /// Name of the thread that held the lock, or its id if it has none
+    pub thread: String,
+    /// How the lock was held
+    pub kind: LockKind,
+    /// How long the lock was held
+    pub held: Duration,
+    /// The threshold that was exceeded
+    pub threshold: Duration,
+}
+
+impl fmt::Display for HoldWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "thread '{}' held the {} on share '{}' for {:?} (threshold {:?})",
+            self.thread, self.kind, self.share, self.held, self.threshold
+        )
+    }
+}
+
+type WarningHook = Arc<dyn Fn(&HoldWarning) + Send + Sync>;
+
+static HOOK: Mutex<Option<WarningHook>> = Mutex::new(None);
+
+/// Global threshold in nanoseconds; `u64::MAX` disables warnings
+static THRESHOLD_NANOS: AtomicU64 = AtomicU64::new(100_000_000);
+
+/// Sets the threshold for shares without their own
+///
+/// ## Arguments
+///
+/// * `threshold` - Maximum hold time before a warning, or `None` to warn
+///   only for shares with their own threshold
+///
+/// ## Example
+///
+/// ```rust
+/// use thread_share::lock_warnings;
+/// use std::time::Duration;
+///
+/// lock_warnings::set_threshold(Some(Duration::from_millis(50)));
+/// assert_eq!(lock_warnings::threshold(), Some(Duration::from_millis(50)));
+/// ```
+pub fn set_threshold(threshold: Option<Duration>) {
+    let nanos = match threshold {
+        Some(threshold) => u64::try_from(threshold.as_nanos()).unwrap_or(u64::MAX - 1),
+        None => u64::MAX,
+    };
+    THRESHOLD_NANOS.store(nanos, Ordering::Relaxed);
+}
+
+/// Gets the threshold for shares without their own
+pub fn threshold() -> Option<Duration> {
+    match THRESHOLD_NANOS.load(Ordering::Relaxed) {
+        u64::MAX => None,
+        nanos => Some(Duration::from_nanos(nanos)),
+    }
+}
+
+/// Installs the function called with each warning
+///
+/// Replaces the default hook, which logs the warning with `log::warn!`. The
+/// hook runs on the thread that released the lock, after releasing it.
+pub fn set_hook<F>(hook: F)
+where
+    F: Fn(&HoldWarning) + Send + Sync + 'static,
+{
+    *HOOK.lock() = Some(Arc::new(hook));
+}
+
+/// Checks a released lock against the share's threshold or the global one
+pub(crate) fn released(
+    share_threshold: Option<Duration>,
+    lock: usize,
+    name: Option<&Arc<str>>,
+    kind: LockKind,
+    held: Duration,
+) {
+    let Some(threshold) = share_threshold.or_else(threshold) else {
+        return;
+    };
+    if held <= threshold {
+        return;
+    }
+
+    let warning = HoldWarning {
+        share: share_label(lock, name),
+        thread: thread_label(),
+        kind,
+        held,
+        threshold,
+    };
+    let hook = HOOK.lock().clone();
+    match hook {
+        Some(hook) => hook(&warning),
+        None => log::warn!("thread-share: {}", warning),
+    }
+}
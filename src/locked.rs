@@ -101,9 +101,9 @@
 //!
 //! ## Thread Safety
 //!
-//! `ArcThreadShareLocked<T>` automatically implements `Send` and `Sync` traits,
-//! making it safe to use across thread boundaries. The internal `RwLock` ensures
-//! that all operations are thread-safe and no data races can occur.
+//! `ArcThreadShareLocked<T>` is `Send` and `Sync` whenever `T` is `Send` and
+//! `Sync`, making it safe to use across thread boundaries. The internal `RwLock`
+//! ensures that all operations are thread-safe and no data races can occur.
 //!
 //! ## Memory Management
 //!
@@ -162,11 +162,17 @@
 //! assert_eq!(data.get(), vec![1, 2, 3, 4]);
 //! ```
 
+use crate::metrics::{Access, Probe};
 use parking_lot::RwLock;
-use std::sync::Arc;
+use std::sync::{Arc, Weak};
 
+#[cfg(feature = "metrics")]
+use crate::metrics::LockStats;
+
+#[cfg(feature = "serialize")]
+use crate::error::Error;
 #[cfg(feature = "serialize")]
-use serde::{de::DeserializeOwned};
+use serde::{de::DeserializeOwned, Serialize};
 
 /// Helper structure for working with Arc<RwLock<T>> directly (with locks)
 ///
@@ -213,18 +219,30 @@ use serde::{de::DeserializeOwned};
 /// - **High Contention**: Consistent performance, no lost operations
 /// - **Memory Usage**: Minimal overhead from lock structures
 /// - **Scalability**: Scales well with thread count
+///
+/// ## Thread Safety
+///
+/// `ArcThreadShareLocked<T>` is `Send` and `Sync` exactly when
+/// `Arc<RwLock<T>>` is, that is when `T` is `Send` and `Sync`:
+///
+/// ```compile_fail
+/// use thread_share::ArcThreadShareLocked;
+/// use std::cell::Cell;
+///
+/// fn assert_sync<S: Sync>(_: S) {}
+///
+/// assert_sync(ArcThreadShareLocked::new(Cell::new(0)));
+/// ```
 pub struct ArcThreadShareLocked<T> {
     pub data: Arc<RwLock<T>>,
+    probe: Probe,
 }
 
-// Automatically implement Send and Sync for ArcThreadShareLocked
-unsafe impl<T> Send for ArcThreadShareLocked<T> {}
-unsafe impl<T> Sync for ArcThreadShareLocked<T> {}
-
 impl<T> Clone for ArcThreadShareLocked<T> {
     fn clone(&self) -> Self {
         Self {
             data: Arc::clone(&self.data),
+            probe: self.probe.clone(),
         }
     }
 }
@@ -254,7 +272,7 @@ impl<T> ArcThreadShareLocked<T> {
     /// ```
     pub fn new(data: T) -> Self {
         let arc = Arc::new(RwLock::new(data));
-        Self { data: arc }
+        Self::from_arc(arc)
     }
 
     /// Creates from Arc<RwLock<T>>
@@ -284,7 +302,54 @@ impl<T> ArcThreadShareLocked<T> {
     /// locked_share.update(|v| v.push(4));
     /// ```
     pub fn from_arc(arc: Arc<RwLock<T>>) -> Self {
-        Self { data: arc }
+        Self {
+            probe: Probe::new(&arc),
+            data: arc,
+        }
+    }
+
+    /// Names the share for diagnostics
+    ///
+    /// The name appears in the reentrancy panics and deadlock reports of the
+    /// `debug-locks` feature. Name the share right after creating it: clones
+    /// made before the call keep the old name.
+    ///
+    /// ## Arguments
+    ///
+    /// * `name` - Name shown in diagnostics
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::ArcThreadShareLocked;
+    ///
+    /// let config = ArcThreadShareLocked::new(0).with_name("config");
+    /// assert_eq!(config.name(), Some("config"));
+    /// ```
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.probe.set_name(name);
+        self
+    }
+
+    /// Gets the name given with `with_name()`
+    pub fn name(&self) -> Option<&str> {
+        self.probe.name()
+    }
+
+    /// Sets how long the lock may be held before a warning is reported
+    ///
+    /// Requires the `lock-warnings` feature. Overrides the global threshold
+    /// of `lock_warnings::set_threshold()` for this share; use
+    /// `Duration::MAX` to never warn. Like `with_name()`, call it right after
+    /// creating the share.
+    ///
+    /// ## Arguments
+    ///
+    /// * `threshold` - Maximum hold time before a warning
+    #[cfg(feature = "lock-warnings")]
+    pub fn with_hold_threshold(mut self, threshold: std::time::Duration) -> Self {
+        self.probe.set_hold_threshold(threshold);
+        self
     }
 
     /// Gets a copy of data
@@ -313,7 +378,9 @@ impl<T> ArcThreadShareLocked<T> {
     where
         T: Clone,
     {
-        self.data.read().clone()
+        self.probe
+            .acquire(Access::Read, || self.data.read())
+            .clone()
     }
 
     /// Gets a reference to data (no cloning!)
@@ -347,7 +414,7 @@ impl<T> ArcThreadShareLocked<T> {
     /// Multiple threads can read simultaneously.
     /// For non-blocking behavior, use `try_get_ref()`.
     pub fn get_ref(&self) -> parking_lot::RwLockReadGuard<'_, T> {
-        self.data.read()
+        self.probe.acquire_untracked(Access::Read, || self.data.read())
     }
 
     /// Tries to get a reference to data without blocking
@@ -379,7 +446,8 @@ impl<T> ArcThreadShareLocked<T> {
     /// assert_eq!(data.get(), vec![1, 2, 3]);
     /// ```
     pub fn try_get_ref(&self) -> Option<parking_lot::RwLockReadGuard<'_, T>> {
-        self.data.try_read()
+        self.probe
+            .try_acquire_untracked(Access::Read, || self.data.try_read())
     }
 
     /// Gets a mutable reference to data (no cloning!)
@@ -420,7 +488,7 @@ impl<T> ArcThreadShareLocked<T> {
     /// - Always drop the guard explicitly in complex scenarios
     /// - Consider using `try_get_mut()` for non-blocking operations
     pub fn get_mut(&self) -> parking_lot::RwLockWriteGuard<'_, T> {
-        self.data.write()
+        self.probe.acquire_untracked(Access::Write, || self.data.write())
     }
 
     /// Tries to get a mutable reference to data without blocking
@@ -449,7 +517,8 @@ impl<T> ArcThreadShareLocked<T> {
     /// assert_eq!(data.get(), vec![1, 2, 3, 4]);
     /// ```
     pub fn try_get_mut(&self) -> Option<parking_lot::RwLockWriteGuard<'_, T>> {
-        self.data.try_write()
+        self.probe
+            .try_acquire_untracked(Access::Write, || self.data.try_write())
     }
 
     /// Sets data
@@ -470,7 +539,7 @@ impl<T> ArcThreadShareLocked<T> {
     /// assert_eq!(counter.get(), 100);
     /// ```
     pub fn set(&self, new_data: T) {
-        let mut data = self.data.write();
+        let mut data = self.probe.acquire(Access::Write, || self.data.write());
         *data = new_data;
     }
 
@@ -500,7 +569,7 @@ impl<T> ArcThreadShareLocked<T> {
     where
         F: FnOnce(&mut T),
     {
-        let mut data = self.data.write();
+        let mut data = self.probe.acquire(Access::Write, || self.data.write());
         f(&mut data);
     }
 
@@ -534,7 +603,7 @@ impl<T> ArcThreadShareLocked<T> {
     where
         F: FnOnce(&T) -> R,
     {
-        let data = self.data.read();
+        let data = self.probe.acquire(Access::Read, || self.data.read());
         f(&data)
     }
 
@@ -570,20 +639,164 @@ impl<T> ArcThreadShareLocked<T> {
     where
         F: FnOnce(&mut T) -> R,
     {
-        let mut data = self.data.write();
+        let mut data = self.probe.acquire(Access::Write, || self.data.write());
         f(&mut data)
     }
 
+    /// Creates a weak handle that does not keep the data alive
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::ArcThreadShareLocked;
+    ///
+    /// let data = ArcThreadShareLocked::new(1);
+    /// let weak = data.downgrade();
+    ///
+    /// assert_eq!(weak.upgrade().map(|d| d.get()), Some(1));
+    /// drop(data);
+    /// assert!(weak.upgrade().is_none());
+    /// ```
+    pub fn downgrade(&self) -> WeakArcThreadShareLocked<T> {
+        WeakArcThreadShareLocked {
+            data: Arc::downgrade(&self.data),
+            probe: self.probe.clone(),
+        }
+    }
+
+    /// Gets the number of handles keeping the data alive
+    ///
+    /// Includes the `ThreadShare<T>` the data came from when created with
+    /// `from_arc(share.as_arc_locked())`.
+    pub fn strong_count(&self) -> usize {
+        Arc::strong_count(&self.data)
+    }
+
+    /// Gets the number of weak handles to the data
+    pub fn weak_count(&self) -> usize {
+        Arc::weak_count(&self.data)
+    }
+
+    /// Gets a snapshot of the lock statistics
+    ///
+    /// Requires the `metrics` feature. Acquisitions through `get_ref()` and
+    /// `get_mut()` are counted, but their hold time is not tracked.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::ArcThreadShareLocked;
+    ///
+    /// #[cfg(feature = "metrics")]
+    /// {
+    ///     let counter = ArcThreadShareLocked::new(0);
+    ///     counter.update(|x| *x += 1);
+    ///     assert_eq!(counter.stats().writes.count, 1);
+    /// }
+    /// ```
+    #[cfg(feature = "metrics")]
+    pub fn stats(&self) -> LockStats {
+        self.probe.stats()
+    }
+
+    /// Serializes the current data to JSON string
+    ///
+    /// This method requires the `serialize` feature to be enabled.
+    ///
+    /// ## Returns
+    ///
+    /// JSON string representation of the data, or `Error::Serialize` if serialization fails.
     #[cfg(feature = "serialize")]
-    pub fn to_json(&self) -> Result<String, serde_json::Error>
+    pub fn to_json(&self) -> crate::Result<String>
     where
-        T: serde::Serialize + Clone,
+        T: Serialize,
     {
-        serde_json::to_string(&self.get())
+        self.read(serde_json::to_string).map_err(Error::Serialize)
     }
 
+    /// Deserializes data from JSON string and sets it
+    ///
+    /// This method requires the `serialize` feature to be enabled.
+    ///
+    /// ## Arguments
+    ///
+    /// * `json` - JSON string to deserialize
+    ///
+    /// ## Returns
+    ///
+    /// `Ok(())` on success, `Err(Error::Deserialize)` if deserialization fails.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::ArcThreadShareLocked;
+    ///
+    /// #[cfg(feature = "serialize")]
+    /// {
+    ///     let data = ArcThreadShareLocked::new(String::new());
+    ///     data.from_json(r#""ok""#).expect("Failed to deserialize");
+    ///     assert_eq!(data.get(), "ok");
+    ///     assert_eq!(data.to_json().unwrap(), r#""ok""#);
+    /// }
+    /// ```
     #[cfg(feature = "serialize")]
-    pub fn from_json<D: DeserializeOwned>(&self, json: &str) -> Result<D, serde_json::Error> {
-        serde_json::from_str(json)
+    pub fn from_json(&self, json: &str) -> crate::Result<()>
+    where
+        T: DeserializeOwned,
+    {
+        let deserialized: T = serde_json::from_str(json).map_err(Error::Deserialize)?;
+
+        self.set(deserialized);
+        Ok(())
+    }
+}
+
+/// Weak handle to the data of an `ArcThreadShareLocked<T>`
+///
+/// Created with `ArcThreadShareLocked::downgrade()`. It does not keep the
+/// data alive; `upgrade()` returns `None` once every owning handle is gone.
+///
+/// Like the owning handle, it is only `Send` and `Sync` for thread-safe data:
+///
+/// ```compile_fail
+/// use thread_share::ArcThreadShareLocked;
+/// use std::cell::Cell;
+///
+/// fn assert_sync<S: Sync>(_: S) {}
+///
+/// let data = ArcThreadShareLocked::new(Cell::new(0));
+/// assert_sync(data.downgrade());
+/// ```
+pub struct WeakArcThreadShareLocked<T> {
+    data: Weak<RwLock<T>>,
+    probe: Probe,
+}
+
+impl<T> WeakArcThreadShareLocked<T> {
+    /// Gets an owning handle if the data is still alive
+    pub fn upgrade(&self) -> Option<ArcThreadShareLocked<T>> {
+        Some(ArcThreadShareLocked {
+            data: self.data.upgrade()?,
+            probe: self.probe.clone(),
+        })
+    }
+
+    /// Gets the number of handles keeping the data alive
+    pub fn strong_count(&self) -> usize {
+        self.data.strong_count()
+    }
+
+    /// Gets the number of weak handles to the data
+    pub fn weak_count(&self) -> usize {
+        self.data.weak_count()
+    }
+}
+
+impl<T> Clone for WeakArcThreadShareLocked<T> {
+    fn clone(&self) -> Self {
+        Self {
+            data: Weak::clone(&self.data),
+            probe: self.probe.clone(),
+        }
     }
 }
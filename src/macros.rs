@@ -11,6 +11,7 @@
 //! - **`share!`** - Creates `ThreadShare<T>` instances with automatic type inference
 //! - **`simple_share!`** - Creates `SimpleShare<T>` instances for basic use cases
 //! - **`enhanced_share!`** - Creates `EnhancedThreadShare<T>` instances
+//! - **`atomic_share!`** - Creates `AtomicShare<T>` instances for integers, `bool` and floats
 //! - **`spawn_workers!`** - Spawns multiple threads with single macro call, returns WorkerManager
 //! - **`spawn_threads!`** - Alternative thread spawning macro for ThreadManager
 //! - **`thread_setup!`** - Sets up thread management with shared data, returns ThreadManager
@@ -387,6 +388,54 @@ macro_rules! enhanced_share {
     };
 }
 
+/// Macro for creating AtomicShare
+///
+/// This macro creates an `AtomicShare<T>` instance, backed by a native atomic
+/// type, for integers, `bool`, floats and raw pointers.
+///
+/// ## Syntax
+///
+/// `atomic_share!(expression)`
+///
+/// ## Arguments
+///
+/// * `expression` - The initial value
+///
+/// ## Returns
+///
+/// A new `AtomicShare<T>` instance where `T` is inferred from the expression.
+///
+/// ## Example
+///
+/// ```rust
+/// use thread_share::atomic_share;
+///
+/// let counter = atomic_share!(0);             // AtomicShare<i32>
+/// let bytes = atomic_share!(0u64);            // AtomicShare<u64>
+/// let flag = atomic_share!(false);            // AtomicShare<bool>
+/// let load = atomic_share!(0.0);              // AtomicShare<f64>
+///
+/// counter.increment();
+/// bytes.fetch_add(512);
+/// flag.set(true);
+/// load.fetch_max(0.75);
+/// ```
+///
+/// ## When to Use
+///
+/// Use `atomic_share!` for hot counters, flags and gauges: every operation is
+/// a single atomic instruction and never allocates.
+///
+/// Use `share!` when you need:
+/// - Any other data type
+/// - Change detection and waiting mechanisms
+#[macro_export]
+macro_rules! atomic_share {
+    ($data:expr) => {
+        $crate::AtomicShare::new($data)
+    };
+}
+
 /// Macro for simplified multi-threaded setup with WorkerManager
 ///
 /// This macro spawns multiple threads and returns a `WorkerManager` instance
@@ -505,7 +554,7 @@ macro_rules! spawn_workers {
 ///
 /// ## Returns
 ///
-/// `Result<(), String>` from `spawn_multiple`.
+/// `thread_share::Result<()>` from `spawn_multiple`.
 ///
 /// ## Performance
 ///
@@ -0,0 +1,409 @@
+//! # Metrics Module - Lock Contention Statistics
+//!
+//! This module provides `LockStats`, a snapshot of how a share's lock has been
+//! used, returned by `stats()` on `ThreadShare<T>`, `SimpleShare<T>` and
+//! `ArcThreadShareLocked<T>`.
+//!
+//! ## Overview
+//!
+//! Statistics are collected only with the `metrics` feature enabled. Without
+//! it the instrumentation compiles away and `stats()` does not exist.
+//!
+//! For reads and writes separately, every successful lock acquisition records:
+//!
+//! - **Count** - How many times the lock was acquired
+//! - **Wait time** - Total and maximum time spent waiting for the lock
+//! - **Hold time** - Total and maximum time the lock was held
+//!
+//! `ThreadShare<T>` additionally counts the notifications it sent, one per
+//! committed change.
+//!
+//! Statistics are shared by all clones of a share. Failed `try_*` and timed
+//! acquisitions are not counted, and the hold time of raw guards returned by
+//! `ArcThreadShareLocked::get_ref()` and `get_mut()` is not tracked.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use thread_share::share;
+//!
+//! #[cfg(feature = "metrics")]
+//! {
+//!     let data = share!(vec![1, 2, 3]);
+//!     data.update(|v| v.push(4));
+//!     let _ = data.get();
+//!
+//!     let stats = data.stats();
+//!     assert_eq!(stats.writes.count, 1);
+//!     assert_eq!(stats.reads.count, 1);
+//!     assert_eq!(stats.notifications, 1);
+//!     println!("longest write wait: {:?}", stats.writes.max_wait);
+//! }
+//! ```
+
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+
+#[cfg(feature = "debug-locks")]
+use crate::debug_locks;
+#[cfg(any(feature = "debug-locks", feature = "lock-warnings"))]
+use crate::lock_info::LockKind;
+#[cfg(feature = "lock-warnings")]
+use crate::lock_warnings;
+#[cfg(feature = "metrics")]
+use std::sync::atomic::{AtomicU64, Ordering};
+#[cfg(any(feature = "metrics", feature = "lock-warnings"))]
+use std::time::{Duration, Instant};
+
+use parking_lot::{RwLockUpgradableReadGuard, RwLockWriteGuard};
+
+/// Statistics for one kind of lock access
+#[cfg(feature = "metrics")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AccessStats {
+    /// Number of successful acquisitions
+    pub count: u64,
+    /// Total time spent waiting to acquire the lock
+    pub total_wait: Duration,
+    /// Longest single wait
+    pub max_wait: Duration,
+    /// Total time the lock was held
+    pub total_hold: Duration,
+    /// Longest single hold
+    pub max_hold: Duration,
+}
+
+/// Snapshot of a share's lock statistics
+///
+/// Returned by `stats()`; the values do not change after the snapshot is
+/// taken.
+#[cfg(feature = "metrics")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LockStats {
+    /// Shared (read) acquisitions
+    pub reads: AccessStats,
+    /// Exclusive (write) acquisitions
+    pub writes: AccessStats,
+    /// Notifications sent to waiting threads, one per committed change
+    pub notifications: u64,
+}
+
+/// Kind of lock access being recorded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Access {
+    Read,
+    /// Read that may be taken while the thread already holds a read lock
+    ReadRecursive,
+    Upgradable,
+    Write,
+}
+
+#[cfg(any(feature = "debug-locks", feature = "lock-warnings"))]
+impl Access {
+    fn kind(self) -> LockKind {
+        match self {
+            Access::Read | Access::ReadRecursive => LockKind::Read,
+            Access::Upgradable => LockKind::UpgradableRead,
+            Access::Write => LockKind::Write,
+        }
+    }
+}
+
+#[cfg(feature = "metrics")]
+#[derive(Default)]
+struct Counters {
+    count: AtomicU64,
+    wait_nanos: AtomicU64,
+    max_wait_nanos: AtomicU64,
+    hold_nanos: AtomicU64,
+    max_hold_nanos: AtomicU64,
+}
+
+#[cfg(feature = "metrics")]
+impl Counters {
+    fn acquired(&self, wait: Duration) {
+        let nanos = saturating_nanos(wait);
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.wait_nanos.fetch_add(nanos, Ordering::Relaxed);
+        self.max_wait_nanos.fetch_max(nanos, Ordering::Relaxed);
+    }
+
+    fn released(&self, hold: Duration) {
+        let nanos = saturating_nanos(hold);
+        self.hold_nanos.fetch_add(nanos, Ordering::Relaxed);
+        self.max_hold_nanos.fetch_max(nanos, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self) -> AccessStats {
+        let load = |counter: &AtomicU64| Duration::from_nanos(counter.load(Ordering::Relaxed));
+        AccessStats {
+            count: self.count.load(Ordering::Relaxed),
+            total_wait: load(&self.wait_nanos),
+            max_wait: load(&self.max_wait_nanos),
+            total_hold: load(&self.hold_nanos),
+            max_hold: load(&self.max_hold_nanos),
+        }
+    }
+}
+
+#[cfg(feature = "metrics")]
+fn saturating_nanos(duration: Duration) -> u64 {
+    u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX)
+}
+
+#[cfg(feature = "metrics")]
+#[derive(Default)]
+struct ProbeInner {
+    reads: Counters,
+    writes: Counters,
+    notifications: AtomicU64,
+}
+
+#[cfg(feature = "metrics")]
+impl ProbeInner {
+    fn counters(&self, access: Access) -> &Counters {
+        match access {
+            Access::Read | Access::ReadRecursive | Access::Upgradable => &self.reads,
+            Access::Write => &self.writes,
+        }
+    }
+}
+
+/// Lock instrumentation shared by all handles of a share
+///
+/// Carries the share's name and, with the `metrics`, `debug-locks` and
+/// `lock-warnings` features, its statistics, lock identity and hold
+/// threshold. Without them acquisitions go straight to the lock.
+#[derive(Clone)]
+pub(crate) struct Probe {
+    name: Option<Arc<str>>,
+    #[cfg(any(feature = "debug-locks", feature = "lock-warnings"))]
+    lock: usize,
+    #[cfg(feature = "metrics")]
+    inner: Arc<ProbeInner>,
+    #[cfg(feature = "lock-warnings")]
+    hold_threshold: Option<Duration>,
+}
+
+impl Probe {
+    /// Creates the instrumentation for the lock behind `lock`
+    pub(crate) fn new<L: ?Sized>(lock: &Arc<L>) -> Self {
+        #[cfg(feature = "debug-locks")]
+        debug_locks::start_detector();
+        #[cfg(not(any(feature = "debug-locks", feature = "lock-warnings")))]
+        let _ = lock;
+        Self {
+            name: None,
+            #[cfg(any(feature = "debug-locks", feature = "lock-warnings"))]
+            lock: Arc::as_ptr(lock) as *const () as usize,
+            #[cfg(feature = "metrics")]
+            inner: Arc::default(),
+            #[cfg(feature = "lock-warnings")]
+            hold_threshold: None,
+        }
+    }
+
+    /// Name used in diagnostics
+    pub(crate) fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub(crate) fn set_name(&mut self, name: &str) {
+        self.name = Some(Arc::from(name));
+    }
+
+    /// Overrides the global threshold for long-held lock warnings
+    #[cfg(feature = "lock-warnings")]
+    pub(crate) fn set_hold_threshold(&mut self, threshold: Duration) {
+        self.hold_threshold = Some(threshold);
+    }
+
+    /// Acquires a lock through `lock`, recording the wait and the hold time
+    pub(crate) fn acquire<G>(&self, access: Access, lock: impl FnOnce() -> G) -> Tracked<'_, G> {
+        self.before_blocking(access);
+        let start = Stamp::now();
+        let guard = lock();
+        Tracked {
+            guard,
+            hold: self.hold(access, start),
+        }
+    }
+
+    /// Like `acquire()`, for non-blocking and timed acquisitions
+    ///
+    /// Failed attempts are not recorded.
+    pub(crate) fn try_acquire<G>(
+        &self,
+        access: Access,
+        lock: impl FnOnce() -> Option<G>,
+    ) -> Option<Tracked<'_, G>> {
+        let start = Stamp::now();
+        let guard = lock()?;
+        Some(Tracked {
+            guard,
+            hold: self.hold(access, start),
+        })
+    }
+
+    /// Acquires a lock whose guard is handed out as is; only the wait is recorded
+    pub(crate) fn acquire_untracked<G>(&self, access: Access, lock: impl FnOnce() -> G) -> G {
+        self.before_blocking(access);
+        let start = Stamp::now();
+        let guard = lock();
+        #[cfg(feature = "debug-locks")]
+        debug_locks::acquired_untracked();
+        self.acquired(access, start);
+        guard
+    }
+
+    /// Like `acquire_untracked()`, for non-blocking and timed acquisitions
+    pub(crate) fn try_acquire_untracked<G>(
+        &self,
+        access: Access,
+        lock: impl FnOnce() -> Option<G>,
+    ) -> Option<G> {
+        let start = Stamp::now();
+        let guard = lock()?;
+        self.acquired(access, start);
+        Some(guard)
+    }
+
+    /// Records a notification sent to waiting threads
+    pub(crate) fn notified(&self) {
+        #[cfg(feature = "metrics")]
+        self.inner.notifications.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Takes a snapshot of the statistics
+    #[cfg(feature = "metrics")]
+    pub(crate) fn stats(&self) -> LockStats {
+        LockStats {
+            reads: self.inner.reads.snapshot(),
+            writes: self.inner.writes.snapshot(),
+            notifications: self.inner.notifications.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Records an acquisition that started waiting at `start`
+    fn acquired(&self, access: Access, start: Stamp) -> Stamp {
+        let acquired = Stamp::now();
+        #[cfg(feature = "metrics")]
+        self.inner.counters(access).acquired(acquired.0 - start.0);
+        #[cfg(not(feature = "metrics"))]
+        let _ = (access, start);
+        acquired
+    }
+
+    /// Panics if blocking on the lock would deadlock the current thread
+    #[cfg(feature = "debug-locks")]
+    fn before_blocking(&self, access: Access) {
+        match access {
+            Access::ReadRecursive => {
+                debug_locks::before_recursive_read(self.lock, self.name.as_ref())
+            }
+            _ => debug_locks::before_blocking(self.lock, self.name.as_ref(), access.kind()),
+        }
+    }
+
+    #[cfg(not(feature = "debug-locks"))]
+    fn before_blocking(&self, _access: Access) {}
+
+    fn hold(&self, access: Access, start: Stamp) -> Hold<'_> {
+        let acquired = self.acquired(access, start);
+        #[cfg(not(any(feature = "metrics", feature = "lock-warnings")))]
+        let _ = acquired;
+        Hold {
+            #[cfg(any(feature = "metrics", feature = "lock-warnings"))]
+            probe: self,
+            #[cfg(any(feature = "metrics", feature = "lock-warnings"))]
+            access,
+            #[cfg(any(feature = "metrics", feature = "lock-warnings"))]
+            acquired,
+            #[cfg(feature = "debug-locks")]
+            token: debug_locks::acquired(self.lock, self.name.as_ref(), access.kind()),
+            _probe: PhantomData,
+        }
+    }
+
+    /// Records that a lock acquired at `acquired` was released
+    #[cfg(any(feature = "metrics", feature = "lock-warnings"))]
+    fn released(&self, access: Access, acquired: Stamp) {
+        let held = acquired.0.elapsed();
+        #[cfg(feature = "metrics")]
+        self.inner.counters(access).released(held);
+        #[cfg(feature = "lock-warnings")]
+        lock_warnings::released(
+            self.hold_threshold,
+            self.lock,
+            self.name.as_ref(),
+            access.kind(),
+            held,
+        );
+    }
+}
+
+/// A point in time, empty unless a feature needs timing
+#[derive(Clone, Copy)]
+struct Stamp(#[cfg(any(feature = "metrics", feature = "lock-warnings"))] Instant);
+
+impl Stamp {
+    fn now() -> Self {
+        Stamp(
+            #[cfg(any(feature = "metrics", feature = "lock-warnings"))]
+            Instant::now(),
+        )
+    }
+}
+
+/// Records the release of a `Tracked` guard when dropped
+struct Hold<'a> {
+    #[cfg(any(feature = "metrics", feature = "lock-warnings"))]
+    probe: &'a Probe,
+    #[cfg(any(feature = "metrics", feature = "lock-warnings"))]
+    access: Access,
+    #[cfg(any(feature = "metrics", feature = "lock-warnings"))]
+    acquired: Stamp,
+    #[cfg(feature = "debug-locks")]
+    token: u64,
+    _probe: PhantomData<&'a Probe>,
+}
+
+impl Drop for Hold<'_> {
+    fn drop(&mut self) {
+        #[cfg(any(feature = "metrics", feature = "lock-warnings"))]
+        self.probe.released(self.access, self.acquired);
+        #[cfg(feature = "debug-locks")]
+        debug_locks::released(self.token);
+    }
+}
+
+/// A lock guard whose hold time is recorded once it is released
+pub(crate) struct Tracked<'a, G> {
+    // Declared first so the lock is released before the hold time is recorded
+    guard: G,
+    hold: Hold<'a>,
+}
+
+impl<G: Deref> Deref for Tracked<'_, G> {
+    type Target = G::Target;
+
+    fn deref(&self) -> &G::Target {
+        &self.guard
+    }
+}
+
+impl<G: DerefMut> DerefMut for Tracked<'_, G> {
+    fn deref_mut(&mut self) -> &mut G::Target {
+        &mut self.guard
+    }
+}
+
+impl<'a, 'l, T> Tracked<'a, RwLockUpgradableReadGuard<'l, T>> {
+    /// Upgrades to a write lock, recording the read hold and the write wait
+    pub(crate) fn upgrade(self, probe: &'a Probe) -> Tracked<'a, RwLockWriteGuard<'l, T>> {
+        let Tracked { guard, hold } = self;
+        drop(hold);
+        probe.acquire(Access::Write, || RwLockUpgradableReadGuard::upgrade(guard))
+    }
+}
@@ -0,0 +1,301 @@
+//! # Notify Module - Versioned Change Notification
+//!
+//! Internal building block shared by every handle of a `ThreadShare<T>`.
+//!
+//! Each committed mutation bumps a monotonically increasing **version**
+//! (generation number). Waiters compare against the version they last
+//! observed, so a change that lands before the waiter parks is never lost:
+//! the check and the wait happen under the same mutex that the writer
+//! takes to publish the new version.
+//!
+//! Blocking waiters park on a condition variable, async waiters register a
+//! `Waker`. Both are woken by the same `notify()` call.
+//!
+//! A notifier can have **dependents** (used by derived shares): every
+//! `notify()` also advances them, so they advance whenever a source does.
+//!
+//! Waking is governed by the notifier's `NotifyPolicy`. The version is always
+//! bumped right away, but waiters only see it once it is **published**: on
+//! every change with `Immediate`, or later from the crate's shared timer
+//! thread with `Coalesce` and `Debounce`. Dependents are published together with
+//! their source.
+
+use crate::timer::timer;
+use parking_lot::{Condvar, Mutex};
+use std::collections::HashMap;
+use std::sync::{Arc, Weak};
+use std::task::{Context, Poll, Waker};
+use std::time::{Duration, Instant};
+
+/// When waiting threads and tasks are woken after a change
+///
+/// Set with `ThreadShare::with_notify_policy()`. Whatever the policy, every
+/// change bumps the version right away and waiters observe the latest
+/// version when they wake; only the number of wakeups differs. Subscriptions
+/// and on-change callbacks still see every change.
+///
+/// ## Example
+///
+/// ```rust
+/// use thread_share::{NotifyPolicy, ThreadShare};
+/// use std::time::Duration;
+///
+/// // Wake waiters at most every 50 ms, however often the counter changes
+/// let counter = ThreadShare::new(0)
+///     .with_notify_policy(NotifyPolicy::Coalesce(Duration::from_millis(50)));
+///
+/// for _ in 0..1000 {
+///     counter.update(|x| *x += 1);
+/// }
+/// assert_eq!(counter.version(), 1000);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NotifyPolicy {
+    /// Wake waiters on every change
+    #[default]
+    Immediate,
+    /// Wake waiters at most once per interval
+    ///
+    /// The first change after a quiet interval wakes waiters right away;
+    /// further changes within the interval are delivered together at its end.
+    Coalesce(Duration),
+    /// Wake waiters once no change happened for the given quiet period
+    ///
+    /// A writer that never pauses for that long delays waiters indefinitely.
+    Debounce(Duration),
+}
+
+struct State {
+    version: u64,
+    /// Latest version waiters have been woken for
+    published: u64,
+    policy: NotifyPolicy,
+    /// When the pending changes are due to be published
+    due: Option<Instant>,
+    /// When a version was last published, tracked for `Coalesce` only
+    published_at: Option<Instant>,
+    wakers: HashMap<u64, Waker>,
+    next_key: u64,
+}
+
+/// Shared version counter plus everything needed to wake waiters
+pub(crate) struct Notifier {
+    state: Mutex<State>,
+    condvar: Condvar,
+    dependents: Mutex<Vec<Weak<Notifier>>>,
+}
+
+impl Notifier {
+    /// Creates a notifier starting at version `0`
+    pub(crate) fn new() -> Self {
+        Self {
+            state: Mutex::new(State {
+                version: 0,
+                published: 0,
+                policy: NotifyPolicy::Immediate,
+                due: None,
+                published_at: None,
+                wakers: HashMap::new(),
+                next_key: 0,
+            }),
+            condvar: Condvar::new(),
+            dependents: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Returns the current version
+    pub(crate) fn version(&self) -> u64 {
+        self.state.lock().version
+    }
+
+    /// Returns the current policy
+    pub(crate) fn policy(&self) -> NotifyPolicy {
+        self.state.lock().policy
+    }
+
+    /// Changes the policy, publishing any change still pending
+    pub(crate) fn set_policy(&self, policy: NotifyPolicy) {
+        let mut state = self.state.lock();
+        state.policy = policy;
+        let pending = state.due.take().is_some();
+        drop(state);
+
+        if pending {
+            self.publish();
+        }
+    }
+
+    /// Bumps the version and wakes waiters as the policy allows
+    ///
+    /// Must be called while the data write lock is still held so that
+    /// versions are assigned in the same order as the mutations.
+    pub(crate) fn notify(self: &Arc<Self>) -> u64 {
+        let mut state = self.state.lock();
+        state.version += 1;
+        let current = state.version;
+        let mut schedule = None;
+        let publish = match state.policy {
+            NotifyPolicy::Immediate => true,
+            NotifyPolicy::Coalesce(_) if state.due.is_some() => false,
+            NotifyPolicy::Coalesce(interval) => match state.published_at {
+                Some(at) if Instant::now() < at + interval => {
+                    state.due = Some(at + interval);
+                    schedule = state.due;
+                    false
+                }
+                _ => true,
+            },
+            NotifyPolicy::Debounce(quiet) => {
+                if state.due.replace(Instant::now() + quiet).is_none() {
+                    schedule = state.due;
+                }
+                false
+            }
+        };
+        drop(state);
+
+        self.for_each_dependent(Notifier::advance);
+        if publish {
+            self.publish();
+        } else if let Some(due) = schedule {
+            schedule_publish(due, Arc::downgrade(self));
+        }
+        current
+    }
+
+    /// Bumps the version without waking anyone, along with all dependents
+    fn advance(&self) {
+        self.state.lock().version += 1;
+        self.for_each_dependent(Notifier::advance);
+    }
+
+    /// Wakes all waiters for the current version, then publishes dependents
+    fn publish(&self) {
+        let mut state = self.state.lock();
+        state.due = None;
+        if state.published == state.version {
+            return;
+        }
+        state.published = state.version;
+        if matches!(state.policy, NotifyPolicy::Coalesce(_)) {
+            state.published_at = Some(Instant::now());
+        }
+        let wakers = std::mem::take(&mut state.wakers);
+        drop(state);
+
+        self.condvar.notify_all();
+        for waker in wakers.into_values() {
+            waker.wake();
+        }
+        self.for_each_dependent(Notifier::publish);
+    }
+
+    /// Publishes pending changes that are due by `now`
+    ///
+    /// Returns when to check again if the due time moved later meanwhile.
+    fn publish_due(&self, now: Instant) -> Option<Instant> {
+        let state = self.state.lock();
+        match state.due {
+            Some(due) if due > now => Some(due),
+            Some(_) => {
+                drop(state);
+                self.publish();
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn for_each_dependent(&self, f: impl Fn(&Notifier)) {
+        let mut dependents = self.dependents.lock();
+        if !dependents.is_empty() {
+            dependents.retain(|dependent| match dependent.upgrade() {
+                Some(dependent) => {
+                    f(&dependent);
+                    true
+                }
+                None => false,
+            });
+        }
+    }
+
+    /// Registers a notifier that is notified along with this one
+    ///
+    /// The dependent is dropped from the list once it is no longer alive.
+    pub(crate) fn add_dependent(&self, dependent: Weak<Notifier>) {
+        self.dependents.lock().push(dependent);
+    }
+
+    /// Waits until the published version moves past `since`
+    ///
+    /// Returns the new version, or `None` if `deadline` passed first.
+    /// With no deadline the call waits indefinitely.
+    pub(crate) fn wait_since(&self, since: u64, deadline: Option<Instant>) -> Option<u64> {
+        let mut state = self.state.lock();
+        while state.published <= since {
+            match deadline {
+                Some(deadline) => {
+                    if self.condvar.wait_until(&mut state, deadline).timed_out() {
+                        return if state.published > since {
+                            Some(state.published)
+                        } else {
+                            None
+                        };
+                    }
+                }
+                None => self.condvar.wait(&mut state),
+            }
+        }
+        Some(state.published)
+    }
+
+    /// Polls for the published version to move past `since`
+    ///
+    /// While pending, the task's waker is registered under `key` (allocated
+    /// on first use) and replaced on later polls. The caller must pass the
+    /// key to `unregister()` when it stops polling.
+    pub(crate) fn poll_since(
+        &self,
+        since: u64,
+        key: &mut Option<u64>,
+        cx: &mut Context<'_>,
+    ) -> Poll<u64> {
+        let mut state = self.state.lock();
+        if state.published > since {
+            if let Some(key) = key.take() {
+                state.wakers.remove(&key);
+            }
+            return Poll::Ready(state.published);
+        }
+
+        let key = *key.get_or_insert_with(|| {
+            let key = state.next_key;
+            state.next_key += 1;
+            key
+        });
+        match state.wakers.get_mut(&key) {
+            Some(waker) if waker.will_wake(cx.waker()) => {}
+            Some(waker) => waker.clone_from(cx.waker()),
+            None => {
+                state.wakers.insert(key, cx.waker().clone());
+            }
+        }
+        Poll::Pending
+    }
+
+    /// Drops the waker registered under `key`, if any
+    pub(crate) fn unregister(&self, key: u64) {
+        self.state.lock().wakers.remove(&key);
+    }
+}
+
+/// Has the timer thread publish the notifier's pending changes at `due`
+fn schedule_publish(due: Instant, notifier: Weak<Notifier>) {
+    timer().schedule(due, move || {
+        if let Some(strong) = notifier.upgrade() {
+            if let Some(later) = strong.publish_due(Instant::now()) {
+                schedule_publish(later, notifier);
+            }
+        }
+    });
+}
@@ -0,0 +1,356 @@
+//! # Projection Module - Field Views of a Share
+//!
+//! This module provides `Projected<U>`, a handle to one field of a
+//! `ThreadShare<T>`, created with `ThreadShare::project()`.
+//!
+//! ## Overview
+//!
+//! A projection gives code access to a part of the shared state and nothing
+//! else. It has no data of its own: every access locks the parent share and
+//! goes through the accessors given to `project()`, so writes through a
+//! projection are regular changes of the parent.
+//!
+//! Projections have their own change detection. After every change of the
+//! parent, made through the projection or not, the field is compared with its
+//! previous value, and the projection's version only advances, waking its
+//! waiters, when the field actually changed.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use thread_share::share;
+//! use std::time::Duration;
+//!
+//! #[derive(Clone)]
+//! struct AppState {
+//!     stats: Vec<u32>,
+//!     title: String,
+//! }
+//!
+//! let state = share!(AppState { stats: vec![], title: String::from("app") });
+//! let stats = state.project(|s| &s.stats, |s| &mut s.stats);
+//!
+//! // The worker only sees the stats
+//! stats.update(|stats| stats.push(1));
+//! assert_eq!(state.read(|s| s.stats.len()), 1);
+//!
+//! stats.wait_for_change(Duration::from_millis(10)); // Sees the change above
+//!
+//! // Other fields changing does not wake stats waiters
+//! state.update(|s| s.title.push('!'));
+//! assert_eq!(stats.version(), 1);
+//! assert!(stats.wait_for_change(Duration::from_millis(10))); // Timed out
+//! ```
+//!
+//! ## Cost
+//!
+//! The comparison runs under the parent's write lock after every change and
+//! clones the field when it changed. Project small fields, or fields that are
+//! cheap to compare.
+
+use crate::callback::CallbackGuard;
+use crate::changed::{Changed, ChangedTimeout};
+use crate::core::ThreadShare;
+use crate::notify::Notifier;
+use parking_lot::Mutex;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// Type-erased access to the field through the parent share
+trait Lens<U>: Send + Sync {
+    fn read(&self, f: &mut dyn FnMut(&U));
+    fn write(&self, f: &mut dyn FnMut(&mut U));
+}
+
+struct Field<T, G, M> {
+    share: ThreadShare<T>,
+    get: Arc<G>,
+    get_mut: M,
+}
+
+impl<T, U, G, M> Lens<U> for Field<T, G, M>
+where
+    G: Fn(&T) -> &U + Send + Sync,
+    M: Fn(&mut T) -> &mut U + Send + Sync,
+{
+    fn read(&self, f: &mut dyn FnMut(&U)) {
+        self.share.read(|data| f((self.get)(data)));
+    }
+
+    fn write(&self, f: &mut dyn FnMut(&mut U)) {
+        self.share.write(|data| f((self.get_mut)(data)));
+    }
+}
+
+struct ProjectedInner<U> {
+    lens: Box<dyn Lens<U>>,
+    notifier: Arc<Notifier>,
+    /// Keeps the field comparison registered on the parent
+    _watch: CallbackGuard,
+    /// Makes the projection `Send` and `Sync` only if `U` is, whatever the lens
+    _field: PhantomData<U>,
+}
+
+/// Handle to one field of a `ThreadShare<T>`
+///
+/// Created with `ThreadShare::project()`. Reads and writes lock the parent
+/// share; waiting methods only wake when the field itself changed. Clones
+/// refer to the same projection.
+///
+/// A projection can only be created for data that is `Send` and `Sync`,
+/// since it hands out references to the field on any thread:
+///
+/// ```compile_fail
+/// use thread_share::ThreadShare;
+/// use std::cell::Cell;
+///
+/// let data = ThreadShare::new((Cell::new(0), 0));
+/// let cell = data.project(|d| &d.0, |d| &mut d.0);
+/// ```
+///
+/// ## Example
+///
+/// ```rust
+/// use thread_share::share;
+/// use std::thread;
+///
+/// let state = share!((0u32, String::from("idle")));
+/// let counter = state.project(|s| &s.0, |s| &mut s.0);
+///
+/// let worker = counter.clone();
+/// thread::spawn(move || worker.update(|c| *c += 1));
+///
+/// counter.wait_for_change_forever();
+/// assert_eq!(counter.get(), 1);
+/// assert_eq!(state.read(|s| s.1.clone()), "idle");
+/// ```
+pub struct Projected<U> {
+    inner: Arc<ProjectedInner<U>>,
+    /// Last version observed through this handle
+    seen: AtomicU64,
+}
+
+impl<U> Projected<U> {
+    /// Gets a copy of the field
+    pub fn get(&self) -> U
+    where
+        U: Clone,
+    {
+        self.read(U::clone)
+    }
+
+    /// Reads the field through a closure
+    ///
+    /// The parent's read lock is held while the closure runs.
+    ///
+    /// ## Arguments
+    ///
+    /// * `f` - Closure that receives a reference to the field
+    ///
+    /// ## Returns
+    ///
+    /// The result of the closure execution.
+    pub fn read<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&U) -> R,
+    {
+        let mut f = Some(f);
+        let mut result = None;
+        self.inner
+            .lens
+            .read(&mut |value| result = f.take().map(|f| f(value)));
+        result.expect("projection accessor was not called")
+    }
+
+    /// Writes the field and returns a result
+    ///
+    /// The write is a change of the parent share: its version is bumped and
+    /// its waiters are notified. Waiters on the projection are notified only
+    /// if the field ended up different.
+    ///
+    /// ## Arguments
+    ///
+    /// * `f` - Closure that receives a mutable reference to the field
+    pub fn write<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut U) -> R,
+    {
+        let mut f = Some(f);
+        let mut result = None;
+        self.inner
+            .lens
+            .write(&mut |value| result = f.take().map(|f| f(value)));
+        result.expect("projection accessor was not called")
+    }
+
+    /// Updates the field using a function
+    ///
+    /// See `write()`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `f` - Closure that receives a mutable reference to the field
+    pub fn update<F>(&self, f: F)
+    where
+        F: FnOnce(&mut U),
+    {
+        self.write(f);
+    }
+
+    /// Sets the field
+    ///
+    /// ## Arguments
+    ///
+    /// * `new_value` - The new value of the field
+    pub fn set(&self, new_value: U) {
+        self.write(|value| *value = new_value);
+    }
+
+    /// Gets the current version of the projection
+    ///
+    /// The version starts at `0` and is incremented every time the field
+    /// changes, which can be less often than the parent's version.
+    pub fn version(&self) -> u64 {
+        self.inner.notifier.version()
+    }
+
+    /// Waits for the field to change, with timeout
+    ///
+    /// Behaves like `ThreadShare::wait_for_change()`.
+    ///
+    /// ## Returns
+    ///
+    /// `true` if the timeout was reached, `false` if the field changed.
+    pub fn wait_for_change(&self, timeout: Duration) -> bool {
+        let since = self.seen.load(Ordering::Acquire);
+        match self
+            .inner
+            .notifier
+            .wait_since(since, Some(Instant::now() + timeout))
+        {
+            Some(version) => {
+                self.seen.fetch_max(version, Ordering::AcqRel);
+                false
+            }
+            None => true,
+        }
+    }
+
+    /// Waits for the field to change indefinitely
+    ///
+    /// Behaves like `ThreadShare::wait_for_change_forever()`.
+    pub fn wait_for_change_forever(&self) {
+        let since = self.seen.load(Ordering::Acquire);
+        if let Some(version) = self.inner.notifier.wait_since(since, None) {
+            self.seen.fetch_max(version, Ordering::AcqRel);
+        }
+    }
+
+    /// Returns a future that resolves once the field changes
+    ///
+    /// Behaves like `ThreadShare::changed()`.
+    pub fn changed(&self) -> Changed<'_> {
+        Changed::new(
+            &self.inner.notifier,
+            Some(&self.seen),
+            self.seen.load(Ordering::Acquire),
+        )
+    }
+
+    /// Returns a future that resolves once the field changes or the timeout expires
+    ///
+    /// Behaves like `ThreadShare::changed_timeout()`.
+    pub fn changed_timeout(&self, timeout: Duration) -> ChangedTimeout<'_> {
+        ChangedTimeout::new(self.changed(), timeout)
+    }
+}
+
+impl<U> Clone for Projected<U> {
+    /// Creates another handle to the same projection
+    ///
+    /// Clones start out having observed the same version as the handle they
+    /// were cloned from.
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+            seen: AtomicU64::new(self.seen.load(Ordering::Acquire)),
+        }
+    }
+}
+
+impl<T> ThreadShare<T> {
+    /// Creates a handle to one field of the data
+    ///
+    /// The projection locks this share for every access, and is notified
+    /// only when the field changes. Hand it to code that should not see or
+    /// modify the rest of the data.
+    ///
+    /// ## Arguments
+    ///
+    /// * `get` - Returns a reference to the field
+    /// * `get_mut` - Returns a mutable reference to the same field
+    ///
+    /// ## Returns
+    ///
+    /// A `Projected<U>` that keeps this share alive.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::ThreadShare;
+    ///
+    /// let point = ThreadShare::new((1, 2));
+    /// let x = point.project(|p| &p.0, |p| &mut p.0);
+    ///
+    /// x.set(10);
+    /// assert_eq!(point.get(), (10, 2));
+    ///
+    /// // Same value: the parent changed, the projection did not
+    /// x.set(10);
+    /// assert_eq!(point.version(), 2);
+    /// assert_eq!(x.version(), 1);
+    /// ```
+    pub fn project<U, G, M>(&self, get: G, get_mut: M) -> Projected<U>
+    where
+        G: Fn(&T) -> &U + Send + Sync + 'static,
+        M: Fn(&mut T) -> &mut U + Send + Sync + 'static,
+        T: Send + Sync + 'static,
+        U: Clone + PartialEq + Send + Sync + 'static,
+    {
+        let get = Arc::new(get);
+        let notifier = Arc::new(Notifier::new());
+
+        // Registered under the read lock so no change slips in between
+        let data = self.read_lock();
+        let last = Mutex::new(get(&data).clone());
+        let watch = {
+            let get = Arc::clone(&get);
+            let notifier = Arc::clone(&notifier);
+            self.callbacks().watch(move |data: &T| {
+                let value = get(data);
+                let mut last = last.lock();
+                if *last != *value {
+                    last.clone_from(value);
+                    notifier.notify();
+                }
+            })
+        };
+        drop(data);
+
+        Projected {
+            inner: Arc::new(ProjectedInner {
+                lens: Box::new(Field {
+                    share: self.clone(),
+                    get,
+                    get_mut,
+                }),
+                notifier,
+                _watch: watch,
+                _field: PhantomData,
+            }),
+            seen: AtomicU64::new(0),
+        }
+    }
+}
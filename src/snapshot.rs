@@ -0,0 +1,344 @@
+//! # Snapshot Module - Copy-on-Write Shared Data
+//!
+//! This module provides `SnapshotShare<T>`, a `ThreadShare<T>` alternative for
+//! large data that is read far more often than it is written.
+//!
+//! ## Overview
+//!
+//! The data is stored as an `Arc<T>`:
+//!
+//! - **O(1) reads**: `snapshot()` clones the `Arc`, holding the read lock only
+//!   for that; the snapshot stays valid and unchanged for as long as it is kept
+//! - **Copy-on-write**: `update()` and `write()` modify the data in place
+//!   through `Arc::make_mut`, so `T` is cloned only while readers still hold
+//!   an older snapshot
+//! - **Same notifications**: every change bumps the version and wakes waiting
+//!   threads exactly like `ThreadShare<T>`; subscriptions and callbacks
+//!   receive snapshots instead of deep copies
+//!
+//! ## Example
+//!
+//! ```rust
+//! use thread_share::SnapshotShare;
+//! use std::collections::HashMap;
+//!
+//! let routes = SnapshotShare::new(HashMap::from([("/", "index")]));
+//!
+//! // Readers get the current map without copying it
+//! let before = routes.snapshot();
+//!
+//! // Clones the map once, since `before` still refers to it
+//! routes.update(|r| {
+//!     r.insert("/about", "about");
+//! });
+//!
+//! assert_eq!(before.len(), 1);
+//! assert_eq!(routes.snapshot().len(), 2);
+//! ```
+
+use crate::callback::CallbackGuard;
+use crate::changed::{Changed, ChangedTimeout};
+use crate::core::ThreadShare;
+use crate::error::Result;
+use crate::notify::NotifyPolicy;
+use crate::subscribe::{SubscribePolicy, Subscription};
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Shared data handed out as cheap, immutable snapshots
+///
+/// `SnapshotShare<T>` wraps a `ThreadShare<Arc<T>>`. Clones share the data.
+///
+/// Snapshots are handed to any thread, so a `SnapshotShare<T>` is only
+/// `Send` and `Sync` if `T` is:
+///
+/// ```compile_fail
+/// use thread_share::SnapshotShare;
+/// use std::cell::Cell;
+///
+/// fn assert_sync<S: Sync>(_: S) {}
+///
+/// assert_sync(SnapshotShare::new(Cell::new(0)));
+/// ```
+///
+/// ## Example
+///
+/// ```rust
+/// use thread_share::SnapshotShare;
+/// use std::thread;
+///
+/// let data = SnapshotShare::new(vec![1, 2, 3]);
+/// let writer = data.clone();
+///
+/// thread::spawn(move || {
+///     writer.update(|v| v.push(4));
+/// });
+///
+/// data.wait_for_change_forever();
+/// assert_eq!(*data.snapshot(), vec![1, 2, 3, 4]);
+/// ```
+pub struct SnapshotShare<T> {
+    share: ThreadShare<Arc<T>>,
+    /// Derives `Send` and `Sync` from `Arc<T>`, not from `ThreadShare`
+    _data: PhantomData<Arc<T>>,
+}
+
+impl<T> SnapshotShare<T> {
+    /// Creates a new SnapshotShare instance
+    ///
+    /// ## Arguments
+    ///
+    /// * `data` - The initial data
+    pub fn new(data: T) -> Self {
+        Self::from_arc(Arc::new(data))
+    }
+
+    /// Creates a SnapshotShare from data that is already in an `Arc`
+    ///
+    /// ## Arguments
+    ///
+    /// * `data` - The initial data
+    pub fn from_arc(data: Arc<T>) -> Self {
+        Self {
+            share: ThreadShare::new(data),
+            _data: PhantomData,
+        }
+    }
+
+    /// Sets when waiting threads and tasks are woken after a change
+    ///
+    /// See `ThreadShare::with_notify_policy()`.
+    pub fn with_notify_policy(self, policy: NotifyPolicy) -> Self {
+        Self {
+            share: self.share.with_notify_policy(policy),
+            _data: PhantomData,
+        }
+    }
+
+    /// Gets the current data without copying it
+    ///
+    /// ## Returns
+    ///
+    /// An `Arc<T>` that keeps referring to the data as of this call, whatever
+    /// later changes are made.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::SnapshotShare;
+    ///
+    /// let data = SnapshotShare::new(String::from("v1"));
+    /// let snapshot = data.snapshot();
+    ///
+    /// data.set(String::from("v2"));
+    /// assert_eq!(*snapshot, "v1");
+    /// assert_eq!(*data.snapshot(), "v2");
+    /// ```
+    pub fn snapshot(&self) -> Arc<T> {
+        self.share.read(Arc::clone)
+    }
+
+    /// Gets a copy of the current data
+    ///
+    /// Prefer `snapshot()`, which does not copy.
+    pub fn get(&self) -> T
+    where
+        T: Clone,
+    {
+        T::clone(&self.snapshot())
+    }
+
+    /// Reads the current data through a closure
+    ///
+    /// The closure runs on a snapshot, so no lock is held while it runs.
+    ///
+    /// ## Arguments
+    ///
+    /// * `f` - Closure that receives a reference to the data
+    pub fn read<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&T) -> R,
+    {
+        f(&self.snapshot())
+    }
+
+    /// Sets new data and notifies waiting threads
+    ///
+    /// Existing snapshots keep the previous data.
+    ///
+    /// ## Arguments
+    ///
+    /// * `new_data` - The new data to set
+    pub fn set(&self, new_data: T) {
+        self.share.set(Arc::new(new_data));
+    }
+
+    /// Updates data in place and notifies waiting threads
+    ///
+    /// The data is cloned first only if a snapshot of it is still held
+    /// somewhere; otherwise it is modified without copying.
+    ///
+    /// ## Requirements
+    ///
+    /// The type `T` must implement `Clone` trait.
+    ///
+    /// ## Arguments
+    ///
+    /// * `f` - Closure that receives a mutable reference to the data
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::SnapshotShare;
+    /// use std::sync::Arc;
+    ///
+    /// let data = SnapshotShare::new(vec![0; 1000]);
+    ///
+    /// let before = Arc::as_ptr(&data.snapshot());
+    /// data.update(|v| v[0] = 1); // No snapshot held: modified in place
+    /// assert_eq!(Arc::as_ptr(&data.snapshot()), before);
+    /// ```
+    pub fn update<F>(&self, f: F)
+    where
+        F: FnOnce(&mut T),
+        T: Clone,
+    {
+        self.share.update(|data| f(Arc::make_mut(data)));
+    }
+
+    /// Writes data in place, notifies waiting threads and returns a result
+    ///
+    /// Like `update()`, but the closure can return a value.
+    ///
+    /// ## Arguments
+    ///
+    /// * `f` - Closure that receives a mutable reference to the data
+    pub fn write<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut T) -> R,
+        T: Clone,
+    {
+        self.share.write(|data| f(Arc::make_mut(data)))
+    }
+
+    /// Gets the current version of the data
+    ///
+    /// See `ThreadShare::version()`.
+    pub fn version(&self) -> u64 {
+        self.share.version()
+    }
+
+    /// Waits for data changes with timeout
+    ///
+    /// See `ThreadShare::wait_for_change()`.
+    pub fn wait_for_change(&self, timeout: Duration) -> bool {
+        self.share.wait_for_change(timeout)
+    }
+
+    /// Waits for data changes indefinitely
+    ///
+    /// See `ThreadShare::wait_for_change_forever()`.
+    pub fn wait_for_change_forever(&self) {
+        self.share.wait_for_change_forever();
+    }
+
+    /// Waits until the version moves past `version`, with timeout
+    ///
+    /// See `ThreadShare::wait_for_change_since()`.
+    pub fn wait_for_change_since(&self, version: u64, timeout: Duration) -> Option<u64> {
+        self.share.wait_for_change_since(version, timeout)
+    }
+
+    /// Waits indefinitely until the version moves past `version`
+    ///
+    /// See `ThreadShare::wait_for_change_since_forever()`.
+    pub fn wait_for_change_since_forever(&self, version: u64) -> u64 {
+        self.share.wait_for_change_since_forever(version)
+    }
+
+    /// Waits until the data satisfies a predicate, with timeout
+    ///
+    /// See `ThreadShare::wait_until()`.
+    ///
+    /// ## Returns
+    ///
+    /// A snapshot of the data that satisfied the predicate, or
+    /// `Err(Error::Timeout)`.
+    pub fn wait_until<F>(&self, mut predicate: F, timeout: Duration) -> Result<Arc<T>>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.share.wait_until(|data| predicate(data), timeout)
+    }
+
+    /// Waits indefinitely until the data satisfies a predicate
+    ///
+    /// See `ThreadShare::wait_until_forever()`.
+    pub fn wait_until_forever<F>(&self, mut predicate: F) -> Arc<T>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.share.wait_until_forever(|data| predicate(data))
+    }
+
+    /// Returns a future that resolves once the data changes
+    ///
+    /// See `ThreadShare::changed()`.
+    pub fn changed(&self) -> Changed<'_> {
+        self.share.changed()
+    }
+
+    /// Returns a future that resolves once the version moves past `version`
+    ///
+    /// See `ThreadShare::changed_since()`.
+    pub fn changed_since(&self, version: u64) -> Changed<'_> {
+        self.share.changed_since(version)
+    }
+
+    /// Returns a future that resolves once the data changes or `timeout` expires
+    ///
+    /// See `ThreadShare::changed_timeout()`.
+    pub fn changed_timeout(&self, timeout: Duration) -> ChangedTimeout<'_> {
+        self.share.changed_timeout(timeout)
+    }
+
+    /// Subscribes to every change of the data
+    ///
+    /// See `ThreadShare::subscribe()`. Each change carries a snapshot rather
+    /// than a copy of the data.
+    pub fn subscribe(&self, policy: SubscribePolicy) -> Subscription<Arc<T>>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.share.subscribe(policy)
+    }
+
+    /// Registers a callback that runs after every change
+    ///
+    /// See `ThreadShare::on_change()`.
+    pub fn on_change<F>(&self, callback: F) -> CallbackGuard
+    where
+        F: Fn(u64, &Arc<T>) + Send + Sync + 'static,
+        T: Send + Sync + 'static,
+    {
+        self.share.on_change(callback)
+    }
+
+    /// Gets the underlying share
+    ///
+    /// Useful for `map()`, guards and the other `ThreadShare<T>` methods.
+    pub fn as_share(&self) -> &ThreadShare<Arc<T>> {
+        &self.share
+    }
+}
+
+impl<T> Clone for SnapshotShare<T> {
+    /// Creates another handle sharing the data
+    fn clone(&self) -> Self {
+        Self {
+            share: self.share.clone(),
+            _data: PhantomData,
+        }
+    }
+}
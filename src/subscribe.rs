@@ -0,0 +1,416 @@
+//! # Subscribe Module - Change Subscriptions
+//!
+//! This module provides `Subscription<V>`, returned by `ThreadShare::subscribe()`
+//! and `ThreadShare::subscribe_versions()`, which delivers every committed change
+//! to the subscriber instead of making it poll.
+//!
+//! ## Overview
+//!
+//! Each subscription owns a small buffer that is filled by `set()`, `update()`
+//! and `write()` while the write lock is held, so changes are delivered in
+//! version order. How the buffer behaves is controlled by `SubscribePolicy`:
+//!
+//! - **`SubscribePolicy::Latest`** - Watch semantics: only the newest change is
+//!   kept, older undelivered ones are replaced
+//! - **`SubscribePolicy::Queue(n)`** - Every change is queued, up to `n`; when
+//!   the queue is full the oldest change is dropped
+//!
+//! Every delivered `Change` reports how many changes were skipped right before
+//! it in `lagged`, so a slow subscriber knows it missed something.
+//!
+//! ## Consuming Changes
+//!
+//! - **Blocking**: `recv()`, `recv_timeout()`, `try_recv()` or plain iteration
+//! - **`std::sync::mpsc`**: `into_receiver()` for code built around channels
+//! - **Async**: `Subscription<V>` implements `futures_core::Stream` when the
+//!   `async` feature is enabled
+//!
+//! A subscription ends once every `ThreadShare<T>` handle of the share has been
+//! dropped and all buffered changes were consumed.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use thread_share::{share, SubscribePolicy};
+//! use std::thread;
+//!
+//! let data = share!(0);
+//! let changes = data.subscribe(SubscribePolicy::Queue(16));
+//!
+//! let writer = data.clone();
+//! thread::spawn(move || {
+//!     for i in 1..=3 {
+//!         writer.set(i);
+//!     }
+//! });
+//!
+//! let values: Vec<i32> = changes.take(3).map(|change| change.value).collect();
+//! assert_eq!(values, vec![1, 2, 3]);
+//! ```
+
+use parking_lot::{Condvar, Mutex};
+use std::collections::VecDeque;
+use std::sync::mpsc::{self, RecvTimeoutError, TryRecvError};
+use std::sync::{Arc, Weak};
+use std::task::Waker;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How a subscription buffers changes it has not consumed yet
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubscribePolicy {
+    /// Keep only the newest change (watch semantics)
+    Latest,
+    /// Queue every change, keeping at most this many (at least one)
+    Queue(usize),
+}
+
+/// A single change delivered to a subscription
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change<V> {
+    /// Version of the share after this change
+    pub version: u64,
+    /// The data as of this change (`()` for version-only subscriptions)
+    pub value: V,
+    /// Number of changes skipped right before this one
+    pub lagged: u64,
+}
+
+/// Subscription that yields only versions, for types that are not `Clone`
+pub type VersionSubscription = Subscription<()>;
+
+struct SlotState<V> {
+    queue: VecDeque<(u64, V)>,
+    closed: bool,
+    waker: Option<Waker>,
+}
+
+struct Slot<V> {
+    policy: SubscribePolicy,
+    state: Mutex<SlotState<V>>,
+    condvar: Condvar,
+}
+
+impl<V> Slot<V> {
+    fn push(&self, version: u64, value: V) {
+        let mut state = self.state.lock();
+        match self.policy {
+            SubscribePolicy::Latest => state.queue.clear(),
+            SubscribePolicy::Queue(capacity) => {
+                if state.queue.len() >= capacity.max(1) {
+                    state.queue.pop_front();
+                }
+            }
+        }
+        state.queue.push_back((version, value));
+        let waker = state.waker.take();
+        drop(state);
+
+        self.condvar.notify_one();
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+
+    fn close(&self) {
+        let mut state = self.state.lock();
+        state.closed = true;
+        let waker = state.waker.take();
+        drop(state);
+
+        self.condvar.notify_all();
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+/// Type-erased subscription endpoint stored by the share
+trait Subscriber<T>: Send + Sync {
+    /// Delivers a change; returns `false` once the subscription is gone
+    fn deliver(&self, version: u64, data: &T) -> bool;
+    fn close(&self);
+}
+
+struct SlotSubscriber<V, F> {
+    slot: Weak<Slot<V>>,
+    map: F,
+}
+
+impl<T, V, F> Subscriber<T> for SlotSubscriber<V, F>
+where
+    V: Send,
+    F: Fn(&T) -> V + Send + Sync,
+{
+    fn deliver(&self, version: u64, data: &T) -> bool {
+        match self.slot.upgrade() {
+            Some(slot) => {
+                slot.push(version, (self.map)(data));
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn close(&self) {
+        if let Some(slot) = self.slot.upgrade() {
+            slot.close();
+        }
+    }
+}
+
+/// Registry of subscriptions shared by all handles of a `ThreadShare<T>`
+///
+/// Dropping it (when the last handle goes away) ends every subscription.
+pub(crate) struct Subscribers<T> {
+    list: Mutex<Vec<Box<dyn Subscriber<T>>>>,
+}
+
+impl<T> Subscribers<T> {
+    pub(crate) fn new() -> Self {
+        Self {
+            list: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Delivers a committed change; called with the data write lock held
+    pub(crate) fn deliver(&self, version: u64, data: &T) {
+        let mut list = self.list.lock();
+        if !list.is_empty() {
+            list.retain(|subscriber| subscriber.deliver(version, data));
+        }
+    }
+
+    /// Registers a new subscription; called with the data lock held
+    pub(crate) fn subscribe<V, F>(
+        &self,
+        policy: SubscribePolicy,
+        version: u64,
+        map: F,
+    ) -> Subscription<V>
+    where
+        T: 'static,
+        V: Send + 'static,
+        F: Fn(&T) -> V + Send + Sync + 'static,
+    {
+        let slot = Arc::new(Slot {
+            policy,
+            state: Mutex::new(SlotState {
+                queue: VecDeque::new(),
+                closed: false,
+                waker: None,
+            }),
+            condvar: Condvar::new(),
+        });
+        self.list.lock().push(Box::new(SlotSubscriber {
+            slot: Arc::downgrade(&slot),
+            map,
+        }));
+        Subscription {
+            slot,
+            last_version: version,
+        }
+    }
+}
+
+impl<T> Drop for Subscribers<T> {
+    fn drop(&mut self) {
+        for subscriber in self.list.get_mut().drain(..) {
+            subscriber.close();
+        }
+    }
+}
+
+/// Receiving end of a change subscription
+///
+/// Created by `ThreadShare::subscribe()` or `ThreadShare::subscribe_versions()`.
+/// Dropping it unsubscribes.
+///
+/// ## Example
+///
+/// ```rust
+/// use thread_share::{share, SubscribePolicy};
+/// use std::time::Duration;
+///
+/// let data = share!(String::from("v1"));
+/// let mut changes = data.subscribe(SubscribePolicy::Latest);
+///
+/// data.set(String::from("v2"));
+/// data.set(String::from("v3"));
+///
+/// // Latest-only: the intermediate value was skipped
+/// let change = changes.recv_timeout(Duration::from_secs(1)).unwrap();
+/// assert_eq!(change.value, "v3");
+/// assert_eq!(change.lagged, 1);
+/// ```
+pub struct Subscription<V> {
+    slot: Arc<Slot<V>>,
+    last_version: u64,
+}
+
+impl<V> Subscription<V> {
+    /// Gets the policy this subscription was created with
+    pub fn policy(&self) -> SubscribePolicy {
+        self.slot.policy
+    }
+
+    fn take(&mut self, entry: (u64, V)) -> Change<V> {
+        let (version, value) = entry;
+        let lagged = version.saturating_sub(self.last_version + 1);
+        self.last_version = version;
+        Change {
+            version,
+            value,
+            lagged,
+        }
+    }
+
+    /// Blocks until the next change arrives
+    ///
+    /// ## Returns
+    ///
+    /// `Some(change)`, or `None` once every handle of the share was dropped
+    /// and no buffered changes are left.
+    pub fn recv(&mut self) -> Option<Change<V>> {
+        let mut state = self.slot.state.lock();
+        loop {
+            if let Some(entry) = state.queue.pop_front() {
+                drop(state);
+                return Some(self.take(entry));
+            }
+            if state.closed {
+                return None;
+            }
+            self.slot.condvar.wait(&mut state);
+        }
+    }
+
+    /// Waits for the next change with timeout
+    ///
+    /// ## Arguments
+    ///
+    /// * `timeout` - Maximum time to wait for a change
+    ///
+    /// ## Returns
+    ///
+    /// The next change, `Err(RecvTimeoutError::Timeout)` if none arrived in
+    /// time, or `Err(RecvTimeoutError::Disconnected)` once the share is gone.
+    pub fn recv_timeout(&mut self, timeout: Duration) -> Result<Change<V>, RecvTimeoutError> {
+        let deadline = Instant::now() + timeout;
+        let mut state = self.slot.state.lock();
+        loop {
+            if let Some(entry) = state.queue.pop_front() {
+                drop(state);
+                return Ok(self.take(entry));
+            }
+            if state.closed {
+                return Err(RecvTimeoutError::Disconnected);
+            }
+            if self
+                .slot
+                .condvar
+                .wait_until(&mut state, deadline)
+                .timed_out()
+                && state.queue.is_empty()
+            {
+                return Err(if state.closed {
+                    RecvTimeoutError::Disconnected
+                } else {
+                    RecvTimeoutError::Timeout
+                });
+            }
+        }
+    }
+
+    /// Takes the next change without blocking
+    ///
+    /// ## Returns
+    ///
+    /// The next change, `Err(TryRecvError::Empty)` if none is buffered, or
+    /// `Err(TryRecvError::Disconnected)` once the share is gone.
+    pub fn try_recv(&mut self) -> Result<Change<V>, TryRecvError> {
+        let mut state = self.slot.state.lock();
+        match state.queue.pop_front() {
+            Some(entry) => {
+                drop(state);
+                Ok(self.take(entry))
+            }
+            None if state.closed => Err(TryRecvError::Disconnected),
+            None => Err(TryRecvError::Empty),
+        }
+    }
+
+    /// Converts the subscription into a `std::sync::mpsc::Receiver`
+    ///
+    /// A forwarding thread moves changes from the subscription into a
+    /// rendezvous channel, so the subscription policy still decides what is
+    /// buffered; the forwarding thread holds at most one change in hand. The
+    /// thread exits once the share is gone or the receiver is dropped and
+    /// another change arrives.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::{share, SubscribePolicy};
+    /// use std::time::Duration;
+    ///
+    /// let data = share!(0);
+    /// let receiver = data.subscribe(SubscribePolicy::Queue(8)).into_receiver();
+    ///
+    /// data.set(1);
+    /// let change = receiver.recv_timeout(Duration::from_secs(1)).unwrap();
+    /// assert_eq!(change.value, 1);
+    /// ```
+    pub fn into_receiver(mut self) -> mpsc::Receiver<Change<V>>
+    where
+        V: Send + 'static,
+    {
+        let (sender, receiver) = mpsc::sync_channel(0);
+        thread::Builder::new()
+            .name("thread-share-subscription".to_string())
+            .spawn(move || {
+                while let Some(change) = self.recv() {
+                    if sender.send(change).is_err() {
+                        break;
+                    }
+                }
+            })
+            .expect("Failed to spawn subscription forwarding thread");
+        receiver
+    }
+}
+
+impl<V> Iterator for Subscription<V> {
+    type Item = Change<V>;
+
+    fn next(&mut self) -> Option<Change<V>> {
+        self.recv()
+    }
+}
+
+#[cfg(feature = "async")]
+impl<V> futures_core::Stream for Subscription<V> {
+    type Item = Change<V>;
+
+    fn poll_next(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Change<V>>> {
+        use std::task::Poll;
+
+        let this = self.get_mut();
+        let mut state = this.slot.state.lock();
+        if let Some(entry) = state.queue.pop_front() {
+            drop(state);
+            return Poll::Ready(Some(this.take(entry)));
+        }
+        if state.closed {
+            return Poll::Ready(None);
+        }
+        match &mut state.waker {
+            Some(waker) if waker.will_wake(cx.waker()) => {}
+            waker => *waker = Some(cx.waker().clone()),
+        }
+        Poll::Pending
+    }
+}
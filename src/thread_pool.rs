@@ -251,6 +251,7 @@
 //! ```
 
 use crate::core::ThreadShare;
+use crate::error::{Error, Result};
 use std::any::{Any, TypeId};
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
@@ -363,7 +364,7 @@ impl ThreadManager {
     ///
     /// ## Returns
     ///
-    /// `Ok(())` on success, `Err(String)` if thread spawning fails.
+    /// `Ok(())` on success, `Err(Error::Spawn)` if the operating system fails to spawn the thread.
     ///
     /// ## Example
     ///
@@ -392,7 +393,7 @@ impl ThreadManager {
     ///     Ok(())
     /// }
     /// ```
-    pub fn spawn<F, T>(&self, name: &str, shared_data: ThreadShare<T>, f: F) -> Result<(), String>
+    pub fn spawn<F, T>(&self, name: &str, shared_data: ThreadShare<T>, f: F) -> Result<()>
     where
         F: FnOnce(ThreadShare<T>) + Send + 'static,
         T: Send + Sync + 'static,
@@ -400,9 +401,9 @@ impl ThreadManager {
         let thread_name = name.to_string();
         let thread_data = shared_data.clone();
 
-        let handle = thread::spawn(move || {
+        let handle = spawn_named(name, move || {
             f(thread_data);
-        });
+        })?;
 
         self.threads.lock().unwrap().insert(thread_name, handle);
         Ok(())
@@ -424,13 +425,13 @@ impl ThreadManager {
     ///
     /// ## Returns
     ///
-    /// `Ok(())` on success, `Err(String)` if any thread spawning fails.
+    /// `Ok(())` on success, `Err(Error::Spawn)` if the operating system fails to spawn a thread.
     /// ```
     pub fn spawn_multiple<F, T>(
         &self,
         shared_data: ThreadShare<T>,
         thread_configs: Vec<(&str, F)>,
-    ) -> Result<(), String>
+    ) -> Result<()>
     where
         F: FnOnce(ThreadShare<T>) + Send + Clone + 'static,
         T: Send + Sync + 'static,
@@ -448,7 +449,7 @@ impl ThreadManager {
     ///
     /// ## Returns
     ///
-    /// `Ok(())` when all threads complete successfully, `Err(String)` if any thread fails.
+    /// `Ok(())` when all threads complete successfully, `Err(Error::WorkerPanicked)` if any thread panics.
     ///
     /// ## Example
     ///
@@ -471,16 +472,13 @@ impl ThreadManager {
     ///     Ok(())
     /// }
     /// ```
-    pub fn join_all(&self) -> Result<(), String> {
+    pub fn join_all(&self) -> Result<()> {
         let mut threads = self.threads.lock().unwrap();
         let thread_handles: Vec<_> = threads.drain().collect();
         drop(threads);
 
         for (name, handle) in thread_handles {
-            let result = handle.join();
-            if let Err(e) = result {
-                return Err(format!("Thread '{}' failed: {:?}", name, e));
-            }
+            handle.join().map_err(|payload| Error::panicked(name, payload))?;
         }
         Ok(())
     }
@@ -580,3 +578,20 @@ impl Default for ThreadManager {
         Self::new()
     }
 }
+
+/// Spawns a named thread, reporting spawn failures as `Error::Spawn`
+///
+/// NUL bytes are left out of the thread's name, which the OS cannot store;
+/// `name` is still used as is everywhere else.
+pub(crate) fn spawn_named<F>(name: &str, f: F) -> Result<thread::JoinHandle<()>>
+where
+    F: FnOnce() + Send + 'static,
+{
+    thread::Builder::new()
+        .name(name.replace('\0', ""))
+        .spawn(f)
+        .map_err(|source| Error::Spawn {
+            name: name.to_string(),
+            source,
+        })
+}
@@ -0,0 +1,114 @@
+//! # Timer Module - Shared Background Timer
+//!
+//! Internal building block that runs callbacks at a given instant.
+//!
+//! A single background thread, `thread-share-timer`, serves the whole
+//! process. It is started on first use and sleeps while nothing is
+//! scheduled. Pending entries are kept in a binary heap ordered by due time.
+//!
+//! Used by `ChangedTimeout` to wake its task at the deadline and by the
+//! `Coalesce` and `Debounce` notification policies to publish pending
+//! changes.
+//!
+//! Callbacks run on the timer thread without the timer's lock held, so they
+//! may schedule further callbacks. They should be short: a slow callback
+//! delays every entry due after it.
+
+use parking_lot::{Condvar, Mutex};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::sync::{Once, OnceLock};
+use std::thread;
+use std::time::Instant;
+
+type Callback = Box<dyn FnOnce() + Send>;
+
+struct TimerState {
+    due: BinaryHeap<Reverse<(Instant, u64)>>,
+    callbacks: HashMap<u64, Callback>,
+    next_key: u64,
+}
+
+/// Process-wide timer running callbacks at their due time
+pub(crate) struct Timer {
+    state: Mutex<TimerState>,
+    condvar: Condvar,
+}
+
+/// Returns the timer, starting its thread on first use
+pub(crate) fn timer() -> &'static Timer {
+    static TIMER: OnceLock<Timer> = OnceLock::new();
+    static START: Once = Once::new();
+
+    let timer = TIMER.get_or_init(|| Timer {
+        state: Mutex::new(TimerState {
+            due: BinaryHeap::new(),
+            callbacks: HashMap::new(),
+            next_key: 0,
+        }),
+        condvar: Condvar::new(),
+    });
+    START.call_once(|| {
+        thread::Builder::new()
+            .name("thread-share-timer".to_string())
+            .spawn(move || timer.run())
+            .expect("Failed to spawn thread-share timer thread");
+    });
+    timer
+}
+
+impl Timer {
+    /// Runs `callback` on the timer thread once `due` has passed
+    ///
+    /// Returns a key for `cancel()`.
+    pub(crate) fn schedule(&self, due: Instant, callback: impl FnOnce() + Send + 'static) -> u64 {
+        let mut state = self.state.lock();
+        let key = state.next_key;
+        state.next_key += 1;
+        state.due.push(Reverse((due, key)));
+        state.callbacks.insert(key, Box::new(callback));
+        drop(state);
+
+        self.condvar.notify_one();
+        key
+    }
+
+    /// Drops the callback scheduled under `key` if it has not run yet
+    pub(crate) fn cancel(&self, key: u64) {
+        // The heap entry is discarded once it is due; the callback is
+        // dropped after the lock is released
+        let callback = self.state.lock().callbacks.remove(&key);
+        drop(callback);
+    }
+
+    fn run(&self) {
+        let mut state = self.state.lock();
+        loop {
+            let now = Instant::now();
+            let mut expired = Vec::new();
+            while let Some(&Reverse((due, key))) = state.due.peek() {
+                if due > now {
+                    break;
+                }
+                state.due.pop();
+                if let Some(callback) = state.callbacks.remove(&key) {
+                    expired.push(callback);
+                }
+            }
+
+            if !expired.is_empty() {
+                parking_lot::MutexGuard::unlocked(&mut state, || {
+                    expired.into_iter().for_each(|callback| callback());
+                });
+                continue;
+            }
+
+            match state.due.peek() {
+                Some(&Reverse((due, _))) => {
+                    self.condvar.wait_until(&mut state, due);
+                }
+                None => self.condvar.wait(&mut state),
+            }
+        }
+    }
+}
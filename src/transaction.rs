@@ -0,0 +1,162 @@
+//! # Transaction Module - Atomic Multi-Share Updates
+//!
+//! This module provides `transaction()`, which updates several `ThreadShare`s
+//! as one atomic step.
+//!
+//! ## Overview
+//!
+//! Nesting `write()` calls on two shares deadlocks as soon as another thread
+//! nests them in the opposite order. `transaction()` avoids this by taking
+//! the write locks of all shares in one global order (the address of each
+//! share's lock), whatever order the shares are passed in.
+//!
+//! While the closure runs, no other thread can observe any of the shares, so
+//! invariants spanning several shares always hold. Once the closure returns,
+//! every share commits exactly once: its version is bumped, waiters are woken
+//! and subscribers are notified, and on-change callbacks run after all locks
+//! are released.
+//!
+//! Transactions over 2 to 8 shares are supported, passed as a tuple of
+//! references. Passing the same share twice panics.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use thread_share::{share, transaction};
+//!
+//! let checking = share!(100);
+//! let savings = share!(50);
+//!
+//! // Move money between accounts; nobody ever sees it in both or neither
+//! transaction((&checking, &savings), |(checking, savings)| {
+//!     *checking -= 30;
+//!     *savings += 30;
+//! });
+//!
+//! assert_eq!(checking.get(), 70);
+//! assert_eq!(savings.get(), 80);
+//! ```
+//!
+//! ## Panics
+//!
+//! If the closure panics, all locks are released and no share is notified;
+//! changes made before the panic stay in place, as with `update()`.
+
+use crate::core::ThreadShare;
+use crate::metrics::Tracked;
+use parking_lot::RwLockWriteGuard;
+use std::sync::Arc;
+
+/// Tuple of shares that can be updated together by `transaction()`
+///
+/// Implemented for tuples of 2 to 8 `&ThreadShare<_>` references whose data
+/// types are `'static`.
+pub trait TransactionShares {
+    /// Mutable references to the data of every share, in tuple order
+    type Data<'a>;
+
+    /// Locks every share, runs `f` and commits each share once
+    fn run<R, F>(self, f: F) -> R
+    where
+        F: for<'a> FnOnce(Self::Data<'a>) -> R;
+}
+
+/// Runs `f` with exclusive access to several shares at once
+///
+/// The write locks are acquired in a stable global order, so concurrent
+/// transactions over overlapping shares never deadlock, regardless of the
+/// order the shares are listed in.
+///
+/// ## Arguments
+///
+/// * `shares` - Tuple of 2 to 8 share references
+/// * `f` - Closure receiving a tuple of mutable references to the data
+///
+/// ## Returns
+///
+/// The result of the closure execution.
+///
+/// ## Panics
+///
+/// Panics if the same share is passed more than once.
+///
+/// ## Example
+///
+/// ```rust
+/// use thread_share::{share, transaction};
+/// use std::thread;
+///
+/// let queue = share!(vec![1, 2, 3]);
+/// let processed = share!(Vec::new());
+/// let count = share!(0);
+///
+/// let handle = {
+///     let (queue, processed, count) = (queue.clone(), processed.clone(), count.clone());
+///     thread::spawn(move || {
+///         // Listed in a different order: still deadlock-free
+///         transaction((&count, &processed, &queue), |(count, processed, queue)| {
+///             processed.extend(queue.drain(..));
+///             *count = processed.len();
+///         });
+///     })
+/// };
+///
+/// handle.join().unwrap();
+/// assert!(queue.get().is_empty());
+/// assert_eq!(count.get(), 3);
+/// ```
+pub fn transaction<S, F, R>(shares: S, f: F) -> R
+where
+    S: TransactionShares,
+    F: for<'a> FnOnce(S::Data<'a>) -> R,
+{
+    shares.run(f)
+}
+
+fn lock_order<const N: usize>(mut order: [(usize, usize); N]) -> [(usize, usize); N] {
+    order.sort_unstable();
+    assert!(
+        order.windows(2).all(|pair| pair[0].0 != pair[1].0),
+        "the same share was passed to transaction() more than once"
+    );
+    order
+}
+
+macro_rules! impl_transaction_shares {
+    ($($T:ident $idx:tt),+) => {
+        impl<'s, $($T: 'static),+> TransactionShares for ($(&'s ThreadShare<$T>,)+) {
+            type Data<'a> = ($(&'a mut $T,)+);
+
+            fn run<R, F>(self, f: F) -> R
+            where
+                F: for<'a> FnOnce(Self::Data<'a>) -> R,
+            {
+                let order = lock_order([$((Arc::as_ptr(self.$idx.data()) as usize, $idx)),+]);
+
+                let mut guards: ($(Option<Tracked<'s, RwLockWriteGuard<'s, $T>>>,)+) = ($(None::<Tracked<'s, RwLockWriteGuard<'s, $T>>>,)+);
+                for (_, index) in order {
+                    match index {
+                        $($idx => guards.$idx = Some(self.$idx.write_lock()),)+
+                        _ => unreachable!(),
+                    }
+                }
+
+                let mut guards = ($(guards.$idx.expect("every share is locked"),)+);
+                let result = f(($(&mut *guards.$idx,)+));
+
+                let pending = ($(self.$idx.commit(&guards.$idx),)+);
+                drop(guards);
+                $(pending.$idx.run();)+
+                result
+            }
+        }
+    };
+}
+
+impl_transaction_shares!(T0 0, T1 1);
+impl_transaction_shares!(T0 0, T1 1, T2 2);
+impl_transaction_shares!(T0 0, T1 1, T2 2, T3 3);
+impl_transaction_shares!(T0 0, T1 1, T2 2, T3 3, T4 4);
+impl_transaction_shares!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5);
+impl_transaction_shares!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6);
+impl_transaction_shares!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7);
@@ -0,0 +1,254 @@
+//! # View Module - Live ArcThreadShare-Style Access to a ThreadShare
+//!
+//! This module provides `ArcView<T>`, the handle returned by
+//! `ThreadShare::as_arc()`.
+//!
+//! ## Overview
+//!
+//! An `ArcView<T>` has the same methods as `ArcThreadShare<T>` (`get`,
+//! `set`, `update`, `fetch_update`, `compare_exchange`, `swap`, ...), but no
+//! data of its own: it refers to the data of the `ThreadShare<T>` it was
+//! created from. Every write goes through that share's lock and is a regular
+//! change of the share, so waiters, subscriptions, callbacks and derived
+//! values see it. Writes made through the share are visible in the view.
+//!
+//! Hand it to code written against the `ArcThreadShare<T>` API that should
+//! work on the original data rather than on a copy.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use thread_share::share;
+//! use std::thread;
+//!
+//! let score = share!(0);
+//! let view = score.as_arc();
+//!
+//! thread::spawn(move || {
+//!     view.add(100);
+//! });
+//!
+//! score.wait_for_change_forever();
+//! assert_eq!(score.get(), 100);
+//! ```
+//!
+//! ## Differences from ArcThreadShare<T>
+//!
+//! - **Reads take the read lock** of the share instead of being lock-free
+//! - **Closures run exactly once**, under the write lock, instead of being
+//!   retried on conflict
+//! - **Writes notify** the share's waiters; writes that leave the data
+//!   untouched, like a `fetch_update()` returning `None`, do not
+
+use crate::core::ThreadShare;
+use std::marker::PhantomData;
+use std::mem;
+use std::sync::Arc;
+
+/// Live handle to the data of a `ThreadShare<T>` with the `ArcThreadShare<T>` API
+///
+/// Created with `ThreadShare::as_arc()`. Clones refer to the same data and
+/// keep the share alive.
+///
+/// Like `ArcThreadShare<T>`, a view is only `Send` and `Sync` if `T` is:
+///
+/// ```compile_fail
+/// use thread_share::ThreadShare;
+/// use std::rc::Rc;
+///
+/// fn assert_send<S: Send>(_: S) {}
+///
+/// let data = ThreadShare::new(Rc::new(0));
+/// assert_send(data.as_arc());
+/// ```
+///
+/// ## Example
+///
+/// ```rust
+/// use thread_share::share;
+///
+/// let data = share!(vec![1, 2, 3]);
+/// let view = data.as_arc();
+///
+/// view.update(|v| v.push(4));
+/// assert_eq!(data.get(), vec![1, 2, 3, 4]);
+/// assert_eq!(data.version(), 1);
+///
+/// data.set(vec![]);
+/// assert!(view.get().is_empty());
+/// ```
+pub struct ArcView<T> {
+    share: ThreadShare<T>,
+    /// Derives `Send` and `Sync` from `Arc<T>`, not from `ThreadShare`
+    _data: PhantomData<Arc<T>>,
+}
+
+impl<T> ArcView<T> {
+    /// Gets a copy of the data
+    ///
+    /// See `ArcThreadShare::get()`.
+    pub fn get(&self) -> T
+    where
+        T: Clone,
+    {
+        self.share.get()
+    }
+
+    /// Sets new data and notifies the share's waiters
+    ///
+    /// See `ArcThreadShare::set()`.
+    pub fn set(&self, new_data: T) {
+        self.share.set(new_data);
+    }
+
+    /// Reads the data through a closure
+    ///
+    /// See `ArcThreadShare::read()`. The read lock is held while the closure
+    /// runs.
+    pub fn read<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&T) -> R,
+    {
+        self.share.read(f)
+    }
+
+    /// Writes the data, notifies the share's waiters and returns a result
+    ///
+    /// See `ArcThreadShare::write()`. The closure runs once, in place.
+    pub fn write<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        self.share.write(f)
+    }
+
+    /// Updates the data and notifies the share's waiters
+    ///
+    /// See `ArcThreadShare::update()`. The closure runs once, in place.
+    pub fn update<F>(&self, f: F)
+    where
+        F: FnOnce(&mut T),
+    {
+        self.share.update(f);
+    }
+
+    /// Increments the data
+    ///
+    /// See `ArcThreadShare::increment()`.
+    pub fn increment(&self)
+    where
+        T: Copy + std::ops::Add<Output = T> + std::ops::AddAssign + From<u8> + 'static,
+    {
+        self.share.update(|x| *x += T::from(1u8));
+    }
+
+    /// Adds a value to the data
+    ///
+    /// See `ArcThreadShare::add()`.
+    pub fn add(&self, value: T)
+    where
+        T: Copy + std::ops::Add<Output = T> + std::ops::AddAssign + 'static,
+    {
+        self.share.update(|x| *x += value);
+    }
+
+    /// Replaces the data with a value computed from it
+    ///
+    /// See `ArcThreadShare::fetch_update()`. The function is called once,
+    /// while other writers are locked out; readers are not blocked until a
+    /// new value is written. Nothing is notified if it returns `None`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::share;
+    ///
+    /// let peak = share!(10);
+    /// let view = peak.as_arc();
+    ///
+    /// assert_eq!(view.fetch_update(|&max| (5 > max).then_some(5)), Err(10));
+    /// assert_eq!(peak.version(), 0);
+    /// assert_eq!(view.fetch_update(|&max| (25 > max).then_some(25)), Ok(10));
+    /// assert_eq!(peak.version(), 1);
+    /// ```
+    pub fn fetch_update<F>(&self, f: F) -> Result<T, T>
+    where
+        F: FnOnce(&T) -> Option<T>,
+        T: Clone,
+    {
+        let data = self.share.upgradable_read();
+        match f(&data) {
+            Some(new_data) => Ok(mem::replace(&mut *data.upgrade(), new_data)),
+            None => Err(T::clone(&data)),
+        }
+    }
+
+    /// Sets new data only if the current data equals `current`
+    ///
+    /// See `ArcThreadShare::compare_exchange()`.
+    pub fn compare_exchange(&self, current: &T, new_data: T) -> Result<T, T>
+    where
+        T: Clone + PartialEq,
+    {
+        self.fetch_update(|data| (data == current).then_some(new_data))
+    }
+
+    /// Sets new data and returns the previous data
+    ///
+    /// See `ArcThreadShare::swap()`.
+    pub fn swap(&self, new_data: T) -> T {
+        self.share.replace(new_data)
+    }
+
+    /// Gets the share this view refers to
+    ///
+    /// Useful for waiting, subscriptions and the other `ThreadShare<T>`
+    /// methods.
+    pub fn as_share(&self) -> &ThreadShare<T> {
+        &self.share
+    }
+}
+
+impl<T> Clone for ArcView<T> {
+    /// Creates another view of the same data
+    fn clone(&self) -> Self {
+        Self {
+            share: self.share.clone(),
+            _data: PhantomData,
+        }
+    }
+}
+
+impl<T> ThreadShare<T> {
+    /// Gets a live handle to the data with the `ArcThreadShare<T>` API
+    ///
+    /// The returned `ArcView<T>` reads and writes this share's data: its
+    /// writes go through this share's lock and notify its waiters,
+    /// subscribers and callbacks, and it sees every change made here.
+    ///
+    /// For an independent, lock-free copy of the current data, use
+    /// `ArcThreadShare::new(share.get())` instead.
+    ///
+    /// ## Returns
+    ///
+    /// An `ArcView<T>` that keeps this share alive.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use thread_share::ThreadShare;
+    ///
+    /// let data = ThreadShare::new(vec![1, 2, 3]);
+    /// let view = data.as_arc();
+    ///
+    /// // Changes the original data
+    /// assert_eq!(view.swap(vec![4]), vec![1, 2, 3]);
+    /// assert_eq!(data.get(), vec![4]);
+    /// ```
+    pub fn as_arc(&self) -> ArcView<T> {
+        ArcView {
+            share: self.clone(),
+            _data: PhantomData,
+        }
+    }
+}
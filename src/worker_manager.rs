@@ -149,7 +149,8 @@
 //!
 //! ## Error Handling
 //!
-//! All methods return `Result<T, String>` for proper error handling:
+//! All fallible methods return `thread_share::Result<T>`, whose `Error` says
+//! what went wrong:
 //!
 //! ```rust
 //! use thread_share::{enhanced_share, spawn_workers};
@@ -215,6 +216,7 @@
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use std::thread;
+use crate::error::{Error, Result};
 
 /// Worker Manager for controlling spawned threads
 ///
@@ -382,7 +384,7 @@ impl WorkerManager {
     ///
     /// ## Returns
     ///
-    /// `Ok(())` on success, `Err(String)` if a worker with the same name already exists.
+    /// `Ok(())` on success, `Err(Error::WorkerExists)` if a worker with the same name already exists.
     ///
     /// ## Example
     ///
@@ -403,11 +405,13 @@ impl WorkerManager {
     /// // Add it to the manager
     /// manager.add_worker("manual_worker", handle).expect("Failed to add worker");
     /// ```
-    pub fn add_worker(&self, name: &str, handle: thread::JoinHandle<()>) -> Result<(), String> {
+    pub fn add_worker(&self, name: &str, handle: thread::JoinHandle<()>) -> Result<()> {
         let mut threads = self.threads.lock().unwrap();
         
         if threads.contains_key(name) {
-            return Err(format!("Worker '{}' already exists", name));
+            return Err(Error::WorkerExists {
+                name: name.to_string(),
+            });
         }
         
         threads.insert(name.to_string(), handle);
@@ -426,7 +430,7 @@ impl WorkerManager {
     ///
     /// ## Returns
     ///
-    /// `Ok(())` on success, `Err(String)` if the worker doesn't exist
+    /// `Ok(())` on success
     ///
     /// ## Example
     ///
@@ -441,7 +445,7 @@ impl WorkerManager {
     /// // Pause the worker
     /// manager.pause_worker("worker").expect("Failed to pause");
     /// ```
-    pub fn pause_worker(&self, name: &str) -> Result<(), String> {
+    pub fn pause_worker(&self, name: &str) -> Result<()> {
         let mut paused = self.paused_workers.lock().unwrap();
         paused.insert(name.to_string(), true);
         println!("Worker '{}' marked for pause (implementation pending)", name);
@@ -456,7 +460,7 @@ impl WorkerManager {
     ///
     /// ## Returns
     ///
-    /// `Ok(())` on success, `Err(String)` if the worker doesn't exist
+    /// `Ok(())` on success
     ///
     /// ## Example
     ///
@@ -472,7 +476,7 @@ impl WorkerManager {
     /// manager.pause_worker("worker").expect("Failed to pause");
     /// manager.resume_worker("worker").expect("Failed to resume");
     /// ```
-    pub fn resume_worker(&self, name: &str) -> Result<(), String> {
+    pub fn resume_worker(&self, name: &str) -> Result<()> {
         let mut paused = self.paused_workers.lock().unwrap();
         paused.remove(name);
         println!("Worker '{}' resumed", name);
@@ -491,7 +495,7 @@ impl WorkerManager {
     ///
     /// ## Returns
     ///
-    /// `Ok(())` on success, `Err(String)` if the worker doesn't exist
+    /// `Ok(())` on success, `Err(Error::WorkerNotFound)` if the worker is not tracked
     ///
     /// ## Example
     ///
@@ -506,13 +510,15 @@ impl WorkerManager {
     /// // Remove from tracking
     /// manager.remove_worker("worker").expect("Failed to remove");
     /// ```
-    pub fn remove_worker(&self, name: &str) -> Result<(), String> {
+    pub fn remove_worker(&self, name: &str) -> Result<()> {
         let mut threads = self.threads.lock().unwrap();
         if threads.remove(name).is_some() {
             println!("Worker '{}' removed from tracking", name);
             Ok(())
         } else {
-            Err(format!("Worker '{}' not found", name))
+            Err(Error::WorkerNotFound {
+                name: name.to_string(),
+            })
         }
     }
 
@@ -540,7 +546,7 @@ impl WorkerManager {
     /// // Remove all workers from tracking
     /// manager.remove_all_workers().expect("Failed to remove all workers");
     /// ```
-    pub fn remove_all_workers(&self) -> Result<(), String> {
+    pub fn remove_all_workers(&self) -> Result<()> {
         let mut threads = self.threads.lock().unwrap();
         let count = threads.len();
         threads.clear();
@@ -642,7 +648,7 @@ impl WorkerManager {
     ///
     /// ## Returns
     ///
-    /// `Ok(())` if all workers completed successfully, `Err(String)` if any worker failed
+    /// `Ok(())` if all workers completed successfully, `Err(Error::WorkerPanicked)` if any worker panicked
     ///
     /// ## Example
     ///
@@ -665,21 +671,24 @@ impl WorkerManager {
     /// // All workers are now completed and removed
     /// assert_eq!(manager.active_workers(), 0);
     /// ```
-    pub fn join_all(&self) -> Result<(), String> {
+    pub fn join_all(&self) -> Result<()> {
         let mut threads = self.threads.lock().unwrap();
         let thread_handles: Vec<_> = threads.drain().collect();
         drop(threads);
 
         for (name, handle) in thread_handles {
-            let result = handle.join();
-            if let Err(e) = result {
-                return Err(format!("Worker '{}' failed: {:?}", name, e));
-            }
+            handle.join().map_err(|payload| Error::panicked(name, payload))?;
         }
         Ok(())
     }
 }
 
+impl Default for WorkerManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Clone for WorkerManager {
     /// Creates a clone of the WorkerManager
     ///
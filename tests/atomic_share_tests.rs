@@ -0,0 +1,137 @@
+use std::ptr;
+use std::thread;
+use thread_share::{atomic_share, AtomicShare};
+
+#[test]
+fn test_atomic_share_get_set_swap() {
+    let share = AtomicShare::new(1i16);
+
+    share.set(2);
+    assert_eq!(share.get(), 2);
+    assert_eq!(share.swap(3), 2);
+    assert_eq!(share.get(), 3);
+}
+
+#[test]
+fn test_atomic_share_clones_share_value() {
+    let share = atomic_share!(0usize);
+    let clone = share.clone();
+
+    clone.add(5);
+    assert_eq!(share.get(), 5);
+}
+
+#[test]
+fn test_atomic_share_arithmetic() {
+    let share = AtomicShare::new(10i64);
+
+    assert_eq!(share.fetch_add(5), 10);
+    assert_eq!(share.fetch_sub(20), 15);
+    assert_eq!(share.fetch_max(3), -5);
+    assert_eq!(share.fetch_min(-7), 3);
+    share.increment();
+    share.decrement();
+    share.decrement();
+    assert_eq!(share.get(), -8);
+}
+
+#[test]
+fn test_atomic_share_wraps_on_overflow() {
+    let share = AtomicShare::new(u8::MAX);
+
+    share.increment();
+    assert_eq!(share.get(), 0);
+}
+
+#[test]
+fn test_atomic_share_bitwise() {
+    let share = AtomicShare::new(0b1100u32);
+
+    assert_eq!(share.fetch_or(0b0011), 0b1100);
+    assert_eq!(share.fetch_and(0b0110), 0b1111);
+    assert_eq!(share.fetch_xor(0b0101), 0b0110);
+    assert_eq!(share.get(), 0b0011);
+}
+
+#[test]
+fn test_atomic_share_bool() {
+    let flag = atomic_share!(false);
+
+    assert_eq!(flag.compare_exchange(false, true), Ok(false));
+    assert_eq!(flag.compare_exchange(false, true), Err(true));
+    assert!(flag.fetch_xor(true));
+    assert!(!flag.get());
+}
+
+#[test]
+fn test_atomic_share_floats() {
+    let share = AtomicShare::new(1.5f64);
+
+    assert_eq!(share.fetch_add(1.0), 1.5);
+    assert_eq!(share.fetch_max(10.0), 2.5);
+    assert_eq!(share.fetch_min(-1.0), 10.0);
+    share.increment();
+    assert_eq!(share.get(), 0.0);
+
+    let single = AtomicShare::new(0.25f32);
+    assert_eq!(single.swap(0.5), 0.25);
+    assert_eq!(single.compare_exchange(0.5, 1.0), Ok(0.5));
+    assert_eq!(single.get(), 1.0);
+}
+
+#[test]
+fn test_atomic_share_float_compare_uses_bits() {
+    let share = AtomicShare::new(0.0f32);
+
+    assert_eq!(share.compare_exchange(-0.0, 1.0), Err(0.0));
+    share.set(f32::NAN);
+    assert!(share.compare_exchange(f32::NAN, 1.0).is_ok());
+    assert_eq!(share.get(), 1.0);
+}
+
+#[test]
+fn test_atomic_share_pointer() {
+    let mut first = 1;
+    let mut second = 2;
+    let share = AtomicShare::new(ptr::null_mut::<i32>());
+
+    assert!(share.compare_exchange(ptr::null_mut(), &mut first).is_ok());
+    assert_eq!(share.swap(&mut second), &mut first as *mut i32);
+    assert_eq!(unsafe { *share.get() }, 2);
+}
+
+#[test]
+fn test_atomic_share_fetch_update() {
+    let share = AtomicShare::new(7u32);
+
+    assert_eq!(share.fetch_update(|x| Some(x * 3)), Ok(7));
+    assert_eq!(share.fetch_update(|x| (x < 10).then_some(0)), Err(21));
+    assert_eq!(share.get(), 21);
+}
+
+#[test]
+fn test_atomic_share_concurrent_counters() {
+    let count = atomic_share!(0u64);
+    let total = atomic_share!(0.0f64);
+    let peak = atomic_share!(0i32);
+
+    let handles: Vec<_> = (0..8)
+        .map(|i| {
+            let (count, total, peak) = (count.clone(), total.clone(), peak.clone());
+            thread::spawn(move || {
+                for j in 0..1000 {
+                    count.increment();
+                    total.fetch_add(0.5);
+                    peak.fetch_max(i * 1000 + j);
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    assert_eq!(count.get(), 8000);
+    assert_eq!(total.get(), 4000.0);
+    assert_eq!(peak.get(), 7999);
+}
@@ -0,0 +1,182 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use thread_share::{CallbackMode, enhanced_share, share};
+
+#[test]
+fn test_on_change_receives_old_version_and_value() {
+    let data = share!(0);
+    let log = Arc::new(Mutex::new(Vec::new()));
+
+    let entries = log.clone();
+    let _guard = data.on_change(move |old_version, value| {
+        entries.lock().unwrap().push((old_version, *value));
+    });
+
+    data.set(1);
+    data.update(|x| *x += 1);
+    data.write(|x| *x *= 10);
+
+    assert_eq!(*log.lock().unwrap(), vec![(0, 1), (1, 2), (2, 20)]);
+}
+
+#[test]
+fn test_on_change_guard_drop_unregisters() {
+    let data = share!(0);
+    let calls = Arc::new(AtomicUsize::new(0));
+
+    let counter = calls.clone();
+    let guard = data.on_change(move |_, _| {
+        counter.fetch_add(1, Ordering::SeqCst);
+    });
+
+    data.set(1);
+    drop(guard);
+    data.set(2);
+
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_on_change_callback_can_access_share() {
+    let data = share!(0);
+    let mirror = share!(0);
+
+    // Reading and writing the share from its own callback must not deadlock
+    let source = data.clone();
+    let target = mirror.clone();
+    let _guard = data.on_change(move |_, value| {
+        assert_eq!(source.get(), *value);
+        if *value < 3 {
+            source.set(*value + 1);
+        }
+        target.set(*value);
+    });
+
+    data.set(1);
+    assert_eq!(data.get(), 3);
+    assert_eq!(mirror.get(), 1);
+}
+
+#[test]
+fn test_on_change_from_multiple_threads() {
+    let data = share!(0);
+    let calls = Arc::new(AtomicUsize::new(0));
+
+    let counter = calls.clone();
+    let _guard = data.on_change(move |_, _| {
+        counter.fetch_add(1, Ordering::SeqCst);
+    });
+
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let data = data.clone();
+            thread::spawn(move || {
+                for _ in 0..100 {
+                    data.update(|x| *x += 1);
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    assert_eq!(data.get(), 400);
+    assert_eq!(calls.load(Ordering::SeqCst), 400);
+}
+
+#[test]
+fn test_on_change_thread_mode_preserves_order() {
+    let data = share!(0);
+    let (sender, receiver) = mpsc::channel();
+
+    let _guard = data.on_change_with(CallbackMode::Thread, move |old_version, value: &i32| {
+        sender.send((old_version, *value)).unwrap();
+    });
+
+    let writer = data.clone();
+    let handle = thread::spawn(move || {
+        for i in 1..=50 {
+            writer.set(i);
+        }
+    });
+    handle.join().unwrap();
+
+    for i in 1..=50 {
+        let (old_version, value) = receiver.recv_timeout(Duration::from_secs(1)).unwrap();
+        assert_eq!(old_version, i as u64 - 1);
+        assert_eq!(value, i);
+    }
+}
+
+#[test]
+fn test_on_change_thread_mode_does_not_block_writer() {
+    let data = share!(0);
+    let (sender, receiver) = mpsc::channel();
+
+    let _guard = data.on_change_with(CallbackMode::Thread, move |_, value: &i32| {
+        thread::sleep(Duration::from_millis(50));
+        sender.send(*value).unwrap();
+    });
+
+    let start = std::time::Instant::now();
+    data.set(1);
+    data.set(2);
+    assert!(start.elapsed() < Duration::from_millis(50));
+
+    assert_eq!(receiver.recv_timeout(Duration::from_secs(1)).unwrap(), 1);
+    assert_eq!(receiver.recv_timeout(Duration::from_secs(1)).unwrap(), 2);
+}
+
+#[test]
+fn test_on_change_thread_mode_guard_drop_stops_thread() {
+    let data = share!(0);
+    let (sender, receiver) = mpsc::channel();
+
+    let guard = data.on_change_with(CallbackMode::Thread, move |_, value: &i32| {
+        sender.send(*value).unwrap();
+    });
+
+    data.set(1);
+    drop(guard);
+    data.set(2);
+
+    assert_eq!(receiver.recv_timeout(Duration::from_secs(1)).unwrap(), 1);
+    // The callback (and its sender) is dropped once the thread exits
+    assert_eq!(
+        receiver.recv_timeout(Duration::from_secs(1)).unwrap_err(),
+        mpsc::RecvTimeoutError::Disconnected
+    );
+}
+
+#[test]
+fn test_guard_outliving_share() {
+    let data = share!(0);
+    let guard = data.on_change(|_, _| {});
+    drop(data);
+    drop(guard);
+}
+
+#[test]
+fn test_enhanced_on_change() {
+    let data = enhanced_share!(0);
+    let calls = Arc::new(AtomicUsize::new(0));
+
+    let counter = calls.clone();
+    let _guard = data.on_change(move |_, _| {
+        counter.fetch_add(1, Ordering::SeqCst);
+    });
+
+    data.spawn("writer", |data| {
+        for i in 1..=3 {
+            data.set(i);
+        }
+    })
+    .expect("Failed to spawn writer");
+    data.join_all().expect("Failed to join");
+
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+}
@@ -0,0 +1,155 @@
+use std::thread;
+use std::time::Duration;
+use thread_share::{enhanced_share, share};
+
+#[tokio::test]
+async fn test_changed_wakes_on_set() {
+    let data = share!(0);
+    let data_clone = data.clone();
+
+    let handle = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        data_clone.set(42);
+    });
+
+    let version = data.changed().await;
+    assert_eq!(version, 1);
+    assert_eq!(data.get(), 42);
+
+    handle.join().unwrap();
+}
+
+#[tokio::test]
+async fn test_changed_wakes_on_update_and_write() {
+    let data = share!(vec![1, 2, 3]);
+
+    let writer = data.clone();
+    tokio::spawn(async move {
+        writer.update(|v| v.push(4));
+    });
+    data.changed().await;
+    assert_eq!(data.get(), vec![1, 2, 3, 4]);
+
+    let writer = data.clone();
+    tokio::spawn(async move {
+        writer.write(|v| v.clear());
+    });
+    data.changed().await;
+    assert!(data.get().is_empty());
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_changed_does_not_block_executor() {
+    let data = share!(0);
+    let writer = data.clone();
+
+    // On a single-threaded runtime this only completes if `changed()` yields
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        writer.set(7);
+    });
+
+    data.changed().await;
+    assert_eq!(data.get(), 7);
+}
+
+#[tokio::test]
+async fn test_changed_since_resolves_immediately_if_moved_on() {
+    let data = share!(0);
+    let seen = data.version();
+
+    data.set(1);
+    data.set(2);
+
+    assert_eq!(data.changed_since(seen).await, 2);
+}
+
+#[tokio::test]
+async fn test_changed_timeout() {
+    let data = share!(0);
+
+    let result = data.changed_timeout(Duration::from_millis(20)).await;
+    assert_eq!(result, None);
+
+    let writer = data.clone();
+    let handle = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(20));
+        writer.set(1);
+    });
+
+    let result = data.changed_timeout(Duration::from_secs(2)).await;
+    assert_eq!(result, Some(1));
+
+    handle.join().unwrap();
+}
+
+#[tokio::test]
+async fn test_changed_is_cancellation_safe() {
+    let data = share!(0);
+
+    // Cancel a pending wait
+    let cancelled = tokio::time::timeout(Duration::from_millis(10), data.changed()).await;
+    assert!(cancelled.is_err());
+
+    // The change made afterwards is still reported
+    let writer = data.clone();
+    writer.set(5);
+    assert_eq!(data.changed().await, 1);
+
+    // A cancelled wait after the change does not consume it either
+    writer.set(6);
+    drop(data.changed());
+    assert_eq!(data.changed().await, 2);
+    assert_eq!(data.get(), 6);
+}
+
+#[tokio::test]
+async fn test_changed_many_waiters() {
+    let data = share!(0);
+
+    let waiters: Vec<_> = (0..8)
+        .map(|_| {
+            let data = data.clone();
+            tokio::spawn(async move { data.changed().await })
+        })
+        .collect();
+
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    data.set(1);
+
+    for waiter in waiters {
+        assert_eq!(waiter.await.unwrap(), 1);
+    }
+}
+
+#[async_std::test]
+async fn test_changed_with_async_std() {
+    let data = share!(String::from("hello"));
+    let writer = data.clone();
+
+    async_std::task::spawn(async move {
+        async_std::task::sleep(Duration::from_millis(20)).await;
+        writer.update(|s| s.push_str(" world"));
+    });
+
+    data.changed().await;
+    assert_eq!(data.get(), "hello world");
+
+    assert_eq!(data.changed_timeout(Duration::from_millis(10)).await, None);
+}
+
+#[tokio::test]
+async fn test_enhanced_changed() {
+    let data = enhanced_share!(0);
+
+    data.spawn("writer", |data| {
+        thread::sleep(Duration::from_millis(20));
+        data.set(3);
+    })
+    .expect("Failed to spawn writer");
+
+    data.changed().await;
+    assert_eq!(data.get(), 3);
+
+    data.join_all().expect("Failed to join");
+}
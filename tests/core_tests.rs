@@ -1,6 +1,6 @@
 use std::thread;
 use std::time::Duration;
-use thread_share::{share, simple_share, ArcThreadShare, SimpleShare, ThreadShare};
+use thread_share::{share, simple_share, Error, SimpleShare, ThreadShare};
 
 #[test]
 fn test_thread_share_new() {
@@ -106,6 +106,143 @@ fn test_thread_share_wait_for_change_forever() {
     handle.join().unwrap();
 }
 
+#[test]
+fn test_thread_share_version() {
+    let share = share!(0);
+    assert_eq!(share.version(), 0);
+
+    share.set(1);
+    assert_eq!(share.version(), 1);
+
+    share.update(|x| *x += 1);
+    assert_eq!(share.version(), 2);
+
+    // write() notifies as well
+    let doubled = share.write(|x| {
+        *x *= 2;
+        *x
+    });
+    assert_eq!(doubled, 4);
+    assert_eq!(share.version(), 3);
+
+    // Reads never bump the version
+    share.read(|x| assert_eq!(*x, 4));
+    assert_eq!(share.get(), 4);
+    assert_eq!(share.version(), 3);
+}
+
+#[test]
+fn test_thread_share_change_before_wait_is_not_lost() {
+    let data = share!(0);
+    let data_clone = data.clone();
+
+    // The change lands before anybody starts waiting
+    thread::spawn(move || data_clone.set(42)).join().unwrap();
+
+    let timed_out = data.wait_for_change(Duration::from_millis(10));
+    assert!(!timed_out);
+    assert_eq!(data.get(), 42);
+
+    // Already observed, so the next wait has nothing to report
+    let timed_out = data.wait_for_change(Duration::from_millis(10));
+    assert!(timed_out);
+}
+
+#[test]
+fn test_thread_share_wait_for_change_since() {
+    let data = share!(0);
+    let seen = data.version();
+
+    data.set(1);
+    assert_eq!(
+        data.wait_for_change_since(seen, Duration::from_millis(10)),
+        Some(seen + 1)
+    );
+    assert_eq!(
+        data.wait_for_change_since(seen + 1, Duration::from_millis(10)),
+        None
+    );
+
+    let data_clone = data.clone();
+    let version = data.version();
+    let handle = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(20));
+        data_clone.write(|x| *x = 2);
+    });
+
+    assert!(data.wait_for_change_since_forever(version) > version);
+    assert_eq!(data.get(), 2);
+
+    handle.join().unwrap();
+}
+
+#[test]
+fn test_thread_share_no_lost_wakeups_under_load() {
+    let data = share!(0u32);
+    let writers: Vec<_> = (0..4)
+        .map(|_| {
+            let data = data.clone();
+            thread::spawn(move || {
+                for _ in 0..250 {
+                    data.update(|x| *x += 1);
+                }
+            })
+        })
+        .collect();
+
+    // Every wait either observes a newer version or times out once all writers are done
+    let mut seen = 0;
+    while let Some(version) = data.wait_for_change_since(seen, Duration::from_millis(500)) {
+        assert!(version > seen);
+        seen = version;
+        if seen == 1000 {
+            break;
+        }
+    }
+
+    for writer in writers {
+        writer.join().unwrap();
+    }
+    assert_eq!(seen, 1000);
+    assert_eq!(data.get(), 1000);
+}
+
+#[test]
+fn test_thread_share_wait_until() {
+    let data = share!(Vec::<u32>::new());
+    let data_clone = data.clone();
+
+    let handle = thread::spawn(move || {
+        for i in 0..5 {
+            thread::sleep(Duration::from_millis(5));
+            data_clone.update(|v| v.push(i));
+        }
+    });
+
+    let matched = data
+        .wait_until(|v| v.len() >= 3, Duration::from_secs(2))
+        .expect("condition should be reached");
+    assert!(matched.len() >= 3);
+    assert_eq!(&matched[..3], &[0, 1, 2]);
+
+    let all = data.wait_until_forever(|v| v.len() == 5);
+    assert_eq!(all, vec![0, 1, 2, 3, 4]);
+
+    handle.join().unwrap();
+}
+
+#[test]
+fn test_thread_share_wait_until_already_satisfied_and_timeout() {
+    let data = share!(7);
+
+    // Satisfied right away: no waiting
+    assert_eq!(data.wait_until(|x| *x == 7, Duration::from_millis(0)).unwrap(), 7);
+
+    // Never satisfied: times out
+    let result = data.wait_until(|x| *x == 8, Duration::from_millis(20));
+    assert!(matches!(result, Err(Error::Timeout { .. })));
+}
+
 #[test]
 fn test_thread_share_clone() {
     let original = share!(String::from("original"));
@@ -132,30 +269,20 @@ fn test_thread_share_clone() {
 #[test]
 fn test_thread_share_as_arc() {
     let share = share!(42);
-    let arc_data = share.as_arc();
+    let view = share.as_arc();
 
     // Verify we can still use the original
     assert_eq!(share.get(), 42);
-
-    // Verify the Arc contains the same initial data using ArcThreadShare
-    let thread_share = ArcThreadShare::from_arc(arc_data.clone());
-    let value = thread_share.get();
-    assert_eq!(value, 42);
+    assert_eq!(view.get(), 42);
 
     // Update through original
     share.set(100);
+    assert_eq!(view.get(), 100);
 
-    // Verify Arc still has the old value (as_arc creates independent copy)
-    let value = thread_share.get();
-    assert_eq!(value, 42);
-
-    // Update through ArcThreadShare
-    thread_share.set(200);
-    let value = thread_share.get();
-    assert_eq!(value, 200);
-
-    // Original share is unchanged
-    assert_eq!(share.get(), 100);
+    // Update through the view
+    view.set(200);
+    assert_eq!(share.get(), 200);
+    assert_eq!(share.version(), 2);
 }
 
 #[test]
@@ -235,7 +362,7 @@ fn test_custom_struct() {
     let original = test_data.get();
     assert_eq!(original.value, 10);
     assert_eq!(original.text, "test");
-    assert_eq!(original.flag, false);
+    assert!(!original.flag);
 
     handle.join().unwrap();
 
@@ -243,7 +370,7 @@ fn test_custom_struct() {
     let updated = test_data.get();
     assert_eq!(updated.value, 20);
     assert_eq!(updated.text, "updated");
-    assert_eq!(updated.flag, true);
+    assert!(updated.flag);
 }
 
 #[test]
@@ -387,3 +514,233 @@ fn test_numeric_operations() {
     let is_even = share.read(|n| n % 2 == 0);
     assert!(is_even);
 }
+
+#[test]
+fn test_thread_share_update_atomic_commits_on_success() {
+    let data = share!(vec![1, 2, 3]);
+    data.update_atomic(|v| v.push(4));
+
+    assert_eq!(data.get(), vec![1, 2, 3, 4]);
+    assert_eq!(data.version(), 1);
+}
+
+#[test]
+fn test_thread_share_update_atomic_rolls_back_on_panic() {
+    let data = share!(vec![1, 2, 3]);
+    let writer = data.clone();
+
+    let result = thread::spawn(move || {
+        writer.update_atomic(|v| {
+            v.clear();
+            panic!("failed halfway");
+        });
+    })
+    .join();
+
+    assert!(result.is_err());
+    assert_eq!(data.get(), vec![1, 2, 3]);
+    assert_eq!(data.version(), 0);
+
+    // The lock was released and the share is still usable
+    data.update(|v| v.push(4));
+    assert_eq!(data.get(), vec![1, 2, 3, 4]);
+}
+
+#[test]
+fn test_thread_share_update_checked() {
+    let balance = share!(100);
+
+    let ok: Result<i32, String> = balance.update_checked(|b| {
+        *b -= 30;
+        Ok(*b)
+    });
+    assert_eq!(ok, Ok(70));
+    assert_eq!(balance.version(), 1);
+
+    let err: Result<i32, String> = balance.update_checked(|b| {
+        *b -= 100;
+        if *b < 0 {
+            return Err(format!("would go negative: {}", b));
+        }
+        Ok(*b)
+    });
+    assert_eq!(err, Err("would go negative: -30".to_string()));
+    assert_eq!(balance.get(), 70);
+    assert_eq!(balance.version(), 1);
+}
+
+#[test]
+fn test_thread_share_update_checked_err_does_not_wake_waiters() {
+    let data = share!(0);
+    let waiter = data.clone();
+
+    let handle = thread::spawn(move || waiter.wait_for_change(Duration::from_millis(100)));
+
+    thread::sleep(Duration::from_millis(10));
+    let _: Result<(), ()> = data.update_checked(|x| {
+        *x = 1;
+        Err(())
+    });
+
+    // Timed out: the rejected change was never published
+    assert!(handle.join().unwrap());
+    assert_eq!(data.get(), 0);
+}
+
+#[test]
+fn test_thread_share_try_read_try_write() {
+    let data = share!(1);
+
+    assert_eq!(data.try_read(|x| *x).unwrap(), 1);
+    data.try_write(|x| *x = 2).unwrap();
+    assert_eq!(data.get(), 2);
+    assert_eq!(data.version(), 1);
+
+    // A reader blocks writers but not other readers
+    data.read(|_| {
+        assert!(matches!(data.try_write(|x| *x = 3), Err(Error::WouldBlock)));
+        assert_eq!(data.try_read(|x| *x).unwrap(), 2);
+    });
+    assert_eq!(data.get(), 2);
+    assert_eq!(data.version(), 1);
+}
+
+#[test]
+fn test_thread_share_try_update_non_blocking() {
+    let data = share!(vec![1]);
+
+    data.try_update(|v| v.push(2)).unwrap();
+    assert_eq!(data.get(), vec![1, 2]);
+    assert_eq!(data.version(), 1);
+
+    // Gives up right away while a reader holds the lock
+    data.read(|_| {
+        assert!(matches!(data.try_update(|v| v.push(3)), Err(Error::WouldBlock)));
+    });
+    assert_eq!(data.get(), vec![1, 2]);
+    assert_eq!(data.version(), 1);
+}
+
+#[test]
+fn test_thread_share_try_read_would_block() {
+    let data = share!(0);
+    let writer = data.clone();
+    let (locked_tx, locked_rx) = std::sync::mpsc::channel();
+    let (release_tx, release_rx) = std::sync::mpsc::channel::<()>();
+
+    let handle = thread::spawn(move || {
+        writer.write(|x| {
+            locked_tx.send(()).unwrap();
+            release_rx.recv().unwrap();
+            *x = 1;
+        });
+    });
+
+    locked_rx.recv().unwrap();
+    assert!(matches!(data.try_read(|x| *x), Err(Error::WouldBlock)));
+    assert!(matches!(
+        data.read_for(Duration::from_millis(20), |x| *x),
+        Err(Error::Timeout { .. })
+    ));
+
+    release_tx.send(()).unwrap();
+    handle.join().unwrap();
+    assert_eq!(data.read_for(Duration::from_millis(20), |x| *x).unwrap(), 1);
+}
+
+#[test]
+fn test_thread_share_write_for_timeout() {
+    let data = share!(0);
+
+    let result = data.read(|_| data.write_for(Duration::from_millis(20), |x| *x = 1));
+    match result {
+        Err(Error::Timeout { timeout }) => assert_eq!(timeout, Duration::from_millis(20)),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert_eq!(data.get(), 0);
+    assert_eq!(data.version(), 0);
+}
+
+#[test]
+fn test_thread_share_update_for_notifies() {
+    let data = share!(0);
+    let waiter = data.clone();
+
+    let handle = thread::spawn(move || waiter.wait_for_change(Duration::from_secs(5)));
+
+    thread::sleep(Duration::from_millis(10));
+    data.update_for(Duration::from_millis(100), |x| *x += 5).unwrap();
+
+    assert!(!handle.join().unwrap());
+    assert_eq!(data.get(), 5);
+}
+
+#[test]
+fn test_thread_share_compare_and_set() {
+    let data = share!(1);
+
+    assert!(!data.compare_and_set(&0, 5));
+    assert_eq!(data.get(), 1);
+    assert_eq!(data.version(), 0);
+
+    assert!(data.compare_and_set(&1, 5));
+    assert_eq!(data.get(), 5);
+    assert_eq!(data.version(), 1);
+}
+
+#[test]
+fn test_thread_share_compare_and_set_concurrent() {
+    let data = share!(0);
+
+    let handles: Vec<_> = (0..8)
+        .map(|_| {
+            let data = data.clone();
+            thread::spawn(move || {
+                let mut won = 0;
+                for _ in 0..100 {
+                    loop {
+                        let current = data.get();
+                        if data.compare_and_set(&current, current + 1) {
+                            won += 1;
+                            break;
+                        }
+                    }
+                }
+                won
+            })
+        })
+        .collect();
+
+    let total: i32 = handles.into_iter().map(|h| h.join().unwrap()).sum();
+    assert_eq!(total, 800);
+    assert_eq!(data.get(), 800);
+}
+
+#[test]
+fn test_thread_share_set_if_changed() {
+    let data = share!(String::from("config"));
+    let calls = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+    let counter = calls.clone();
+    let _guard = data.on_change(move |_, _| {
+        counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+    });
+
+    assert!(!data.set_if_changed(String::from("config")));
+    assert_eq!(data.version(), 0);
+    assert!(data.wait_for_change(Duration::from_millis(10)));
+
+    assert!(data.set_if_changed(String::from("reloaded")));
+    assert_eq!(data.version(), 1);
+    assert_eq!(data.get(), "reloaded");
+    assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_thread_share_replace_and_take() {
+    let data = share!(vec![1, 2]);
+
+    assert_eq!(data.replace(vec![3]), vec![1, 2]);
+    assert_eq!(data.take(), vec![3]);
+    assert!(data.get().is_empty());
+    assert_eq!(data.version(), 2);
+}
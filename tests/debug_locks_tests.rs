@@ -0,0 +1,164 @@
+#![cfg(feature = "debug-locks")]
+
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc;
+use std::sync::{Arc, Barrier};
+use std::thread;
+use std::time::Duration;
+use thread_share::debug_locks::{self, LockKind};
+use thread_share::{ArcThreadShareLocked, Error, SimpleShare, ThreadShare, combine, share};
+
+fn panic_message<F: FnOnce()>(f: F) -> String {
+    let payload = panic::catch_unwind(AssertUnwindSafe(f)).expect_err("expected a panic");
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => payload.downcast_ref::<&str>().unwrap().to_string(),
+    }
+}
+
+#[test]
+fn test_update_inside_read_panics() {
+    let config = ThreadShare::new(0).with_name("config");
+
+    let message = thread::Builder::new()
+        .name("handler".to_string())
+        .spawn(move || panic_message(|| config.read(|_| config.update(|x| *x += 1))))
+        .unwrap()
+        .join()
+        .unwrap();
+
+    assert_eq!(
+        message,
+        "thread 'handler' tried to take a write lock on share 'config' while already holding a read lock on it"
+    );
+}
+
+#[test]
+fn test_nested_read_does_not_panic() {
+    let data = share!(7).with_name("counter");
+    assert_eq!(data.read(|_| data.get()), 7);
+    assert_eq!(data.read(|_| data.read_guard().to_string()), "7");
+
+    // An upgradable read inside a read only conflicts once upgraded
+    data.read(|_| {
+        let upgradable = data.upgradable_read();
+        assert_eq!(*upgradable, 7);
+    });
+}
+
+#[test]
+fn test_read_inside_write_guard_panics() {
+    let data = share!(vec![1]).with_name("items");
+    let message = panic_message(|| {
+        let mut items = data.write_guard();
+        items.push(data.get().len() as i32);
+    });
+    assert!(message.contains("while already holding a write lock"));
+
+    // The panic released the lock and the bookkeeping
+    data.update(|v| v.push(2));
+    assert_eq!(data.read(|v| v.len()), 2);
+}
+
+#[test]
+fn test_upgrade_while_reading_panics() {
+    let data = share!(0).with_name("balance");
+    let message = panic_message(|| {
+        let upgradable = data.upgradable_read();
+        data.read(|_| {
+            let _ = upgradable.upgrade();
+        });
+    });
+    assert!(message.contains("tried to take a write lock on share 'balance'"));
+}
+
+#[test]
+fn test_allowed_patterns_do_not_panic() {
+    let data = share!(1);
+
+    // Plain reads next to an upgradable read
+    let upgradable = data.upgradable_read();
+    assert_eq!(data.get(), 1);
+    *upgradable.upgrade() += 1;
+
+    // Non-blocking and timed acquisitions report instead of deadlocking
+    data.read(|_| {
+        assert!(matches!(
+            data.try_write(|x| *x += 1),
+            Err(Error::WouldBlock)
+        ));
+        assert!(matches!(
+            data.write_for(Duration::from_millis(5), |x| *x += 1),
+            Err(Error::Timeout { .. })
+        ));
+    });
+
+    // Combining a share with itself takes the second lock recursively
+    let doubled = combine(&data, &data, |a, b| a + b);
+    assert_eq!(doubled.get(), 4);
+}
+
+#[test]
+fn test_other_share_types_are_checked() {
+    let simple = SimpleShare::new(0).with_name("simple");
+    let message = panic_message(|| {
+        simple.update(|_| simple.set(1));
+    });
+    assert!(message.contains("share 'simple'"));
+
+    let locked = ArcThreadShareLocked::new(0).with_name("locked");
+    let message = panic_message(|| {
+        locked.write(|_| {
+            drop(locked.get_ref());
+        });
+    });
+    assert!(message.contains("tried to take a read lock on share 'locked'"));
+}
+
+#[test]
+fn test_deadlock_between_threads_is_reported() {
+    let (report_tx, report_rx) = mpsc::channel();
+    let report_tx = std::sync::Mutex::new(report_tx);
+    debug_locks::set_deadlock_handler(move |report| {
+        let _ = report_tx.lock().unwrap().send(report.clone());
+    });
+
+    let accounts = ThreadShare::new(0).with_name("accounts");
+    let ledger = ThreadShare::new(0).with_name("ledger");
+    let barrier = Arc::new(Barrier::new(2));
+
+    for (name, first, second) in [
+        ("transfer", accounts.clone(), ledger.clone()),
+        ("audit", ledger.clone(), accounts.clone()),
+    ] {
+        let barrier = barrier.clone();
+        thread::Builder::new()
+            .name(name.to_string())
+            .spawn(move || {
+                first.update(|_| {
+                    barrier.wait();
+                    second.update(|_| {});
+                });
+            })
+            .unwrap();
+    }
+
+    let report = report_rx.recv_timeout(Duration::from_secs(10)).unwrap();
+    assert_eq!(report.cycles, 1);
+
+    let transfer = report
+        .threads
+        .iter()
+        .find(|t| t.thread == "transfer")
+        .unwrap();
+    assert_eq!(transfer.waiting_for.share, "ledger");
+    assert_eq!(transfer.waiting_for.kind, LockKind::Write);
+    assert_eq!(transfer.holding[0].share, "accounts");
+
+    let audit = report.threads.iter().find(|t| t.thread == "audit").unwrap();
+    assert_eq!(audit.waiting_for.share, "accounts");
+    assert_eq!(audit.holding[0].share, "ledger");
+
+    let text = report.to_string();
+    assert!(text.contains("thread 'audit' waits for a write lock on share 'accounts'"));
+}
@@ -0,0 +1,212 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+use thread_share::{combine, combine_all, enhanced_share, share};
+
+#[test]
+fn test_map_follows_source() {
+    let data = share!(vec![1, 2, 3]);
+    let sum = data.map(|v| v.iter().sum::<i32>());
+
+    assert_eq!(sum.get(), 6);
+    data.update(|v| v.push(4));
+    assert_eq!(sum.get(), 10);
+    data.set(vec![]);
+    assert_eq!(sum.get(), 0);
+}
+
+#[test]
+fn test_map_recomputes_lazily() {
+    let data = share!(1);
+    let computations = Arc::new(AtomicUsize::new(0));
+
+    let counter = computations.clone();
+    let doubled = data.map(move |x| {
+        counter.fetch_add(1, Ordering::SeqCst);
+        x * 2
+    });
+
+    // Nothing is computed until the value is read
+    assert_eq!(computations.load(Ordering::SeqCst), 0);
+
+    assert_eq!(doubled.get(), 2);
+    assert_eq!(doubled.get(), 2);
+    assert_eq!(doubled.read(|x| *x), 2);
+    assert_eq!(computations.load(Ordering::SeqCst), 1);
+
+    // Several changes between reads cause a single recomputation
+    data.set(2);
+    data.set(3);
+    assert_eq!(doubled.get(), 6);
+    assert_eq!(computations.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn test_clones_share_cache() {
+    let data = share!(5);
+    let computations = Arc::new(AtomicUsize::new(0));
+
+    let counter = computations.clone();
+    let derived = data.map(move |x| {
+        counter.fetch_add(1, Ordering::SeqCst);
+        *x
+    });
+    let clone = derived.clone();
+
+    assert_eq!(derived.get(), 5);
+    assert_eq!(clone.get(), 5);
+    assert_eq!(computations.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_combine_two_sources() {
+    let width = share!(3);
+    let height = share!(4);
+    let computations = Arc::new(AtomicUsize::new(0));
+
+    let counter = computations.clone();
+    let area = combine(&width, &height, move |w, h| {
+        counter.fetch_add(1, Ordering::SeqCst);
+        w * h
+    });
+
+    assert_eq!(area.get(), 12);
+    width.set(5);
+    assert_eq!(area.get(), 20);
+    height.set(2);
+    assert_eq!(area.get(), 10);
+    assert_eq!(area.get(), 10);
+    assert_eq!(computations.load(Ordering::SeqCst), 3);
+    assert_eq!(area.version(), 2);
+}
+
+#[test]
+fn test_combine_same_share_twice() {
+    let data = share!(7);
+    let squared = combine(&data, &data, |a, b| a * b);
+
+    assert_eq!(squared.get(), 49);
+    data.set(3);
+    assert_eq!(squared.get(), 9);
+}
+
+#[test]
+fn test_derived_of_derived() {
+    let data = share!(vec![1, 2, 3]);
+    let sum = data.map(|v| v.iter().sum::<i32>());
+    let label = sum.map(|sum| format!("total: {}", sum));
+
+    assert_eq!(label.get(), "total: 6");
+    data.update(|v| v.push(10));
+    assert_eq!(label.get(), "total: 16");
+    assert_eq!(label.version(), 1);
+}
+
+#[test]
+fn test_derived_wait_for_change() {
+    let data = share!(0);
+    let doubled = data.map(|x| x * 2);
+
+    assert!(doubled.wait_for_change(Duration::from_millis(10)));
+
+    let writer = data.clone();
+    let handle = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(20));
+        writer.set(21);
+    });
+
+    assert!(!doubled.wait_for_change(Duration::from_secs(2)));
+    assert_eq!(doubled.get(), 42);
+
+    handle.join().unwrap();
+}
+
+#[test]
+fn test_derived_consistent_under_concurrent_writes() {
+    let data = share!((0, 0));
+    let difference = data.map(|(a, b)| a - b);
+
+    let writer = data.clone();
+    let handle = thread::spawn(move || {
+        for i in 1..=1000 {
+            writer.set((i, i));
+        }
+    });
+
+    for _ in 0..1000 {
+        assert_eq!(difference.get(), 0);
+    }
+    handle.join().unwrap();
+    assert_eq!(difference.get(), 0);
+}
+
+#[test]
+fn test_derived_outlives_dropped_source_handle() {
+    let data = share!(String::from("a"));
+    let len = data.map(|s| s.len());
+    drop(data);
+    assert_eq!(len.get(), 1);
+}
+
+#[tokio::test]
+async fn test_derived_changed() {
+    let width = share!(1);
+    let height = share!(1);
+    let area = combine(&width, &height, |w, h| w * h);
+
+    let writer = height.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        writer.set(8);
+    });
+
+    area.changed().await;
+    assert_eq!(area.get(), 8);
+
+    assert_eq!(area.changed_timeout(Duration::from_millis(10)).await, None);
+}
+
+#[test]
+fn test_enhanced_map() {
+    let data = enhanced_share!(vec![1, 2]);
+    let len = data.map(|v| v.len());
+
+    data.spawn("writer", |data| data.update(|v| v.push(3)))
+        .expect("Failed to spawn writer");
+    data.join_all().expect("Failed to join");
+
+    assert_eq!(len.get(), 3);
+}
+
+#[test]
+fn test_combine_all_sources() {
+    let north = share!(1);
+    let south = share!(2);
+    let west = share!(3);
+    let calls = Arc::new(AtomicUsize::new(0));
+
+    let counter = calls.clone();
+    let labels = combine_all(&[&west, &north, &south, &west], move |values| {
+        counter.fetch_add(1, Ordering::SeqCst);
+        values.iter().map(|v| v.to_string()).collect::<Vec<_>>().join(",")
+    });
+
+    // Values are passed in the order of the sources
+    assert_eq!(labels.get(), "3,1,2,3");
+    assert_eq!(labels.get(), "3,1,2,3");
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    south.set(20);
+    assert_eq!(labels.version(), 1);
+    assert_eq!(labels.get(), "3,1,20,3");
+    west.set(30);
+    assert_eq!(labels.get(), "30,1,20,30");
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+fn test_combine_all_empty() {
+    let count = combine_all::<i32, _, _>(&[], |values| values.len());
+    assert_eq!(count.get(), 0);
+}
@@ -0,0 +1,107 @@
+use std::error::Error as _;
+use std::time::Duration;
+use thread_share::{Error, ThreadManager, enhanced_share, share};
+
+#[test]
+fn test_join_all_reports_worker_panic() {
+    let data = enhanced_share!(0);
+    data.spawn("crasher", |_| panic!("boom")).unwrap();
+
+    match data.join_all() {
+        Err(Error::WorkerPanicked { name, message }) => {
+            assert_eq!(name, "crasher");
+            assert_eq!(message, "boom");
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_join_all_reports_formatted_panic_message() {
+    let manager = ThreadManager::new();
+    let data = share!(0);
+    manager
+        .spawn("worker", data, |_| panic!("failed at step {}", 3))
+        .unwrap();
+
+    let error = manager.join_all().unwrap_err();
+    assert!(
+        matches!(&error, Error::WorkerPanicked { message, .. } if message == "failed at step 3")
+    );
+    assert_eq!(
+        error.to_string(),
+        "Worker 'worker' panicked: failed at step 3"
+    );
+}
+
+#[test]
+fn test_spawned_threads_are_named() {
+    let data = enhanced_share!(String::new());
+    data.spawn("named-worker", |data| {
+        data.set(
+            std::thread::current()
+                .name()
+                .unwrap_or_default()
+                .to_string(),
+        );
+    })
+    .unwrap();
+    data.join_all().unwrap();
+
+    assert_eq!(data.get(), "named-worker");
+}
+
+#[test]
+fn test_spawn_with_nul_in_name() {
+    let manager = ThreadManager::new();
+    let data = share!(String::new());
+
+    manager
+        .spawn("bad\0name", data.clone(), |data| {
+            data.set(
+                std::thread::current()
+                    .name()
+                    .unwrap_or_default()
+                    .to_string(),
+            );
+        })
+        .unwrap();
+    manager.join_all().unwrap();
+
+    assert_eq!(data.get(), "badname");
+}
+
+#[test]
+fn test_wait_until_timeout_error() {
+    let data = share!(0);
+    let error = data
+        .wait_until(|x| *x > 0, Duration::from_millis(10))
+        .unwrap_err();
+
+    assert!(matches!(error, Error::Timeout { timeout } if timeout == Duration::from_millis(10)));
+    assert!(error.source().is_none());
+}
+
+#[test]
+fn test_error_works_with_question_mark() {
+    fn run() -> Result<(), Box<dyn std::error::Error>> {
+        let data = enhanced_share!(1);
+        data.spawn("worker", |data| data.update(|x| *x += 1))?;
+        data.join_all()?;
+        Ok(())
+    }
+
+    run().unwrap();
+}
+
+#[cfg(feature = "serialize")]
+#[test]
+fn test_deserialize_error_has_source() {
+    let data = share!(vec![1, 2, 3]);
+    let error = data.from_json("not json").unwrap_err();
+
+    assert!(matches!(error, Error::Deserialize(_)));
+    assert!(error.to_string().starts_with("Deserialization failed"));
+    assert!(error.source().is_some());
+    assert_eq!(data.get(), vec![1, 2, 3]);
+}
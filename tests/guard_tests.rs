@@ -0,0 +1,210 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+use thread_share::{SubscribePolicy, enhanced_share, share};
+
+#[test]
+fn test_read_guard() {
+    let data = share!(vec![1, 2, 3]);
+
+    let items = data.read_guard();
+    assert_eq!(items.len(), 3);
+
+    // Other threads can read while the guard is held
+    let reader = data.clone();
+    let last = thread::spawn(move || reader.read_guard()[2]).join().unwrap();
+    assert_eq!(last, 3);
+    drop(items);
+
+    assert_eq!(data.version(), 0);
+}
+
+#[test]
+fn test_write_guard_commits_on_drop() {
+    let data = share!(0);
+
+    {
+        let mut value = data.write_guard();
+        *value += 1;
+        *value *= 10;
+        assert_eq!(data.version(), 0);
+    }
+
+    assert_eq!(data.get(), 10);
+    assert_eq!(data.version(), 1);
+}
+
+#[test]
+fn test_write_guard_early_return() {
+    fn apply(data: &thread_share::ThreadShare<Vec<i32>>, value: i32) -> Result<usize, String> {
+        let mut items = data.write_guard();
+        if value < 0 {
+            return Err(format!("negative value: {}", value));
+        }
+        items.push(value);
+        Ok(items.len())
+    }
+
+    let data = share!(Vec::new());
+    assert_eq!(apply(&data, 1), Ok(1));
+    assert!(apply(&data, -1).is_err());
+    assert_eq!(apply(&data, 2), Ok(2));
+
+    // Every dropped write guard commits, including the early return
+    assert_eq!(data.get(), vec![1, 2]);
+    assert_eq!(data.version(), 3);
+}
+
+#[test]
+fn test_write_guard_wakes_waiters() {
+    let data = share!(0);
+    let waiter = data.clone();
+
+    let handle = thread::spawn(move || waiter.wait_for_change(Duration::from_secs(5)));
+
+    thread::sleep(Duration::from_millis(10));
+    *data.write_guard() = 42;
+
+    assert!(!handle.join().unwrap());
+    assert_eq!(data.get(), 42);
+}
+
+#[test]
+fn test_write_guard_delivers_to_subscribers_and_callbacks() {
+    let data = share!(0);
+    let mut subscription = data.subscribe(SubscribePolicy::Queue(8));
+    let calls = Arc::new(AtomicUsize::new(0));
+
+    let counter = calls.clone();
+    let reader = data.clone();
+    let _guard = data.on_change(move |old_version, value| {
+        // Runs after the lock is released, so the share can be read here
+        assert_eq!(reader.get(), *value);
+        assert_eq!(old_version, 0);
+        counter.fetch_add(1, Ordering::SeqCst);
+    });
+
+    *data.write_guard() = 7;
+
+    let change = subscription.try_recv().unwrap();
+    assert_eq!(change.version, 1);
+    assert_eq!(change.value, 7);
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_upgradable_read_without_upgrade_does_not_notify() {
+    let data = share!(5);
+
+    {
+        let value = data.upgradable_read();
+        assert_eq!(*value, 5);
+        // Plain readers can share the lock with an upgradable reader
+        assert_eq!(data.get(), 5);
+    }
+
+    assert_eq!(data.version(), 0);
+}
+
+#[test]
+fn test_upgradable_read_upgrade() {
+    let data = share!(100);
+
+    let value = data.upgradable_read();
+    if *value >= 30 {
+        let mut value = value.upgrade();
+        *value -= 30;
+    }
+
+    assert_eq!(data.get(), 70);
+    assert_eq!(data.version(), 1);
+}
+
+#[test]
+fn test_upgradable_read_check_then_write_is_race_free() {
+    let data = share!(0);
+    let inserted = Arc::new(AtomicUsize::new(0));
+
+    let handles: Vec<_> = (0..8)
+        .map(|_| {
+            let data = data.clone();
+            let inserted = inserted.clone();
+            thread::spawn(move || {
+                for _ in 0..100 {
+                    let value = data.upgradable_read();
+                    if *value < 50 {
+                        *value.upgrade() += 1;
+                        inserted.fetch_add(1, Ordering::SeqCst);
+                    }
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    assert_eq!(data.get(), 50);
+    assert_eq!(inserted.load(Ordering::SeqCst), 50);
+}
+
+#[test]
+fn test_enhanced_guards() {
+    let data = enhanced_share!(vec![1]);
+
+    data.write_guard().push(2);
+    assert_eq!(data.read_guard().len(), 2);
+
+    let items = data.upgradable_read();
+    if !items.contains(&3) {
+        items.upgrade().push(3);
+    }
+    assert_eq!(data.get(), vec![1, 2, 3]);
+}
+
+#[test]
+fn test_write_guard_skips_commit_on_panic() {
+    let data = share!(vec![1]);
+    let mut changes = data.subscribe(SubscribePolicy::Queue(8));
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let mut items = data.write_guard();
+        items.push(2);
+        panic!("writer failed");
+    }));
+
+    assert!(result.is_err());
+    assert_eq!(data.version(), 0);
+    assert!(changes.try_recv().is_err());
+
+    // The lock was released
+    data.write_guard().push(3);
+    assert_eq!(data.version(), 1);
+}
+
+#[test]
+fn test_write_guard_commits_when_taken_during_unwinding() {
+    struct Cleanup(thread_share::ThreadShare<Vec<i32>>);
+
+    impl Drop for Cleanup {
+        fn drop(&mut self) {
+            // Runs while unwinding, but no panic happens while the guard is held
+            self.0.write_guard().push(-1);
+        }
+    }
+
+    let data = share!(vec![1]);
+    let mut changes = data.subscribe(SubscribePolicy::Queue(8));
+
+    let cleanup = Cleanup(data.clone());
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(move || {
+        let _cleanup = cleanup;
+        panic!("request failed");
+    }));
+
+    assert!(result.is_err());
+    assert_eq!(data.get(), vec![1, -1]);
+    assert_eq!(data.version(), 1);
+    assert_eq!(changes.try_recv().unwrap().value, vec![1, -1]);
+}
@@ -0,0 +1,178 @@
+use std::thread;
+use std::time::{Duration, SystemTime};
+use thread_share::HistoryShare;
+
+#[test]
+fn test_undo_redo() {
+    let data = HistoryShare::new(0, 10);
+    data.set(1);
+    data.set(2);
+    data.update(|x| *x += 1);
+
+    assert_eq!(data.get(), 3);
+    assert!(data.undo());
+    assert_eq!(data.get(), 2);
+    assert!(data.undo());
+    assert!(data.undo());
+    assert_eq!(data.get(), 0);
+    assert!(!data.undo());
+    assert!(!data.can_undo());
+
+    assert!(data.redo());
+    assert_eq!(data.get(), 1);
+    assert!(data.can_redo());
+}
+
+#[test]
+fn test_new_change_discards_redo() {
+    let data = HistoryShare::new(String::from("a"), 10);
+    data.set(String::from("b"));
+    data.set(String::from("c"));
+
+    data.undo();
+    data.set(String::from("d"));
+
+    assert!(!data.redo());
+    let values: Vec<String> = data.history().iter().map(|e| (*e.value).clone()).collect();
+    assert_eq!(values, vec!["a", "b", "d"]);
+}
+
+#[test]
+fn test_capacity_bounds_history() {
+    let data = HistoryShare::new(0, 3);
+    for i in 1..=10 {
+        data.set(i);
+    }
+
+    let values: Vec<i32> = data.history().iter().map(|e| *e.value).collect();
+    assert_eq!(values, vec![7, 8, 9, 10]);
+
+    for _ in 0..3 {
+        assert!(data.undo());
+    }
+    assert!(!data.undo());
+    assert_eq!(data.get(), 7);
+}
+
+#[test]
+fn test_zero_capacity_keeps_only_current() {
+    let data = HistoryShare::new(0, 0);
+    data.set(1);
+    assert!(!data.undo());
+    assert_eq!(data.history().len(), 1);
+}
+
+#[test]
+fn test_history_entries_have_versions_and_timestamps() {
+    let before = SystemTime::now();
+    let data = HistoryShare::new(0, 10);
+    data.set(1);
+    data.set(2);
+
+    let history = data.history();
+    let versions: Vec<u64> = history.iter().map(|e| e.version).collect();
+    assert_eq!(versions, vec![0, 1, 2]);
+    assert!(history.iter().all(|e| e.timestamp >= before));
+    assert!(history.windows(2).all(|w| w[0].timestamp <= w[1].timestamp));
+    assert_eq!(data.position(), 2);
+}
+
+#[test]
+fn test_undo_notifies_waiters() {
+    let data = HistoryShare::new(0, 10);
+    data.set(1);
+
+    let waiter = data.clone();
+    let handle = thread::spawn(move || {
+        let timed_out = waiter.wait_for_change(Duration::from_secs(2));
+        (timed_out, waiter.get())
+    });
+
+    thread::sleep(Duration::from_millis(20));
+    data.undo();
+
+    assert_eq!(handle.join().unwrap(), (false, 0));
+}
+
+#[test]
+fn test_clear_history() {
+    let data = HistoryShare::new(0, 10);
+    data.set(1);
+    data.set(2);
+    data.undo();
+
+    data.clear_history();
+    assert_eq!(data.history().len(), 1);
+    assert_eq!(*data.history()[0].value, 1);
+    assert!(!data.can_undo());
+    assert!(!data.can_redo());
+}
+
+#[test]
+fn test_concurrent_updates_are_recorded_in_order() {
+    let data = HistoryShare::new(0, 1000);
+
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let data = data.clone();
+            thread::spawn(move || {
+                for _ in 0..50 {
+                    data.update(|x| *x += 1);
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    let values: Vec<i32> = data.history().iter().map(|e| *e.value).collect();
+    assert_eq!(values, (0..=200).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_callback_can_use_history_share() {
+    let data = HistoryShare::new(0, 10);
+    let handle = data.clone();
+
+    // Rejects negative values by undoing them
+    let _guard = data.as_share().on_change(move |_, value: &i32| {
+        if *value < 0 {
+            handle.undo();
+        }
+    });
+
+    data.set(5);
+    data.set(-1);
+    assert_eq!(data.get(), 5);
+    assert_eq!(data.position(), 1);
+    assert!(data.can_redo());
+
+    let handle = data.clone();
+    let _guard = data.as_share().on_change(move |_, value: &i32| {
+        if *value == 6 {
+            handle.set(7);
+        }
+    });
+
+    data.set(6);
+    let values: Vec<i32> = data.history().iter().map(|e| *e.value).collect();
+    assert_eq!(values, vec![0, 5, 6, 7]);
+    assert_eq!(data.get(), 7);
+}
+
+#[test]
+fn test_direct_share_writes_bypass_history() {
+    let data = HistoryShare::new(1, 10);
+    data.set(2);
+
+    data.as_share().set(3);
+    assert_eq!(data.get(), 3);
+    assert_eq!(data.history().len(), 2);
+    assert!(!data.can_redo());
+
+    assert!(data.undo());
+    assert_eq!(data.get(), 1);
+    assert!(data.redo());
+    assert_eq!(data.get(), 2);
+}
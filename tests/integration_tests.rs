@@ -85,10 +85,9 @@ fn test_game_state_simulation() {
         }
     });
 
-    // Score management thread using ArcThreadShare
+    // Score management thread using a live ArcThreadShare-style view
     let score_thread = thread::spawn({
-        let arc_data = game_state.as_arc();
-        let arc_share = ArcThreadShare::from_arc(arc_data);
+        let arc_share = game_state.as_arc();
         move || {
             for _ in 0..5 {
                 arc_share.update(|state| {
@@ -124,21 +123,17 @@ fn test_game_state_simulation() {
     // Verify final game state
     let final_state = game_state.get();
     assert!(final_state.player_health <= 50);
-    // Note: score and level changes are in independent ArcThreadShare copies
-    // so they won't affect the main game_state
-    assert_eq!(final_state.score, 0); // Original value
-    assert_eq!(final_state.level, 1); // Original value
-                                      // inventory_thread uses as_arc_locked, so changes should be visible
+    // as_arc is a live view, so score and level changes reach game_state
+    assert_eq!(final_state.score, 500);
+    assert_eq!(final_state.level, 2);
+    // inventory_thread uses as_arc_locked, so changes should be visible
     assert_eq!(final_state.inventory.len(), 6); // 1 initial + 5 new
     assert!(!final_state.is_game_over); // Health should not reach 0
 
-    // Check the independent copies
-    let arc_data = game_state.as_arc();
-    let arc_share = ArcThreadShare::from_arc(arc_data);
-    let arc_state = arc_share.get();
-    // as_arc creates independent copy, so score and level should be original values
-    assert_eq!(arc_state.score, 0);
-    assert_eq!(arc_state.level, 1);
+    // An ArcThreadShare built from a copy stays independent
+    let arc_share = ArcThreadShare::new(game_state.get());
+    arc_share.update(|state| state.score = 0);
+    assert_eq!(game_state.get().score, 500);
 }
 
 #[test]
@@ -196,7 +191,7 @@ fn test_concurrent_readers_writers() {
 
     // All values should be between 0 and 4 (writer IDs)
     for &value in &final_data {
-        assert!(value >= 0 && value <= 4);
+        assert!((0..=4).contains(&value));
     }
 }
 
@@ -0,0 +1,184 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use thread_share::{ArcThreadShare, share};
+
+/// Counts the bytes allocated and not yet freed by the current thread
+struct CountingAllocator;
+
+thread_local! {
+    static LIVE: Cell<isize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = LIVE.try_with(|live| live.set(live.get() + layout.size() as isize));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        let _ = LIVE.try_with(|live| live.set(live.get() - layout.size() as isize));
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Runs `f` and returns the bytes it left allocated on this thread
+fn leaked<F: FnOnce()>(f: F) -> isize {
+    // The first access registers this thread with arc-swap (and with the
+    // lock registry under `debug-locks`), which allocates once for good
+    ArcThreadShare::new(0).get();
+    share!(0).update(|x| *x += 1);
+
+    let before = LIVE.with(Cell::get);
+    f();
+    LIVE.with(Cell::get) - before
+}
+
+/// Value that counts how many instances are alive
+struct Tracked(Arc<AtomicUsize>);
+
+impl Tracked {
+    fn new(alive: &Arc<AtomicUsize>) -> Self {
+        alive.fetch_add(1, Ordering::SeqCst);
+        Self(Arc::clone(alive))
+    }
+}
+
+impl Clone for Tracked {
+    fn clone(&self) -> Self {
+        Self::new(&self.0)
+    }
+}
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn test_drop_frees_value() {
+    let leak = leaked(|| {
+        let share = ArcThreadShare::new(vec![0u8; 1024]);
+        assert_eq!(share.get().len(), 1024);
+    });
+    assert_eq!(leak, 0);
+}
+
+#[test]
+fn test_set_frees_replaced_values() {
+    let leak = leaked(|| {
+        let share = ArcThreadShare::new(String::from("first"));
+        for i in 0..100 {
+            share.set(format!("value {i}"));
+        }
+        assert_eq!(share.get(), "value 99");
+    });
+    assert_eq!(leak, 0);
+}
+
+#[test]
+fn test_increment_and_add_free_replaced_values() {
+    let leak = leaked(|| {
+        let share = ArcThreadShare::new(0u64);
+        for _ in 0..100 {
+            share.increment();
+            share.add(2);
+        }
+        assert_eq!(share.get(), 300);
+    });
+    assert_eq!(leak, 0);
+}
+
+#[test]
+fn test_update_fetch_update_and_swap_free_values() {
+    let leak = leaked(|| {
+        let share = ArcThreadShare::new(vec![1, 2, 3]);
+        share.update(|v| v.push(4));
+        let _ = share.fetch_update(|v| Some(v.iter().rev().copied().collect()));
+        let _ = share.compare_exchange(&vec![4, 3, 2, 1], vec![0]);
+        assert_eq!(share.swap(vec![]), vec![0]);
+    });
+    assert_eq!(leak, 0);
+}
+
+#[test]
+fn test_clone_and_drop_sequences() {
+    let leak = leaked(|| {
+        let share = ArcThreadShare::new(String::from("shared"));
+        let clones: Vec<_> = (0..10).map(|_| share.clone()).collect();
+        let weak = share.downgrade();
+
+        drop(share);
+        for (i, clone) in clones.iter().enumerate() {
+            clone.set(format!("clone {i}"));
+        }
+        drop(clones);
+
+        assert!(weak.upgrade().is_none());
+    });
+    assert_eq!(leak, 0);
+}
+
+#[test]
+fn test_as_arc_frees_view() {
+    let leak = leaked(|| {
+        let data = share!(vec![0u8; 256]);
+        let view = data.as_arc();
+        view.update(|v| v[0] = 1);
+        drop(data);
+        drop(view);
+    });
+    assert_eq!(leak, 0);
+}
+
+#[test]
+fn test_try_unwrap_and_into_inner() {
+    let leak = leaked(|| {
+        let share = ArcThreadShare::new(vec![1u8; 64]);
+        let clone = share.clone();
+
+        let Err(share) = share.try_unwrap() else {
+            panic!("share is still cloned");
+        };
+        assert!(clone.into_inner().is_none());
+        assert_eq!(share.try_unwrap().ok(), Some(vec![1u8; 64]));
+
+        let share = ArcThreadShare::new(String::from("last"));
+        share.set(String::from("final"));
+        assert_eq!(share.into_inner().as_deref(), Some("final"));
+    });
+    assert_eq!(leak, 0);
+}
+
+#[test]
+fn test_every_value_dropped_once() {
+    let alive = Arc::new(AtomicUsize::new(0));
+    let share = ArcThreadShare::new(Tracked::new(&alive));
+
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let share = share.clone();
+            let alive = Arc::clone(&alive);
+            thread::spawn(move || {
+                for _ in 0..100 {
+                    share.set(Tracked::new(&alive));
+                    share.update(|_| {});
+                    let _ = share.swap(Tracked::new(&alive));
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    assert_eq!(alive.load(Ordering::SeqCst), 1);
+    drop(share);
+    assert_eq!(alive.load(Ordering::SeqCst), 0);
+}
@@ -0,0 +1,116 @@
+#![cfg(feature = "lock-warnings")]
+
+use std::sync::{Mutex, Once};
+use std::thread;
+use std::time::Duration;
+use thread_share::lock_warnings::{self, HoldWarning, LockKind};
+use thread_share::{SimpleShare, ThreadShare};
+
+static WARNINGS: Mutex<Vec<HoldWarning>> = Mutex::new(Vec::new());
+
+/// Installs a hook collecting warnings, and returns those for `share`
+fn warnings_for(share: &str) -> Vec<HoldWarning> {
+    install_hook();
+    WARNINGS
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|warning| warning.share == share)
+        .cloned()
+        .collect()
+}
+
+fn install_hook() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        lock_warnings::set_hook(|warning| WARNINGS.lock().unwrap().push(warning.clone()));
+    });
+}
+
+#[test]
+fn test_slow_update_is_reported() {
+    install_hook();
+    let config = ThreadShare::new(0)
+        .with_name("slow-update")
+        .with_hold_threshold(Duration::from_millis(10));
+
+    thread::Builder::new()
+        .name("loader".to_string())
+        .spawn(move || {
+            config.update(|x| {
+                thread::sleep(Duration::from_millis(30));
+                *x += 1;
+            });
+        })
+        .unwrap()
+        .join()
+        .unwrap();
+
+    let warnings = warnings_for("slow-update");
+    assert_eq!(warnings.len(), 1);
+    let warning = &warnings[0];
+    assert_eq!(warning.thread, "loader");
+    assert_eq!(warning.kind, LockKind::Write);
+    assert_eq!(warning.threshold, Duration::from_millis(10));
+    assert!(warning.held >= Duration::from_millis(30));
+    assert!(
+        warning
+            .to_string()
+            .starts_with("thread 'loader' held the write lock on share 'slow-update' for ")
+    );
+}
+
+#[test]
+fn test_fast_operations_are_not_reported() {
+    install_hook();
+    let data = ThreadShare::new(vec![1, 2, 3])
+        .with_name("fast")
+        .with_hold_threshold(Duration::from_millis(50));
+
+    data.update(|v| v.push(4));
+    data.read(|v| assert_eq!(v.len(), 4));
+    data.write_guard().push(5);
+
+    assert!(warnings_for("fast").is_empty());
+}
+
+#[test]
+fn test_slow_read_and_guard_are_reported() {
+    install_hook();
+    let data = ThreadShare::new(0)
+        .with_name("slow-read")
+        .with_hold_threshold(Duration::from_millis(10));
+
+    data.read(|_| thread::sleep(Duration::from_millis(20)));
+    {
+        let _guard = data.write_guard();
+        thread::sleep(Duration::from_millis(20));
+    }
+
+    let kinds: Vec<LockKind> = warnings_for("slow-read").iter().map(|w| w.kind).collect();
+    assert_eq!(kinds, vec![LockKind::Read, LockKind::Write]);
+}
+
+#[test]
+fn test_global_threshold_and_per_share_override() {
+    install_hook();
+    assert_eq!(lock_warnings::threshold(), Some(Duration::from_millis(100)));
+
+    let global = SimpleShare::new(0).with_name("global");
+    let exempt = ThreadShare::new(0)
+        .with_name("exempt")
+        .with_hold_threshold(Duration::MAX);
+
+    lock_warnings::set_threshold(Some(Duration::from_millis(10)));
+    global.update(|_| thread::sleep(Duration::from_millis(20)));
+    exempt.update(|_| thread::sleep(Duration::from_millis(20)));
+
+    lock_warnings::set_threshold(None);
+    assert_eq!(lock_warnings::threshold(), None);
+    global.update(|_| thread::sleep(Duration::from_millis(20)));
+
+    lock_warnings::set_threshold(Some(Duration::from_millis(100)));
+
+    assert_eq!(warnings_for("global").len(), 1);
+    assert!(warnings_for("exempt").is_empty());
+}
@@ -176,7 +176,7 @@ fn test_arc_thread_share_locked_custom_struct() {
     let initial = locked_share.get();
     assert_eq!(initial.value, 10);
     assert_eq!(initial.text, "test");
-    assert_eq!(initial.flag, false);
+    assert!(!initial.flag);
 
     // Test update
     locked_share.update(|data| {
@@ -189,7 +189,7 @@ fn test_arc_thread_share_locked_custom_struct() {
     let updated = locked_share.get();
     assert_eq!(updated.value, 20);
     assert_eq!(updated.text, "updated");
-    assert_eq!(updated.flag, true);
+    assert!(updated.flag);
 }
 
 #[test]
@@ -12,11 +12,11 @@ fn test_share_macro_basic_types() {
 
     // Test with boolean
     let bool_share = share!(true);
-    assert_eq!(bool_share.get(), true);
+    assert!(bool_share.get());
 
     // Test with float
-    let float_share = share!(3.14);
-    assert_eq!(float_share.get(), 3.14);
+    let float_share = share!(2.5);
+    assert_eq!(float_share.get(), 2.5);
 }
 
 #[test]
@@ -56,7 +56,7 @@ fn test_share_macro_custom_struct() {
     let result = struct_share.get();
     assert_eq!(result.value, 42);
     assert_eq!(result.text, "test");
-    assert_eq!(result.flag, true);
+    assert!(result.flag);
 }
 
 #[test]
@@ -212,11 +212,11 @@ fn test_simple_share_macro_basic_types() {
 
     // Test with boolean
     let bool_share = simple_share!(true);
-    assert_eq!(bool_share.get(), true);
+    assert!(bool_share.get());
 
     // Test with float
-    let float_share = simple_share!(3.14);
-    assert_eq!(float_share.get(), 3.14);
+    let float_share = simple_share!(2.5);
+    assert_eq!(float_share.get(), 2.5);
 }
 
 #[test]
@@ -256,7 +256,7 @@ fn test_simple_share_macro_custom_struct() {
     let result = struct_share.get();
     assert_eq!(result.value, 42);
     assert_eq!(result.text, "test");
-    assert_eq!(result.flag, true);
+    assert!(result.flag);
 }
 
 #[test]
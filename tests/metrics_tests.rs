@@ -0,0 +1,136 @@
+#![cfg(feature = "metrics")]
+
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+use thread_share::{ArcThreadShareLocked, LockStats, share, simple_share, transaction};
+
+#[test]
+fn test_thread_share_counts() {
+    let data = share!(0);
+    assert_eq!(data.stats(), LockStats::default());
+
+    data.set(1);
+    data.update(|x| *x += 1);
+    data.write(|x| *x += 1);
+    assert_eq!(data.get(), 3);
+    data.read(|x| assert_eq!(*x, 3));
+
+    let stats = data.stats();
+    assert_eq!(stats.writes.count, 3);
+    assert_eq!(stats.reads.count, 2);
+    assert_eq!(stats.notifications, 3);
+    assert!(stats.writes.max_hold <= stats.writes.total_hold);
+    assert!(stats.reads.max_wait <= stats.reads.total_wait);
+}
+
+#[test]
+fn test_stats_are_shared_by_clones() {
+    let data = share!(0);
+    let clone = data.clone();
+
+    let handle = thread::spawn(move || clone.update(|x| *x += 1));
+    handle.join().unwrap();
+    data.update(|x| *x += 1);
+
+    assert_eq!(data.stats().writes.count, 2);
+    assert_eq!(data.stats().notifications, 2);
+}
+
+#[test]
+fn test_wait_and_hold_times_under_contention() {
+    let data = share!(0);
+    let holder = data.clone();
+    let (locked_tx, locked_rx) = mpsc::channel();
+
+    let handle = thread::spawn(move || {
+        holder.write(|x| {
+            locked_tx.send(()).unwrap();
+            thread::sleep(Duration::from_millis(50));
+            *x += 1;
+        });
+    });
+
+    locked_rx.recv().unwrap();
+    data.update(|x| *x += 1);
+    handle.join().unwrap();
+
+    let stats = data.stats();
+    assert_eq!(stats.writes.count, 2);
+    assert!(stats.writes.max_hold >= Duration::from_millis(50));
+    assert!(stats.writes.max_wait >= Duration::from_millis(20));
+    assert!(stats.writes.total_wait >= stats.writes.max_wait);
+}
+
+#[test]
+fn test_failed_try_lock_is_not_counted() {
+    let data = share!(0);
+
+    data.read(|_| {
+        assert!(data.try_write(|x| *x = 1).is_err());
+        assert!(
+            data.write_for(Duration::from_millis(5), |x| *x = 1)
+                .is_err()
+        );
+    });
+    data.try_write(|x| *x = 2).unwrap();
+
+    let stats = data.stats();
+    assert_eq!(stats.writes.count, 1);
+    assert_eq!(stats.reads.count, 1);
+    assert_eq!(stats.notifications, 1);
+}
+
+#[test]
+fn test_guards_and_transactions_are_counted() {
+    let from = share!(10);
+    let to = share!(0);
+
+    {
+        let balance = from.upgradable_read();
+        if *balance >= 5 {
+            *balance.upgrade() -= 5;
+        }
+    }
+    drop(to.read_guard());
+    transaction((&from, &to), |(from, to)| {
+        *from -= 1;
+        *to += 1;
+    });
+
+    let from_stats = from.stats();
+    assert_eq!(from_stats.reads.count, 1);
+    assert_eq!(from_stats.writes.count, 2);
+    assert_eq!(from_stats.notifications, 2);
+
+    let to_stats = to.stats();
+    assert_eq!(to_stats.reads.count, 1);
+    assert_eq!(to_stats.writes.count, 1);
+    assert_eq!(to_stats.notifications, 1);
+}
+
+#[test]
+fn test_simple_share_counts() {
+    let data = simple_share!(0);
+    data.set(1);
+    data.update(|x| *x += 1);
+    assert_eq!(data.get(), 2);
+
+    let stats = data.stats();
+    assert_eq!(stats.writes.count, 2);
+    assert_eq!(stats.reads.count, 1);
+    assert_eq!(stats.notifications, 0);
+}
+
+#[test]
+fn test_arc_thread_share_locked_counts() {
+    let data = ArcThreadShareLocked::new(vec![1]);
+    data.update(|v| v.push(2));
+    data.get_mut().push(3);
+    assert_eq!(data.get_ref().len(), 3);
+    assert_eq!(data.read(|v| v.len()), 3);
+
+    let stats = data.stats();
+    assert_eq!(stats.writes.count, 2);
+    assert_eq!(stats.reads.count, 2);
+}
@@ -0,0 +1,147 @@
+use std::thread;
+use std::time::{Duration, Instant};
+use thread_share::{NotifyPolicy, enhanced_share, share};
+
+#[test]
+fn test_default_policy_is_immediate() {
+    let data = share!(0);
+    assert_eq!(data.notify_policy(), NotifyPolicy::Immediate);
+
+    let data = data.with_notify_policy(NotifyPolicy::Coalesce(Duration::from_millis(10)));
+    assert_eq!(
+        data.clone().notify_policy(),
+        NotifyPolicy::Coalesce(Duration::from_millis(10))
+    );
+}
+
+#[test]
+fn test_coalesce_limits_wakeups() {
+    let data = share!(0).with_notify_policy(NotifyPolicy::Coalesce(Duration::from_millis(50)));
+    let writer = data.clone();
+
+    let handle = thread::spawn(move || {
+        let start = Instant::now();
+        while start.elapsed() < Duration::from_millis(300) {
+            writer.update(|x| *x += 1);
+            thread::sleep(Duration::from_micros(100));
+        }
+        writer.update(|x| *x = -1);
+    });
+
+    let mut wakeups = 0;
+    while data.get() != -1 {
+        data.wait_for_change_forever();
+        wakeups += 1;
+    }
+    handle.join().unwrap();
+
+    // About one wakeup per interval instead of one per update
+    assert!(wakeups <= 15, "woken {} times", wakeups);
+}
+
+#[test]
+fn test_coalesce_first_change_is_immediate() {
+    let data = share!(0).with_notify_policy(NotifyPolicy::Coalesce(Duration::from_secs(10)));
+    let seen = data.version();
+
+    data.set(1);
+    assert_eq!(
+        data.wait_for_change_since(seen, Duration::from_secs(1)),
+        Some(1)
+    );
+}
+
+#[test]
+fn test_coalesce_delivers_latest_version() {
+    let data = share!(0).with_notify_policy(NotifyPolicy::Coalesce(Duration::from_millis(30)));
+    data.set(1);
+    let seen = data.version();
+
+    for i in 2..=10 {
+        data.set(i);
+    }
+
+    // Held back until the interval ends, then delivered as one change
+    assert_eq!(
+        data.wait_for_change_since(seen, Duration::from_millis(5)),
+        None
+    );
+    assert_eq!(
+        data.wait_for_change_since(seen, Duration::from_secs(5)),
+        Some(10)
+    );
+    assert_eq!(data.get(), 10);
+}
+
+#[test]
+fn test_debounce_waits_for_quiet_period() {
+    let data = share!(0).with_notify_policy(NotifyPolicy::Debounce(Duration::from_millis(50)));
+    let writer = data.clone();
+    let seen = data.version();
+
+    let handle = thread::spawn(move || {
+        for _ in 0..10 {
+            writer.update(|x| *x += 1);
+            thread::sleep(Duration::from_millis(10));
+        }
+    });
+
+    let start = Instant::now();
+    let version = data.wait_for_change_since(seen, Duration::from_secs(5));
+    handle.join().unwrap();
+
+    assert_eq!(version, Some(10));
+    assert_eq!(data.get(), 10);
+    assert!(start.elapsed() >= Duration::from_millis(100));
+}
+
+#[test]
+fn test_derived_not_stale_while_pending() {
+    let data = share!(1).with_notify_policy(NotifyPolicy::Debounce(Duration::from_secs(10)));
+    let doubled = data.map(|x| x * 2);
+
+    data.set(5);
+    assert_eq!(doubled.get(), 10);
+    assert_eq!(doubled.version(), 1);
+
+    // Waiters on the derived share follow the source's policy
+    assert!(doubled.wait_for_change(Duration::from_millis(20)));
+}
+
+#[test]
+fn test_changing_policy_publishes_pending() {
+    let data = share!(0).with_notify_policy(NotifyPolicy::Debounce(Duration::from_secs(10)));
+    let seen = data.version();
+    data.set(1);
+
+    let data = data.with_notify_policy(NotifyPolicy::Immediate);
+    assert_eq!(
+        data.wait_for_change_since(seen, Duration::from_millis(10)),
+        Some(1)
+    );
+}
+
+#[test]
+fn test_enhanced_share_policy() {
+    let data =
+        enhanced_share!(0).with_notify_policy(NotifyPolicy::Debounce(Duration::from_millis(5)));
+    assert_eq!(
+        data.notify_policy(),
+        NotifyPolicy::Debounce(Duration::from_millis(5))
+    );
+}
+
+#[tokio::test]
+async fn test_changed_woken_by_timer() {
+    let data = share!(0).with_notify_policy(NotifyPolicy::Debounce(Duration::from_millis(20)));
+    let writer = data.clone();
+
+    tokio::spawn(async move {
+        for i in 1..=5 {
+            writer.set(i);
+        }
+    });
+
+    assert_eq!(data.changed().await, 5);
+    assert_eq!(data.get(), 5);
+}
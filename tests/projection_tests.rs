@@ -0,0 +1,129 @@
+use std::thread;
+use std::time::Duration;
+use thread_share::{enhanced_share, share};
+
+#[derive(Clone, Debug, PartialEq, Default)]
+struct Stats {
+    requests: u64,
+    errors: u64,
+}
+
+#[derive(Clone, Debug, Default)]
+struct AppState {
+    stats: Stats,
+    title: String,
+}
+
+#[test]
+fn test_project_reads_and_writes_parent() {
+    let state = share!(AppState::default());
+    let stats = state.project(|s| &s.stats, |s| &mut s.stats);
+
+    stats.update(|s| s.requests += 1);
+    assert_eq!(state.read(|s| s.stats.requests), 1);
+
+    state.update(|s| s.stats.errors = 2);
+    assert_eq!(stats.read(|s| s.errors), 2);
+    assert_eq!(
+        stats.get(),
+        Stats {
+            requests: 1,
+            errors: 2
+        }
+    );
+}
+
+#[test]
+fn test_project_version_follows_field_only() {
+    let state = share!(AppState::default());
+    let stats = state.project(|s| &s.stats, |s| &mut s.stats);
+
+    state.update(|s| s.title.push_str("dashboard"));
+    stats.update(|_| {});
+    assert_eq!(state.version(), 2);
+    assert_eq!(stats.version(), 0);
+
+    state.update(|s| s.stats.requests += 1);
+    assert_eq!(stats.version(), 1);
+}
+
+#[test]
+fn test_project_waiters_ignore_other_fields() {
+    let state = share!(AppState::default());
+    let stats = state.project(|s| &s.stats, |s| &mut s.stats);
+
+    let writer = state.clone();
+    let handle = thread::spawn(move || {
+        for _ in 0..5 {
+            writer.update(|s| s.title.push('.'));
+        }
+        thread::sleep(Duration::from_millis(20));
+        writer.update(|s| s.stats.requests = 10);
+    });
+
+    assert!(!stats.wait_for_change(Duration::from_secs(5)));
+    assert_eq!(stats.get().requests, 10);
+    handle.join().unwrap();
+}
+
+#[test]
+fn test_project_clones_share_projection() {
+    let state = share!((1, 2));
+    let first = state.project(|s| &s.0, |s| &mut s.0);
+    let clone = first.clone();
+
+    clone.set(5);
+    assert_eq!(first.get(), 5);
+    assert!(!first.wait_for_change(Duration::from_millis(10)));
+    assert!(first.wait_for_change(Duration::from_millis(10)));
+}
+
+#[test]
+fn test_project_guard_writes_are_detected() {
+    let state = share!((1, 2));
+    let second = state.project(|s| &s.1, |s| &mut s.1);
+
+    state.write_guard().1 = 3;
+    assert_eq!(second.version(), 1);
+
+    state.write_guard().0 = 3;
+    assert_eq!(second.version(), 1);
+}
+
+#[test]
+fn test_project_keeps_parent_alive() {
+    let state = share!((1, String::from("a")));
+    let weak = state.downgrade();
+    let name = state.project(|s| &s.1, |s| &mut s.1);
+
+    drop(state);
+    assert!(weak.upgrade().is_some());
+    assert_eq!(name.get(), "a");
+
+    drop(name);
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_enhanced_share_project() {
+    let data = enhanced_share!((0, 0));
+    let first = data.project(|s| &s.0, |s| &mut s.0);
+
+    first.update(|x| *x += 1);
+    assert_eq!(data.get(), (1, 0));
+}
+
+#[tokio::test]
+async fn test_project_changed() {
+    let state = share!((0, 0));
+    let first = state.project(|s| &s.0, |s| &mut s.0);
+
+    let writer = state.clone();
+    tokio::spawn(async move {
+        writer.update(|s| s.1 = 1);
+        writer.update(|s| s.0 = 1);
+    });
+
+    assert_eq!(first.changed().await, 1);
+    assert_eq!(first.get(), 1);
+}
@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+use thread_share::{SnapshotShare, SubscribePolicy};
+
+#[test]
+fn test_snapshot_is_shared() {
+    let data = SnapshotShare::new(vec![1, 2, 3]);
+
+    let a = data.snapshot();
+    let b = data.snapshot();
+    assert!(Arc::ptr_eq(&a, &b));
+    assert_eq!(*a, vec![1, 2, 3]);
+}
+
+#[test]
+fn test_snapshot_unchanged_by_later_writes() {
+    let data = SnapshotShare::new(String::from("v1"));
+    let old = data.snapshot();
+
+    data.update(|s| s.push_str("-edited"));
+    data.set(String::from("v2"));
+
+    assert_eq!(*old, "v1");
+    assert_eq!(data.get(), "v2");
+    assert_eq!(data.version(), 2);
+}
+
+#[test]
+fn test_update_clones_only_when_snapshot_held() {
+    let data = SnapshotShare::new(vec![0u8; 1024]);
+
+    let ptr = Arc::as_ptr(&data.snapshot());
+    data.update(|v| v[0] = 1);
+    assert_eq!(Arc::as_ptr(&data.snapshot()), ptr);
+
+    let held = data.snapshot();
+    data.update(|v| v[0] = 2);
+    assert_ne!(Arc::as_ptr(&data.snapshot()), Arc::as_ptr(&held));
+    assert_eq!(held[0], 1);
+    assert_eq!(data.read(|v| v[0]), 2);
+}
+
+#[test]
+fn test_write_returns_result() {
+    let data = SnapshotShare::new(HashMap::from([("a", 1)]));
+
+    let previous = data.write(|m| m.insert("a", 2));
+    assert_eq!(previous, Some(1));
+    assert_eq!(data.snapshot()["a"], 2);
+}
+
+#[test]
+fn test_wait_for_change() {
+    let data = SnapshotShare::new(0);
+    let writer = data.clone();
+
+    let handle = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(20));
+        writer.update(|x| *x += 1);
+    });
+
+    assert!(!data.wait_for_change(Duration::from_secs(5)));
+    assert_eq!(*data.snapshot(), 1);
+    handle.join().unwrap();
+}
+
+#[test]
+fn test_wait_until_returns_snapshot() {
+    let data = SnapshotShare::new(0);
+    let writer = data.clone();
+
+    let handle = thread::spawn(move || {
+        for _ in 0..5 {
+            writer.update(|x| *x += 1);
+        }
+    });
+
+    let snapshot = data
+        .wait_until(|x| *x == 5, Duration::from_secs(5))
+        .unwrap();
+    assert_eq!(*snapshot, 5);
+    assert!(
+        data.wait_until(|x| *x < 0, Duration::from_millis(10))
+            .is_err()
+    );
+    handle.join().unwrap();
+}
+
+#[test]
+fn test_subscribe_and_callbacks_receive_snapshots() {
+    let data = SnapshotShare::new(vec![1]);
+    let mut subscription = data.subscribe(SubscribePolicy::Queue(4));
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    let _guard = data.on_change(move |_, snapshot: &Arc<Vec<i32>>| {
+        counter.fetch_add(snapshot.len(), Ordering::SeqCst);
+    });
+
+    data.update(|v| v.push(2));
+
+    let change = subscription.recv().unwrap();
+    assert_eq!(*change.value, vec![1, 2]);
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_changed() {
+    let data = SnapshotShare::new(0);
+    let writer = data.clone();
+
+    tokio::spawn(async move {
+        writer.set(7);
+    });
+
+    assert_eq!(data.changed().await, 1);
+    assert_eq!(*data.snapshot(), 7);
+}
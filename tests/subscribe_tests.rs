@@ -0,0 +1,224 @@
+use std::sync::mpsc::{RecvTimeoutError, TryRecvError};
+use std::thread;
+use std::time::Duration;
+use thread_share::{SubscribePolicy, ThreadShare, enhanced_share, share};
+
+#[test]
+fn test_subscribe_queue_delivers_every_change() {
+    let data = share!(0);
+    let changes = data.subscribe(SubscribePolicy::Queue(1000));
+
+    let writer = data.clone();
+    let handle = thread::spawn(move || {
+        for i in 1..=100 {
+            writer.set(i);
+        }
+    });
+
+    let received: Vec<_> = changes.take(100).collect();
+    handle.join().unwrap();
+
+    for (i, change) in received.iter().enumerate() {
+        assert_eq!(change.value, i as i32 + 1);
+        assert_eq!(change.version, i as u64 + 1);
+        assert_eq!(change.lagged, 0);
+    }
+}
+
+#[test]
+fn test_subscribe_latest_keeps_newest() {
+    let data = share!(0);
+    let mut changes = data.subscribe(SubscribePolicy::Latest);
+
+    data.set(1);
+    data.set(2);
+    data.update(|x| *x += 1);
+
+    let change = changes.try_recv().unwrap();
+    assert_eq!(change.value, 3);
+    assert_eq!(change.version, 3);
+    assert_eq!(change.lagged, 2);
+    assert_eq!(changes.try_recv().unwrap_err(), TryRecvError::Empty);
+}
+
+#[test]
+fn test_subscribe_queue_reports_lag_when_full() {
+    let data = share!(0);
+    let mut changes = data.subscribe(SubscribePolicy::Queue(2));
+
+    for i in 1..=5 {
+        data.set(i);
+    }
+
+    let first = changes.try_recv().unwrap();
+    assert_eq!(first.value, 4);
+    assert_eq!(first.lagged, 3);
+
+    let second = changes.try_recv().unwrap();
+    assert_eq!(second.value, 5);
+    assert_eq!(second.lagged, 0);
+}
+
+#[test]
+fn test_subscribe_ignores_changes_before_subscribing() {
+    let data = share!(0);
+    data.set(1);
+
+    let mut changes = data.subscribe(SubscribePolicy::Queue(4));
+    assert_eq!(changes.try_recv().unwrap_err(), TryRecvError::Empty);
+
+    data.write(|x| *x = 2);
+    let change = changes.try_recv().unwrap();
+    assert_eq!(change.version, 2);
+    assert_eq!(change.lagged, 0);
+}
+
+#[test]
+fn test_subscribe_versions_for_non_clone_type() {
+    struct NotClone(i32);
+
+    let data = ThreadShare::new(NotClone(0));
+    let mut versions = data.subscribe_versions(SubscribePolicy::Queue(4));
+
+    data.update(|v| v.0 = 1);
+    data.update(|v| v.0 = 2);
+
+    assert_eq!(versions.try_recv().unwrap().version, 1);
+    assert_eq!(versions.try_recv().unwrap().version, 2);
+    assert_eq!(data.read(|v| v.0), 2);
+}
+
+#[test]
+fn test_subscription_ends_when_share_dropped() {
+    let data = share!(0);
+    let mut changes = data.subscribe(SubscribePolicy::Queue(4));
+    let clone = data.clone();
+
+    data.set(1);
+    drop(data);
+    clone.set(2);
+    drop(clone);
+
+    assert_eq!(changes.recv().unwrap().value, 1);
+    assert_eq!(changes.recv().unwrap().value, 2);
+    assert!(changes.recv().is_none());
+    assert_eq!(
+        changes.recv_timeout(Duration::from_millis(10)).unwrap_err(),
+        RecvTimeoutError::Disconnected
+    );
+}
+
+#[test]
+fn test_subscription_recv_timeout() {
+    let data = share!(0);
+    let mut changes = data.subscribe(SubscribePolicy::Latest);
+
+    assert_eq!(
+        changes.recv_timeout(Duration::from_millis(20)).unwrap_err(),
+        RecvTimeoutError::Timeout
+    );
+
+    let writer = data.clone();
+    let handle = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(20));
+        writer.set(7);
+    });
+
+    let change = changes.recv_timeout(Duration::from_secs(2)).unwrap();
+    assert_eq!(change.value, 7);
+
+    handle.join().unwrap();
+}
+
+#[test]
+fn test_dropped_subscription_does_not_block_writers() {
+    let data = share!(0);
+    let changes = data.subscribe(SubscribePolicy::Queue(1));
+    drop(changes);
+
+    for i in 0..100 {
+        data.set(i);
+    }
+    assert_eq!(data.get(), 99);
+}
+
+#[test]
+fn test_subscription_into_receiver() {
+    let data = share!(String::new());
+    let receiver = data.subscribe(SubscribePolicy::Queue(16)).into_receiver();
+
+    data.set(String::from("a"));
+    data.update(|s| s.push('b'));
+
+    let first = receiver.recv_timeout(Duration::from_secs(1)).unwrap();
+    let second = receiver.recv_timeout(Duration::from_secs(1)).unwrap();
+    assert_eq!(first.value, "a");
+    assert_eq!(second.value, "ab");
+
+    drop(data);
+    assert!(receiver.recv_timeout(Duration::from_secs(1)).is_err());
+}
+
+#[test]
+fn test_enhanced_subscribe() {
+    let data = enhanced_share!(0);
+    let changes = data.subscribe(SubscribePolicy::Queue(16));
+
+    data.spawn("writer", |data| {
+        for i in 1..=3 {
+            data.set(i);
+        }
+    })
+    .expect("Failed to spawn writer");
+
+    let values: Vec<i32> = changes.take(3).map(|change| change.value).collect();
+    assert_eq!(values, vec![1, 2, 3]);
+
+    data.join_all().expect("Failed to join");
+}
+
+#[cfg(feature = "async")]
+mod stream {
+    use futures_core::Stream;
+    use std::future::poll_fn;
+    use std::pin::Pin;
+    use std::time::Duration;
+    use thread_share::{Change, SubscribePolicy, Subscription, share};
+
+    async fn next<V>(subscription: &mut Subscription<V>) -> Option<Change<V>> {
+        poll_fn(|cx| Pin::new(&mut *subscription).poll_next(cx)).await
+    }
+
+    #[tokio::test]
+    async fn test_subscription_stream() {
+        let data = share!(0);
+        let mut changes = data.subscribe(SubscribePolicy::Queue(16));
+
+        let writer = data.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            writer.set(1);
+            writer.set(2);
+        });
+
+        assert_eq!(next(&mut changes).await.unwrap().value, 1);
+        assert_eq!(next(&mut changes).await.unwrap().value, 2);
+
+        drop(data);
+        assert!(next(&mut changes).await.is_none());
+    }
+
+    #[async_std::test]
+    async fn test_subscription_stream_with_async_std() {
+        let data = share!(String::from("a"));
+        let mut changes = data.subscribe(SubscribePolicy::Latest);
+
+        let writer = data.clone();
+        async_std::task::spawn(async move {
+            async_std::task::sleep(Duration::from_millis(20)).await;
+            writer.update(|s| s.push('b'));
+        });
+
+        assert_eq!(next(&mut changes).await.unwrap().value, "ab");
+    }
+}
@@ -0,0 +1,153 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+use thread_share::{share, transaction};
+
+#[test]
+fn test_transaction_two_shares() {
+    let a = share!(10);
+    let b = share!(String::from("x"));
+
+    let result = transaction((&a, &b), |(a, b)| {
+        *a += 1;
+        b.push('y');
+        *a * 2
+    });
+
+    assert_eq!(result, 22);
+    assert_eq!(a.get(), 11);
+    assert_eq!(b.get(), "xy");
+}
+
+#[test]
+fn test_transaction_eight_shares() {
+    let shares: Vec<_> = (0..8).map(|i| share!(i)).collect();
+    let [s0, s1, s2, s3, s4, s5, s6, s7] = &shares[..] else {
+        unreachable!()
+    };
+
+    transaction(
+        (s0, s1, s2, s3, s4, s5, s6, s7),
+        |(v0, v1, v2, v3, v4, v5, v6, v7)| {
+            for v in [v0, v1, v2, v3, v4, v5, v6, v7] {
+                *v *= 10;
+            }
+        },
+    );
+
+    let values: Vec<i32> = shares.iter().map(|s| s.get()).collect();
+    assert_eq!(values, vec![0, 10, 20, 30, 40, 50, 60, 70]);
+}
+
+#[test]
+fn test_transaction_notifies_each_share_once() {
+    let a = share!(0);
+    let b = share!(0);
+    let c = share!(0);
+
+    transaction((&a, &b, &c), |(a, b, c)| {
+        *a += 1;
+        *a += 1;
+        *b += 1;
+        *c += 1;
+    });
+
+    assert_eq!(a.version(), 1);
+    assert_eq!(b.version(), 1);
+    assert_eq!(c.version(), 1);
+}
+
+#[test]
+fn test_transaction_wakes_waiters_and_callbacks() {
+    let a = share!(0);
+    let b = share!(0);
+    let calls = Arc::new(AtomicUsize::new(0));
+
+    let counter = calls.clone();
+    let reader = a.clone();
+    let _guard = b.on_change(move |_, _| {
+        // Runs after the transaction released its locks
+        reader.get();
+        counter.fetch_add(1, Ordering::SeqCst);
+    });
+
+    let waiter = a.clone();
+    let handle = thread::spawn(move || waiter.wait_for_change(Duration::from_secs(2)));
+
+    thread::sleep(Duration::from_millis(20));
+    transaction((&a, &b), |(a, b)| {
+        *a = 1;
+        *b = 1;
+    });
+
+    assert!(!handle.join().unwrap());
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_transactions_in_opposite_order_do_not_deadlock() {
+    let a = share!(0i64);
+    let b = share!(0i64);
+
+    let handles: Vec<_> = (0..4)
+        .map(|i| {
+            let (a, b) = (a.clone(), b.clone());
+            thread::spawn(move || {
+                for _ in 0..1000 {
+                    if i % 2 == 0 {
+                        transaction((&a, &b), |(a, b)| {
+                            *a += 1;
+                            *b -= 1;
+                        });
+                    } else {
+                        transaction((&b, &a), |(b, a)| {
+                            *b += 1;
+                            *a -= 1;
+                        });
+                    }
+                }
+            })
+        })
+        .collect();
+
+    // Observers never see the invariant broken
+    for _ in 0..1000 {
+        transaction((&a, &b), |(a, b)| assert_eq!(*a + *b, 0));
+    }
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(a.get() + b.get(), 0);
+}
+
+#[test]
+#[should_panic(expected = "more than once")]
+fn test_transaction_same_share_twice_panics() {
+    let a = share!(0);
+    let alias = a.clone();
+    transaction((&a, &alias), |(_, _)| {});
+}
+
+#[test]
+fn test_transaction_panic_releases_locks() {
+    let a = share!(0);
+    let b = share!(0);
+
+    let (a2, b2) = (a.clone(), b.clone());
+    let result = thread::spawn(move || {
+        transaction((&a2, &b2), |(a, _)| {
+            *a = 1;
+            panic!("boom");
+        })
+    })
+    .join();
+
+    assert!(result.is_err());
+    // Locks were released and nothing was committed
+    assert_eq!(a.get(), 1);
+    assert_eq!(a.version(), 0);
+    b.set(2);
+    assert_eq!(b.get(), 2);
+}
@@ -0,0 +1,128 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use thread_share::{share, SubscribePolicy};
+
+#[test]
+fn test_view_sees_share_writes() {
+    let data = share!(String::from("a"));
+    let view = data.as_arc();
+
+    data.update(|s| s.push('b'));
+    assert_eq!(view.get(), "ab");
+    assert_eq!(view.read(|s| s.len()), 2);
+}
+
+#[test]
+fn test_view_writes_reach_share() {
+    let data = share!(vec![1]);
+    let view = data.as_arc();
+
+    view.set(vec![2]);
+    view.update(|v| v.push(3));
+    assert_eq!(view.write(|v| v.len()), 2);
+    assert_eq!(data.get(), vec![2, 3]);
+    assert_eq!(data.version(), 3);
+}
+
+#[test]
+fn test_view_numeric_operations() {
+    let data = share!(10u32);
+    let view = data.as_arc();
+
+    view.increment();
+    view.add(5);
+    assert_eq!(view.swap(0), 16);
+    assert_eq!(data.get(), 0);
+}
+
+#[test]
+fn test_view_fetch_update_and_compare_exchange() {
+    let data = share!(5);
+    let view = data.as_arc();
+
+    assert_eq!(view.fetch_update(|x| Some(x * 2)), Ok(5));
+    assert_eq!(view.fetch_update(|_| None), Err(10));
+    assert_eq!(view.compare_exchange(&3, 7), Err(10));
+    assert_eq!(data.version(), 1);
+
+    assert_eq!(view.compare_exchange(&10, 7), Ok(10));
+    assert_eq!(data.get(), 7);
+    assert_eq!(data.version(), 2);
+}
+
+#[test]
+fn test_view_writes_wake_waiters() {
+    let data = share!(0);
+    let view = data.as_arc();
+
+    let handle = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(20));
+        view.increment();
+    });
+
+    assert!(!data.wait_for_change(Duration::from_secs(5)));
+    assert_eq!(data.get(), 1);
+    handle.join().unwrap();
+}
+
+#[test]
+fn test_view_writes_reach_subscribers_and_callbacks() {
+    let data = share!(0);
+    let view = data.as_arc();
+    let mut subscription = data.subscribe(SubscribePolicy::Queue(4));
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    let _guard = data.on_change(move |_, _| {
+        counter.fetch_add(1, Ordering::SeqCst);
+    });
+
+    view.set(1);
+    let _ = view.swap(2);
+
+    assert_eq!(subscription.recv().unwrap().value, 1);
+    assert_eq!(subscription.recv().unwrap().value, 2);
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn test_view_concurrent_with_share() {
+    let data = share!(0u64);
+
+    let handles: Vec<_> = (0..4)
+        .map(|i| {
+            let data = data.clone();
+            let view = data.as_arc();
+            thread::spawn(move || {
+                for _ in 0..250 {
+                    if i % 2 == 0 {
+                        view.increment();
+                    } else {
+                        data.update(|x| *x += 1);
+                    }
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    assert_eq!(data.get(), 1000);
+    assert_eq!(data.version(), 1000);
+}
+
+#[test]
+fn test_view_keeps_share_alive() {
+    let data = share!(1);
+    let weak = data.downgrade();
+    let view = data.as_arc();
+
+    drop(data);
+    assert_eq!(view.as_share().get(), 1);
+    assert!(weak.upgrade().is_some());
+
+    drop(view);
+    assert!(weak.upgrade().is_none());
+}
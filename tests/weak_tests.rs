@@ -0,0 +1,171 @@
+use std::thread;
+use std::time::Duration;
+use thread_share::{ArcThreadShare, ArcThreadShareLocked, SimpleShare, SubscribePolicy, share};
+
+#[test]
+fn test_upgrade_after_drop() {
+    let data = share!(vec![1, 2, 3]);
+    let weak = data.downgrade();
+
+    let upgraded = weak.upgrade().unwrap();
+    upgraded.update(|v| v.push(4));
+    assert_eq!(data.get(), vec![1, 2, 3, 4]);
+
+    drop(upgraded);
+    drop(data);
+    assert!(weak.upgrade().is_none());
+    assert_eq!(weak.strong_count(), 0);
+}
+
+#[test]
+fn test_counts() {
+    let data = share!(0);
+    assert_eq!(data.strong_count(), 1);
+    assert_eq!(data.weak_count(), 0);
+
+    let clone = data.clone();
+    let weak = data.downgrade();
+    let weak_clone = weak.clone();
+    assert_eq!(data.strong_count(), 2);
+    assert_eq!(data.weak_count(), 2);
+    assert_eq!(weak.strong_count(), 2);
+
+    drop(clone);
+    drop(weak_clone);
+    assert_eq!(weak.strong_count(), 1);
+    assert_eq!(weak.weak_count(), 1);
+}
+
+#[test]
+fn test_worker_exits_when_owner_drops() {
+    let data = share!(0);
+    let weak = data.downgrade();
+
+    let worker = thread::spawn(move || {
+        let mut iterations = 0;
+        while let Some(data) = weak.upgrade() {
+            data.wait_for_change(Duration::from_millis(10));
+            iterations += 1;
+        }
+        iterations
+    });
+
+    data.set(1);
+    thread::sleep(Duration::from_millis(30));
+    drop(data);
+
+    let iterations = worker.join().unwrap();
+    assert!(iterations > 0);
+}
+
+#[test]
+fn test_upgraded_handle_notifies_waiters() {
+    let data = share!(0);
+    let weak = data.downgrade();
+
+    let writer = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(20));
+        weak.upgrade().unwrap().set(5);
+    });
+
+    assert!(!data.wait_for_change(Duration::from_secs(5)));
+    assert_eq!(data.get(), 5);
+    writer.join().unwrap();
+}
+
+#[test]
+fn test_upgraded_handle_waits_for_next_change() {
+    let data = share!(0);
+    data.set(1);
+
+    let upgraded = data.downgrade().upgrade().unwrap();
+    assert!(upgraded.wait_for_change(Duration::from_millis(10)));
+}
+
+#[test]
+fn test_subscription_closes_with_only_weak_handles() {
+    let data = share!(0);
+    let weak = data.downgrade();
+    let mut subscription = data.subscribe(SubscribePolicy::Queue(16));
+
+    data.set(1);
+    drop(data);
+
+    assert_eq!(subscription.recv().map(|c| c.value), Some(1));
+    assert!(subscription.recv().is_none());
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_simple_share_weak() {
+    let data = SimpleShare::new(1);
+    let weak = data.downgrade();
+    assert_eq!(data.weak_count(), 1);
+
+    weak.upgrade().unwrap().set(2);
+    assert_eq!(data.get(), 2);
+
+    drop(data);
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_locked_weak() {
+    let data = ArcThreadShareLocked::new(1);
+    let weak = data.downgrade();
+    assert_eq!(data.strong_count(), 1);
+
+    weak.upgrade().unwrap().update(|x| *x += 1);
+    assert_eq!(data.get(), 2);
+
+    drop(data);
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_atomic_weak() {
+    let data = ArcThreadShare::new(1);
+    let weak = data.downgrade();
+    assert_eq!(data.weak_count(), 1);
+
+    weak.upgrade().unwrap().increment();
+    assert_eq!(data.get(), 2);
+
+    drop(data);
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_upgrade_with_only_locked_holder() {
+    let data = share!(1);
+    let weak = data.downgrade();
+    let locked = data.as_arc_locked();
+    let _subscription = data.subscribe(SubscribePolicy::Queue(4));
+
+    drop(data);
+    assert_eq!(weak.strong_count(), 1);
+
+    // The data is still alive, so upgrading succeeds
+    let first = weak.upgrade().unwrap();
+    let second = weak.upgrade().unwrap();
+    assert_eq!(first.get(), 1);
+
+    // Both upgraded handles share the recreated registries
+    let mut changes = first.subscribe(SubscribePolicy::Queue(4));
+    second.set(2);
+    assert_eq!(changes.recv().unwrap().value, 2);
+    assert_eq!(*locked.read(), 2);
+
+    drop((first, second, locked));
+    assert_eq!(weak.strong_count(), 0);
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_weak_handles_cross_threads() {
+    let data = share!(String::from("shared"));
+    let weak = data.downgrade();
+
+    let handle = thread::spawn(move || weak.upgrade().map(|data| data.get()));
+    assert_eq!(handle.join().unwrap().as_deref(), Some("shared"));
+}
@@ -3,6 +3,7 @@ use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::Duration;
 use thread_share::worker_manager::WorkerManager;
+use thread_share::Error;
 
 #[test]
 fn test_worker_manager_new() {
@@ -49,8 +50,9 @@ fn test_add_duplicate_worker() {
     });
     
     let result2 = manager.add_worker("test_worker", handle2);
-    assert!(result2.is_err());
-    assert_eq!(result2.unwrap_err(), "Worker 'test_worker' already exists");
+    let error = result2.unwrap_err();
+    assert!(matches!(&error, Error::WorkerExists { name } if name == "test_worker"));
+    assert_eq!(error.to_string(), "Worker 'test_worker' already exists");
     
     assert_eq!(manager.active_workers(), 1);
 }
@@ -82,8 +84,9 @@ fn test_remove_nonexistent_worker() {
     let manager = WorkerManager::new_with_threads(threads);
     
     let result = manager.remove_worker("nonexistent");
-    assert!(result.is_err());
-    assert_eq!(result.unwrap_err(), "Worker 'nonexistent' not found");
+    let error = result.unwrap_err();
+    assert!(matches!(&error, Error::WorkerNotFound { name } if name == "nonexistent"));
+    assert_eq!(error.to_string(), "Worker 'nonexistent' not found");
 }
 
 #[test]